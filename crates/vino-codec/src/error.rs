@@ -21,6 +21,9 @@ pub enum CodecError {
   /// Error when deserialization from a raw value.
   #[error("Failed to deserialize payload: {0}")]
   DeserializationError(serde_value::DeserializerError),
+  /// Error when the data of a MessagePack timestamp extension is invalid.
+  #[error("Invalid MessagePack timestamp: {0}")]
+  InvalidTimestamp(String),
   /// Error returned when requesting a field of the payload that doesn't exist.
   #[error("Input data for port '{0}' missing")]
   MissingInput(String),
@@ -90,6 +90,9 @@ pub mod messagepack;
 /// Serialization/deserialization implementations to raw [serde_value]s.
 pub mod raw;
 
+/// MessagePack timestamp extensions and the calendar math to read and write them as RFC3339.
+pub mod timestamp;
+
 /// The crate's Result type.
 pub(crate) type Result<T> = std::result::Result<T, error::CodecError>;
 
@@ -0,0 +1,158 @@
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+use crate::error::CodecError;
+use crate::Result;
+
+/// The MessagePack extension type reserved for timestamps.
+pub const TIMESTAMP_EXT: i8 = -1;
+
+/// The serde name MessagePack serializers use for extension types.
+pub const EXT_STRUCT_NAME: &str = "_ExtStruct";
+
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Encode seconds and nanoseconds since the Unix epoch as the data of a MessagePack timestamp extension,
+/// using the smallest of the 32, 64 or 96 bit layouts.
+#[must_use]
+pub fn encode(seconds: i64, nanos: u32) -> Vec<u8> {
+  if seconds >> 34 == 0 {
+    let data = (u64::from(nanos) << 34) | seconds as u64;
+    if data >> 32 == 0 {
+      (data as u32).to_be_bytes().to_vec()
+    } else {
+      data.to_be_bytes().to_vec()
+    }
+  } else {
+    let mut data = nanos.to_be_bytes().to_vec();
+    data.extend(seconds.to_be_bytes());
+    data
+  }
+}
+
+/// Decode the data of a MessagePack timestamp extension into seconds and nanoseconds since the Unix epoch.
+pub fn decode(data: &[u8]) -> Result<(i64, u32)> {
+  let (seconds, nanos) = match data.len() {
+    4 => (i64::from(u32::from_be_bytes(slice(data))), 0),
+    8 => {
+      let data = u64::from_be_bytes(slice(data));
+      ((data & 0x0003_ffff_ffff) as i64, (data >> 34) as u32)
+    }
+    12 => (i64::from_be_bytes(slice(&data[4..])), u32::from_be_bytes(slice(data))),
+    len => return Err(CodecError::InvalidTimestamp(format!("extension data of {} bytes", len))),
+  };
+  if nanos >= NANOS_PER_SECOND {
+    return Err(CodecError::InvalidTimestamp(format!(
+      "{} nanoseconds is more than a second",
+      nanos
+    )));
+  }
+  Ok((seconds, nanos))
+}
+
+fn slice<const N: usize>(data: &[u8]) -> [u8; N] {
+  let mut bytes = [0; N];
+  bytes.copy_from_slice(&data[..N]);
+  bytes
+}
+
+/// Format seconds and nanoseconds since the Unix epoch as RFC3339 in UTC, with 3, 6 or 9 fractional digits
+/// when there is a fraction, e.g. `2021-11-04T11:30:00.500Z`.
+#[must_use]
+pub fn to_rfc3339(seconds: i64, nanos: u32) -> String {
+  let secs = seconds.rem_euclid(SECONDS_PER_DAY);
+  let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+  let fraction = match nanos {
+    0 => String::new(),
+    n if n % 1_000_000 == 0 => format!(".{:03}", n / 1_000_000),
+    n if n % 1000 == 0 => format!(".{:06}", n / 1000),
+    n => format!(".{:09}", n),
+  };
+  format!(
+    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+    year,
+    month,
+    day,
+    secs / 3600,
+    secs % 3600 / 60,
+    secs % 60,
+    fraction
+  )
+}
+
+/// Days since 1970-01-01 for a date in the proleptic Gregorian calendar.
+#[must_use]
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let year_of_era = year.rem_euclid(400);
+  let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+  era * 146_097 + day_of_era - 719_468
+}
+
+/// The date in the proleptic Gregorian calendar that is `days` after 1970-01-01, as year, month and day.
+#[must_use]
+pub fn civil_from_days(days: i64) -> (i64, i64, i64) {
+  let days = days + 719_468;
+  let era = days.div_euclid(146_097);
+  let day_of_era = days.rem_euclid(146_097);
+  let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 {
+    shifted_month + 3
+  } else {
+    shifted_month - 9
+  };
+  let year = year_of_era + era * 400 + i64::from(month <= 2);
+  (year, month, day)
+}
+
+/// The binary data of a MessagePack extension.
+///
+/// MessagePack serializers write an extension as a newtype named [EXT_STRUCT_NAME] around a tuple of its type
+/// and an [ExtData]. Formats without binary data may hand it back as a sequence of bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtData(pub Vec<u8>);
+
+impl Serialize for ExtData {
+  fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(&self.0)
+  }
+}
+
+impl<'de> Deserialize<'de> for ExtData {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+    deserializer.deserialize_bytes(ExtDataVisitor)
+  }
+}
+
+struct ExtDataVisitor;
+
+impl<'de> Visitor<'de> for ExtDataVisitor {
+  type Value = ExtData;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("bytes")
+  }
+
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<Self::Value, E> {
+    Ok(ExtData(v.to_vec()))
+  }
+
+  fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> std::result::Result<Self::Value, E> {
+    Ok(ExtData(v))
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<Self::Value, A::Error> {
+    let mut bytes = Vec::new();
+    while let Some(byte) = seq.next_element()? {
+      bytes.push(byte);
+    }
+    Ok(ExtData(bytes))
+  }
+}
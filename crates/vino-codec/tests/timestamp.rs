@@ -0,0 +1,35 @@
+use vino_codec::timestamp::{civil_from_days, days_from_civil, decode, encode, to_rfc3339};
+use vino_codec::Error;
+
+#[test]
+pub fn timestamp_layouts() -> Result<(), Error> {
+  let cases = [
+    (1_636_025_400, 0, 4),
+    (1_636_025_400, 500_000_000, 8),
+    (-1, 999_999_999, 12),
+    (1 << 34, 0, 12),
+  ];
+  for (seconds, nanos, len) in cases {
+    let data = encode(seconds, nanos);
+    assert_eq!(data.len(), len, "{}.{}", seconds, nanos);
+    assert_eq!(decode(&data)?, (seconds, nanos));
+  }
+  assert!(matches!(decode(&[0; 5]), Err(Error::InvalidTimestamp(_))));
+  assert!(matches!(
+    decode(&encode(0, 1_000_000_000)),
+    Err(Error::InvalidTimestamp(_))
+  ));
+  Ok(())
+}
+
+#[test]
+pub fn rfc3339() {
+  assert_eq!(to_rfc3339(0, 0), "1970-01-01T00:00:00Z");
+  assert_eq!(to_rfc3339(1_636_025_400, 500_000_000), "2021-11-04T11:30:00.500Z");
+  assert_eq!(to_rfc3339(-1, 1000), "1969-12-31T23:59:59.000001Z");
+  assert_eq!(to_rfc3339(951_782_400, 1), "2000-02-29T00:00:00.000000001Z");
+  for days in [-719_468, -1, 0, 11_016, 2_932_896] {
+    let (year, month, day) = civil_from_days(days);
+    assert_eq!(days_from_civil(year, month, day), days);
+  }
+}
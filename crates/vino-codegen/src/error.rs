@@ -0,0 +1,29 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+/// The error type for Vino code generation.
+pub enum CodegenError {
+  /// Error used when the signature file could not be read.
+  #[error("Could not read {0}: {1}")]
+  Read(String, String),
+
+  /// Error used when the generated file could not be written.
+  #[error("Could not write {0}: {1}")]
+  Write(String, String),
+
+  /// Error used when the signature JSON could not be parsed.
+  #[error("Invalid provider signature: {0}")]
+  Signature(String),
+
+  /// Error used when a signature references a type that is not in the provider's types.
+  #[error("Unresolved type reference '{0}'")]
+  UnresolvedReference(String),
+
+  /// Error used when a map's key type has no hashable Rust equivalent.
+  #[error("Map key type '{0}' is not hashable")]
+  InvalidMapKey(String),
+
+  /// Error used when two names in the same scope convert to the same identifier.
+  #[error("'{0}' and '{1}' both generate the identifier '{2}'")]
+  IdentifierCollision(String, String, String),
+}
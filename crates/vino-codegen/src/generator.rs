@@ -0,0 +1,497 @@
+use std::path::Path;
+
+use vino_types::{ComponentSignature, MapWrapper, ProviderSignature, StructMap, TypeMap, TypeSignature};
+
+use crate::rust::{check_idents, pascal_ident, rust_type, snake_ident};
+use crate::Error;
+
+/// Path to generated structs from inside a component module.
+const COMPONENT_TYPES_PATH: &str = "super::super::types::";
+
+const NATIVE: &str = "vino_provider::native::prelude";
+const WASM: &str = "vino_provider::wasm";
+
+/// The kind of provider to generate code for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+  /// A native provider built on `vino_provider::native`.
+  Native,
+  /// A WebAssembly provider built on `vino_provider::wasm`.
+  Wasm,
+}
+
+/// Generates Rust source for a provider from its [ProviderSignature].
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Generator {
+  target: Target,
+  jobs_module: String,
+  context_type: String,
+}
+
+impl Generator {
+  /// Create a new [Generator] for the passed [Target].
+  pub fn new(target: Target) -> Self {
+    Self {
+      target,
+      jobs_module: "crate::jobs".to_owned(),
+      context_type: "crate::Context".to_owned(),
+    }
+  }
+
+  /// Set the module that contains a submodule with a `job` function for every component.
+  pub fn jobs_module<T: AsRef<str>>(mut self, path: T) -> Self {
+    self.jobs_module = path.as_ref().to_owned();
+    self
+  }
+
+  /// Set the context type passed to native jobs. Ignored for [Target::Wasm].
+  pub fn context_type<T: AsRef<str>>(mut self, ty: T) -> Self {
+    self.context_type = ty.as_ref().to_owned();
+    self
+  }
+
+  /// Read a JSON [ProviderSignature] from `input` and write the generated code to `output`.
+  pub fn generate_file<I: AsRef<Path>, O: AsRef<Path>>(&self, input: I, output: O) -> Result<(), Error> {
+    generate_file(input.as_ref(), output.as_ref(), |json| self.generate_json(json))
+  }
+
+  /// Generate code for a JSON [ProviderSignature].
+  pub fn generate_json(&self, json: &str) -> Result<String, Error> {
+    self.generate(&parse_signature(json)?)
+  }
+
+  /// Generate code for a [ProviderSignature].
+  ///
+  /// Output is deterministic: types, components, ports and fields are emitted in the order they are declared.
+  pub fn generate(&self, signature: &ProviderSignature) -> Result<String, Error> {
+    check_idents(signature.components.inner().keys(), snake_ident)?;
+    let mut code = String::from("// This file is generated by vino-codegen. Do not edit it by hand.\n\n");
+    code.push_str(&types_module(&signature.types)?);
+
+    code.push_str("/// The components defined by the provider signature.\npub mod components {\n");
+    for (name, component) in signature.components.ordered() {
+      let module = match self.target {
+        Target::Native => native_component(name, component, &signature.types)?,
+        Target::Wasm => wasm_component(name, component, &signature.types)?,
+      };
+      code.push_str(&module);
+    }
+    code.push_str("}\n\n");
+
+    let names: Vec<&String> = signature
+      .components
+      .ordered()
+      .into_iter()
+      .map(|(name, _)| name)
+      .collect();
+    code.push_str(&match self.target {
+      Target::Native => self.native_dispatcher(&names),
+      Target::Wasm => self.wasm_dispatcher(&names),
+    });
+    Ok(code)
+  }
+
+  fn native_dispatcher(&self, names: &[&String]) -> String {
+    let (context, data) = if names.is_empty() {
+      ("_context", "_data")
+    } else {
+      ("context", "data")
+    };
+    let mut arms = String::new();
+    for name in names {
+      let module = snake_ident(name);
+      arms.push_str(&format!(
+        r#"      {name:?} => {{
+        let inputs = components::{module}::Inputs::from_transport_map(data)
+          .map_err(|e| NativeComponentError::boxed(e.to_string()))?;
+        let mut outputs = components::{module}::Outputs::default();
+        let stream = outputs.stream();
+        {jobs}::{module}::job(inputs, outputs, context).await.map_err(Box::new)?;
+        Ok(stream)
+      }}
+"#,
+        name = name,
+        module = module,
+        jobs = self.jobs_module
+      ));
+    }
+    format!(
+      r#"/// Routes operation names to the provider's components.
+#[derive(Debug, Clone, Copy)]
+pub struct Dispatcher;
+
+#[{p}::async_trait]
+impl {p}::Dispatch for Dispatcher {{
+  type Context = {ctx_ty};
+  async fn dispatch(
+    op: &str,
+    {context}: Self::Context,
+    {data}: {p}::TransportMap,
+  ) -> Result<{p}::TransportStream, Box<{p}::NativeComponentError>> {{
+    use {p}::NativeComponentError;
+    match op {{
+{arms}      _ => Err(NativeComponentError::boxed(format!("Component '{{}}' not found on this provider", op))),
+    }}
+  }}
+}}
+"#,
+      p = NATIVE,
+      ctx_ty = self.context_type,
+      context = context,
+      data = data,
+      arms = arms
+    )
+  }
+
+  fn wasm_dispatcher(&self, names: &[&String]) -> String {
+    let mut arms = String::new();
+    for name in names {
+      let module = snake_ident(name);
+      arms.push_str(&format!(
+        r#"      {name:?} => {{
+        let inputs = components::{module}::Inputs::from_payload(&payload)?;
+        let outputs = components::{module}::Outputs::new(payload.id());
+        {jobs}::{module}::job(inputs, outputs)?;
+      }}
+"#,
+        name = name,
+        module = module,
+        jobs = self.jobs_module
+      ));
+    }
+    let valid = names.iter().map(|n| n.as_str()).collect::<Vec<_>>().join(", ");
+    format!(
+      r#"/// Routes operation names to the provider's components.
+#[derive(Debug, Clone, Copy)]
+pub struct Dispatcher;
+
+impl {p}::Dispatch for Dispatcher {{
+  fn dispatch(op: &str, payload: &[u8]) -> {p}::CallResult {{
+    let payload = {p}::IncomingPayload::from_buffer(payload)?;
+    match op {{
+{arms}      _ => return Err({p}::Error::ComponentNotFound(op.to_owned(), {valid:?}.to_owned())),
+    }}
+    Ok(Vec::new())
+  }}
+}}
+"#,
+      p = WASM,
+      arms = arms,
+      valid = valid
+    )
+  }
+}
+
+/// Parse a JSON [ProviderSignature].
+pub(crate) fn parse_signature(json: &str) -> Result<ProviderSignature, Error> {
+  serde_json::from_str(json).map_err(|e| Error::Signature(e.to_string()))
+}
+
+/// Read a JSON [ProviderSignature] from `input`, generate code from it and write the result to `output`.
+pub(crate) fn generate_file<F>(input: &Path, output: &Path, generate: F) -> Result<(), Error>
+where
+  F: FnOnce(&str) -> Result<String, Error>,
+{
+  let json =
+    std::fs::read_to_string(input).map_err(|e| Error::Read(input.to_string_lossy().to_string(), e.to_string()))?;
+  let code = generate(&json)?;
+  std::fs::write(output, code).map_err(|e| Error::Write(output.to_string_lossy().to_string(), e.to_string()))
+}
+
+/// Doc comment lines for a signature's description, or the fallback when it has none.
+fn doc(description: Option<&String>, fallback: &str, indent: &str) -> String {
+  description
+    .map_or(fallback, String::as_str)
+    .lines()
+    .map(|line| format!("{}/// {}\n", indent, line).replace("/// \n", "///\n"))
+    .collect()
+}
+
+fn types_module(types: &StructMap) -> Result<String, Error> {
+  check_idents(types.inner().keys(), pascal_ident)?;
+  let mut code = String::from("/// The types defined by the provider signature.\npub mod types {\n");
+  for (name, def) in types.ordered() {
+    code.push_str(&format!(
+      "{}{}  pub struct {} {{\n{}  }}\n\n",
+      doc(def.description.as_ref(), &format!("The `{}` type.", name), "  "),
+      DERIVES,
+      pascal_ident(name),
+      fields(&def.fields, types, "", "    ")?
+    ));
+  }
+  code.push_str("}\n\n");
+  Ok(code)
+}
+
+const DERIVES: &str = "  #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]\n";
+
+/// Struct fields for every entry in the [TypeMap], renamed back to their signature name for serde.
+fn fields(map: &TypeMap, types: &StructMap, types_path: &str, indent: &str) -> Result<String, Error> {
+  check_idents(map.inner().keys(), snake_ident)?;
+  let mut code = String::new();
+  for (name, sig) in map.ordered() {
+    let default = if matches!(sig, TypeSignature::Optional { .. }) {
+      ", default"
+    } else {
+      ""
+    };
+    code.push_str(&format!(
+      "{doc}{i}#[serde(rename = {name:?}{default})]\n{i}pub {field}: {ty},\n",
+      doc = doc(
+        map.metadata(name).and_then(|m| m.description.as_ref()),
+        &format!("The `{}` field.", name),
+        indent
+      ),
+      i = indent,
+      name = name,
+      default = default,
+      field = snake_ident(name),
+      ty = rust_type(sig, types, types_path)?
+    ));
+  }
+  Ok(code)
+}
+
+fn component_header(name: &str, component: &ComponentSignature, types: &StructMap) -> Result<String, Error> {
+  check_idents(component.outputs.inner().keys(), snake_ident)?;
+  check_idents(component.outputs.inner().keys(), pascal_ident)?;
+  Ok(format!(
+    r#"{doc}  pub mod {module} {{
+    /// The inputs of the `{name}` component.
+  {derives}    pub struct Inputs {{
+{fields}    }}
+
+"#,
+    doc = doc(
+      component.description.as_ref(),
+      &format!("Generated code for the `{}` component.", name),
+      "  "
+    ),
+    name = name,
+    module = snake_ident(name),
+    derives = DERIVES,
+    fields = fields(&component.inputs, types, COMPONENT_TYPES_PATH, "      ")?
+  ))
+}
+
+fn output_fields(component: &ComponentSignature) -> String {
+  component
+    .outputs
+    .ordered()
+    .into_iter()
+    .map(|(port, _)| {
+      format!(
+        "      /// The `{}` port.\n      pub {}: {}Sender,\n",
+        port,
+        snake_ident(port),
+        pascal_ident(port)
+      )
+    })
+    .collect()
+}
+
+fn native_component(name: &str, component: &ComponentSignature, types: &StructMap) -> Result<String, Error> {
+  let mut code = component_header(name, component, types)?;
+  let map = if component.inputs.is_empty() { "_map" } else { "mut map" };
+  let mut reads = String::new();
+  for (port, sig) in component.inputs.ordered() {
+    let read = if matches!(sig, TypeSignature::Optional { .. }) {
+      format!(
+        "if map.contains({port:?}) {{ map.consume({port:?})? }} else {{ None }}",
+        port = port
+      )
+    } else {
+      format!("map.consume({:?})?", port)
+    };
+    reads.push_str(&format!("          {}: {},\n", snake_ident(port), read));
+  }
+  code.push_str(&format!(
+    r#"    impl Inputs {{
+      /// Read the component's inputs out of a [{p}::TransportMap].
+      pub fn from_transport_map({map}: {p}::TransportMap) -> Result<Self, {p}::TransportError> {{
+        Ok(Self {{
+{reads}        }})
+      }}
+    }}
+
+"#,
+    p = NATIVE,
+    map = map,
+    reads = reads
+  ));
+
+  let mut channels = Vec::new();
+  for (port, sig) in component.outputs.ordered() {
+    let sender = pascal_ident(port);
+    channels.push(format!("&mut self.{}.port", snake_ident(port)));
+    code.push_str(&format!(
+      r#"    /// Sender for the `{port}` output port.
+    #[derive(Debug, Clone)]
+    pub struct {sender}Sender {{
+      port: {p}::PortChannel,
+    }}
+
+    impl Default for {sender}Sender {{
+      fn default() -> Self {{
+        Self {{
+          port: {p}::PortChannel::new({port:?}),
+        }}
+      }}
+    }}
+
+    impl {p}::PortSender for {sender}Sender {{
+      fn get_port(&self) -> Result<&{p}::PortChannel, {p}::ProviderError> {{
+        if self.port.is_closed() {{
+          Err({p}::ProviderError::SendChannelClosed)
+        }} else {{
+          Ok(&self.port)
+        }}
+      }}
+
+      fn get_port_name(&self) -> &str {{
+        &self.port.name
+      }}
+    }}
+
+    impl {sender}Sender {{
+      /// Send a value out of the port.
+      pub fn send_value(&self, value: &{ty}) -> Result<(), {p}::ProviderError> {{
+        {p}::PortSender::send(self, {p}::Payload::success(value))
+      }}
+
+      /// Send a value out of the port then close it.
+      pub fn done_value(&self, value: &{ty}) -> Result<(), {p}::ProviderError> {{
+        {p}::PortSender::done(self, {p}::Payload::success(value))
+      }}
+    }}
+
+"#,
+      p = NATIVE,
+      port = port,
+      sender = sender,
+      ty = rust_type(sig, types, COMPONENT_TYPES_PATH)?
+    ));
+  }
+
+  code.push_str(&format!(
+    r#"    /// The output ports of the `{name}` component.
+    #[derive(Debug, Default)]
+    pub struct Outputs {{
+{fields}    }}
+
+    impl Outputs {{
+      /// Open every output port and merge them into a single stream.
+      pub fn stream(&mut self) -> {p}::TransportStream {{
+        {p}::PortChannel::merge_all(&mut [{channels}])
+      }}
+    }}
+  }}
+
+"#,
+    p = NATIVE,
+    name = name,
+    fields = output_fields(component),
+    channels = channels.join(", ")
+  ));
+  Ok(code)
+}
+
+fn wasm_component(name: &str, component: &ComponentSignature, types: &StructMap) -> Result<String, Error> {
+  let mut code = component_header(name, component, types)?;
+  let payload = if component.inputs.is_empty() {
+    "_payload"
+  } else {
+    "payload"
+  };
+  let mut reads = String::new();
+  for (port, sig) in component.inputs.ordered() {
+    let read = if matches!(sig, TypeSignature::Optional { .. }) {
+      format!(
+        "match payload.get({port:?}) {{ Ok(bytes) => {d}(bytes)?, Err(_) => None }}",
+        port = port,
+        d = "vino_provider::codec::messagepack::deserialize"
+      )
+    } else {
+      format!(
+        "vino_provider::codec::messagepack::deserialize(payload.get({:?})?)?",
+        port
+      )
+    };
+    reads.push_str(&format!("          {}: {},\n", snake_ident(port), read));
+  }
+  code.push_str(&format!(
+    r#"    impl Inputs {{
+      /// Read the component's inputs out of an [{p}::IncomingPayload].
+      pub fn from_payload({payload}: &{p}::IncomingPayload) -> Result<Self, {p}::Error> {{
+        Ok(Self {{
+{reads}        }})
+      }}
+    }}
+
+"#,
+    p = WASM,
+    payload = payload,
+    reads = reads
+  ));
+
+  let mut senders = String::new();
+  for (port, sig) in component.outputs.ordered() {
+    let sender = pascal_ident(port);
+    senders.push_str(&format!(
+      "          {}: {}Sender {{ id }},\n",
+      snake_ident(port),
+      sender
+    ));
+    code.push_str(&format!(
+      r#"    /// Sender for the `{port}` output port.
+    #[derive(Debug, Clone, Copy)]
+    pub struct {sender}Sender {{
+      id: u32,
+    }}
+
+    impl {p}::PortSender for {sender}Sender {{
+      type PayloadType = {ty};
+
+      fn get_name(&self) -> String {{
+        {port:?}.to_owned()
+      }}
+
+      fn get_id(&self) -> u32 {{
+        self.id
+      }}
+    }}
+
+"#,
+      p = WASM,
+      port = port,
+      sender = sender,
+      ty = rust_type(sig, types, COMPONENT_TYPES_PATH)?
+    ));
+  }
+
+  let id = if component.outputs.is_empty() { "_id" } else { "id" };
+  code.push_str(&format!(
+    r#"    /// The output ports of the `{name}` component.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Outputs {{
+{fields}    }}
+
+    impl Outputs {{
+      /// Create the output senders for the transaction with the passed id.
+      #[must_use]
+      pub fn new({id}: u32) -> Self {{
+        Self {{
+{senders}        }}
+      }}
+    }}
+  }}
+
+"#,
+    name = name,
+    id = id,
+    fields = output_fields(component),
+    senders = senders
+  ));
+  Ok(code)
+}
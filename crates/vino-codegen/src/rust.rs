@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use vino_types::{MapWrapper, StructMap, TypeSignature, TYPES_PREFIX};
+
+use crate::Error;
+
+const KEYWORDS: &[&str] = &[
+  "as", "async", "await", "box", "break", "const", "continue", "crate", "do", "dyn", "else", "enum", "extern", "false",
+  "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+  "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+  "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+fn words(name: &str) -> impl Iterator<Item = &str> {
+  name
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|w| !w.is_empty())
+}
+
+/// Prefix identifiers that are keywords or that start with a digit so they are valid Rust.
+fn escape(ident: String) -> String {
+  if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+    format!("_{}", ident)
+  } else if KEYWORDS.contains(&ident.as_str()) {
+    match ident.as_str() {
+      "crate" | "self" | "Self" | "super" => format!("{}_", ident),
+      _ => format!("r#{}", ident),
+    }
+  } else {
+    ident
+  }
+}
+
+/// Convert a signature name into a snake_case identifier for fields and modules.
+pub(crate) fn snake_ident(name: &str) -> String {
+  let mut snake = String::new();
+  for word in words(name) {
+    if !snake.is_empty() {
+      snake.push('_');
+    }
+    let mut prev_lower = false;
+    for c in word.chars() {
+      if c.is_ascii_uppercase() && prev_lower {
+        snake.push('_');
+      }
+      prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+      snake.push(c.to_ascii_lowercase());
+    }
+  }
+  escape(snake)
+}
+
+/// Convert a signature name into a PascalCase identifier for types.
+pub(crate) fn pascal_ident(name: &str) -> String {
+  let pascal = words(name)
+    .map(|word| {
+      let mut chars = word.chars();
+      chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+    })
+    .collect();
+  escape(pascal)
+}
+
+/// Fail if two distinct names convert to the same identifier, e.g. `fooBar` and `foo_bar` with [snake_ident].
+pub(crate) fn check_idents<'a, I: IntoIterator<Item = &'a String>>(
+  names: I,
+  ident: fn(&str) -> String,
+) -> Result<(), Error> {
+  let mut seen: HashMap<String, &String> = HashMap::new();
+  for name in names {
+    let converted = ident(name);
+    if let Some(other) = seen.get(&converted) {
+      return Err(Error::IdentifierCollision((*other).clone(), name.clone(), converted));
+    }
+    seen.insert(converted, name);
+  }
+  Ok(())
+}
+
+/// The name of the struct a [TypeSignature::Ref] points to.
+pub(crate) fn resolve_ref<'a>(reference: &'a str, types: &StructMap) -> Result<&'a str, Error> {
+  let name = reference.strip_prefix(TYPES_PREFIX).unwrap_or(reference);
+  if types.contains_key(name) {
+    Ok(name)
+  } else {
+    Err(Error::UnresolvedReference(reference.to_owned()))
+  }
+}
+
+/// Whether the Rust type for a [TypeSignature] implements `Eq` and `Hash` so it can key a `HashMap`.
+/// Floats and generated structs do not.
+fn hashable(sig: &TypeSignature) -> bool {
+  match sig {
+    TypeSignature::F32
+    | TypeSignature::F64
+    | TypeSignature::Ref { .. }
+    | TypeSignature::Struct
+    | TypeSignature::Map { .. }
+    | TypeSignature::Link { .. } => false,
+    TypeSignature::Internal(t) => hashable(&t.value_type()),
+    TypeSignature::List { element } | TypeSignature::Array { element, .. } => hashable(element),
+    TypeSignature::Tuple { elements } => elements.iter().all(hashable),
+    TypeSignature::Constrained { base, .. } => hashable(base),
+    TypeSignature::Optional { option } => hashable(option),
+    _ => true,
+  }
+}
+
+/// The Rust type for a [TypeSignature]. Generated structs are referenced through `types_path`.
+pub(crate) fn rust_type(sig: &TypeSignature, types: &StructMap, types_path: &str) -> Result<String, Error> {
+  let ty = match sig {
+    TypeSignature::I8 => "i8".to_owned(),
+    TypeSignature::I16 => "i16".to_owned(),
+    TypeSignature::I32 => "i32".to_owned(),
+    TypeSignature::I64 => "i64".to_owned(),
+    TypeSignature::U8 => "u8".to_owned(),
+    TypeSignature::U16 => "u16".to_owned(),
+    TypeSignature::U32 => "u32".to_owned(),
+    TypeSignature::U64 => "u64".to_owned(),
+    TypeSignature::F32 => "f32".to_owned(),
+    TypeSignature::F64 => "f64".to_owned(),
+    TypeSignature::Bool => "bool".to_owned(),
+    TypeSignature::String => "String".to_owned(),
+    TypeSignature::Datetime => "vino_types::Datetime".to_owned(),
+    TypeSignature::Uuid => "vino_types::Uuid".to_owned(),
+    TypeSignature::Duration => "vino_types::Duration".to_owned(),
+    TypeSignature::Decimal => "vino_types::Decimal".to_owned(),
+    TypeSignature::Url => "vino_types::Url".to_owned(),
+    TypeSignature::Bytes => "Vec<u8>".to_owned(),
+    TypeSignature::Raw | TypeSignature::Value => "serde_value::Value".to_owned(),
+    TypeSignature::Internal(t) => rust_type(&t.value_type(), types, types_path)?,
+    TypeSignature::Struct => "std::collections::HashMap<String, serde_value::Value>".to_owned(),
+    TypeSignature::Enum { .. } => "String".to_owned(),
+    // Variants are validated here but unions are left for the job to match on by tag.
+    TypeSignature::Union { variants, .. } => {
+      for variant in variants {
+        resolve_ref(variant, types)?;
+      }
+      "serde_value::Value".to_owned()
+    }
+    TypeSignature::Link { .. } => "vino_provider::ProviderLink".to_owned(),
+    TypeSignature::Ref { reference } => format!("{}{}", types_path, pascal_ident(resolve_ref(reference, types)?)),
+    TypeSignature::List { element } => format!("Vec<{}>", rust_type(element, types, types_path)?),
+    // serde only implements its traits for arrays of up to 32 elements.
+    TypeSignature::Array { element, length } if *length <= 32 => {
+      format!("[{}; {}]", rust_type(element, types, types_path)?, length)
+    }
+    TypeSignature::Array { element, .. } => format!("Vec<{}>", rust_type(element, types, types_path)?),
+    TypeSignature::Tuple { elements } => {
+      let elements = elements
+        .iter()
+        .map(|element| rust_type(element, types, types_path))
+        .collect::<Result<Vec<_>, _>>()?;
+      match elements.as_slice() {
+        [element] => format!("({},)", element),
+        _ => format!("({})", elements.join(", ")),
+      }
+    }
+    // Constraints are checked by validation rather than the type system.
+    TypeSignature::Constrained { base, .. } => rust_type(base, types, types_path)?,
+    TypeSignature::Optional { option } => format!("Option<{}>", rust_type(option, types, types_path)?),
+    TypeSignature::Map { key, .. } if !hashable(key) => return Err(Error::InvalidMapKey(key.to_string())),
+    TypeSignature::Map { key, value } => format!(
+      "std::collections::HashMap<{}, {}>",
+      rust_type(key, types, types_path)?,
+      rust_type(value, types, types_path)?
+    ),
+  };
+  Ok(ty)
+}
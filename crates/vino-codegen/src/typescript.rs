@@ -0,0 +1,269 @@
+use std::path::Path;
+
+use vino_types::{ComponentSignature, MapWrapper, ProviderSignature, StructMap, TypeMap, TypeSignature};
+
+use crate::generator::{generate_file, parse_signature};
+use crate::rust::{check_idents, pascal_ident, resolve_ref};
+use crate::Error;
+
+/// The envelope `MessageTransport::as_json` wraps every port value in.
+const ENVELOPE: &str = r#"/** The kinds of errors a {@link TransportJson} can carry. */
+export type JsonError = "None" | "Exception" | "Error" | "InternalError";
+
+/** Signals sent on a port in place of a value. */
+export type MessageSignal = "Done" | "OpenBracket" | "CloseBracket";
+
+/** The JSON envelope around a port value, as produced by `MessageTransport::as_json`. */
+export interface TransportJson<T> {
+  /** Error message for the port if it exists. */
+  error_msg?: string;
+  /** The error kind if it exists. */
+  error_kind?: JsonError;
+  /** The signal if the message was a signal. */
+  signal?: MessageSignal;
+  /** The value, or null for errors and signals. */
+  value: T | null;
+}
+
+"#;
+
+/// Generates TypeScript declarations (`.d.ts`) for a provider from its [ProviderSignature].
+///
+/// The declarations describe the JSON shape of each type, of each component's inputs and outputs,
+/// and of the [TransportJson](https://docs.rs/vino-transport) envelope output messages arrive in.
+#[derive(Debug, Clone, Copy, Default)]
+#[must_use]
+pub struct TypeScript;
+
+impl TypeScript {
+  /// Create a new [TypeScript] generator.
+  pub fn new() -> Self {
+    Self
+  }
+
+  /// Read a JSON [ProviderSignature] from `input` and write the generated declarations to `output`.
+  pub fn generate_file<I: AsRef<Path>, O: AsRef<Path>>(&self, input: I, output: O) -> Result<(), Error> {
+    generate_file(input.as_ref(), output.as_ref(), |json| self.generate_json(json))
+  }
+
+  /// Generate declarations for a JSON [ProviderSignature].
+  pub fn generate_json(&self, json: &str) -> Result<String, Error> {
+    self.generate(&parse_signature(json)?)
+  }
+
+  /// Generate declarations for a [ProviderSignature], in the order its types and components are declared.
+  pub fn generate(&self, signature: &ProviderSignature) -> Result<String, Error> {
+    let types = &signature.types;
+    check_idents(types.inner().keys(), pascal_ident)?;
+    check_idents(signature.components.inner().keys(), pascal_ident)?;
+    let mut code = String::from("// This file is generated by vino-codegen. Do not edit it by hand.\n\n");
+    code.push_str(ENVELOPE);
+    for (name, def) in types.ordered() {
+      code.push_str(&format!(
+        "{}export interface {} {{\n{}}}\n\n",
+        jsdoc(
+          def.description.as_ref(),
+          &format!("The `{}` type.", name),
+          def.deprecated,
+          ""
+        ),
+        pascal_ident(name),
+        properties(&def.fields, types)?
+      ));
+    }
+    for (name, component) in signature.components.ordered() {
+      code.push_str(&component_declarations(name, component, types)?);
+    }
+    Ok(code)
+  }
+}
+
+fn component_declarations(name: &str, component: &ComponentSignature, types: &StructMap) -> Result<String, Error> {
+  let ident = pascal_ident(name);
+  let mut code = jsdoc(
+    component.description.as_ref(),
+    &format!("The inputs of the `{}` component.", name),
+    component.deprecated,
+    "",
+  );
+  code.push_str(&format!(
+    "export interface {}Inputs {{\n{}}}\n\n",
+    ident,
+    properties(&component.inputs, types)?
+  ));
+  code.push_str(&format!(
+    "/** The outputs of the `{}` component. */\nexport interface {}Outputs {{\n{}}}\n\n",
+    name,
+    ident,
+    properties(&component.outputs, types)?
+  ));
+
+  let mut messages = Vec::new();
+  for (port, sig) in component.outputs.ordered() {
+    messages.push(format!(
+      "\n  | {{ {}: TransportJson<{}> }}",
+      property_name(port),
+      ts_type(sig, types)?
+    ));
+  }
+  let messages = if messages.is_empty() {
+    " never".to_owned()
+  } else {
+    messages.concat()
+  };
+  code.push_str(&format!(
+    "/** A message from one of the `{}` component's output ports, as produced by `TransportWrapper::as_json`. */\nexport type {}Output ={};\n\n",
+    name, ident, messages
+  ));
+  Ok(code)
+}
+
+/// Interface properties for every entry in the [TypeMap]. Optional and defaulted entries may be left out.
+fn properties(map: &TypeMap, types: &StructMap) -> Result<String, Error> {
+  let mut code = String::new();
+  for (name, sig) in map.ordered() {
+    let metadata = map.metadata(name);
+    let omittable = matches!(sig, TypeSignature::Optional { .. }) || metadata.is_some_and(|m| m.default.is_some());
+    let mut description = metadata
+      .and_then(|m| m.description.clone())
+      .unwrap_or_else(|| format!("The `{}` field.", name));
+    if has_wide_integer(sig) {
+      description.push_str(PRECISION_NOTE);
+    }
+    code.push_str(&format!(
+      "{}  {}{}: {};\n",
+      jsdoc(Some(&description), "", metadata.is_some_and(|m| m.deprecated), "  "),
+      property_name(name),
+      if omittable { "?" } else { "" },
+      ts_type(sig, types)?
+    ));
+  }
+  Ok(code)
+}
+
+/// The TypeScript type for the JSON representation of a [TypeSignature].
+fn ts_type(sig: &TypeSignature, types: &StructMap) -> Result<String, Error> {
+  let ty = match sig {
+    // 64-bit integers beyond `Number.MAX_SAFE_INTEGER` lose precision. See [has_wide_integer].
+    TypeSignature::I8
+    | TypeSignature::I16
+    | TypeSignature::I32
+    | TypeSignature::I64
+    | TypeSignature::U8
+    | TypeSignature::U16
+    | TypeSignature::U32
+    | TypeSignature::U64
+    | TypeSignature::F32
+    | TypeSignature::F64 => "number".to_owned(),
+    TypeSignature::Bool => "boolean".to_owned(),
+    TypeSignature::String
+    | TypeSignature::Datetime
+    | TypeSignature::Uuid
+    | TypeSignature::Duration
+    | TypeSignature::Decimal
+    | TypeSignature::Url => "string".to_owned(),
+    TypeSignature::Bytes => "number[]".to_owned(),
+    TypeSignature::Raw | TypeSignature::Value => "unknown".to_owned(),
+    TypeSignature::Internal(t) => ts_type(&t.value_type(), types)?,
+    TypeSignature::Struct | TypeSignature::Link { .. } => "Record<string, unknown>".to_owned(),
+    TypeSignature::Enum { values } if values.is_empty() => "never".to_owned(),
+    TypeSignature::Enum { values } => values.iter().map(|v| quote(v)).collect::<Vec<_>>().join(" | "),
+    TypeSignature::Union { tag, variants } => {
+      let mut members = Vec::new();
+      for variant in variants {
+        let name = resolve_ref(variant, types)?;
+        members.push(format!(
+          "({} & {{ {}: {} }})",
+          pascal_ident(name),
+          property_name(tag),
+          quote(name)
+        ));
+      }
+      if members.is_empty() {
+        "never".to_owned()
+      } else {
+        members.join(" | ")
+      }
+    }
+    TypeSignature::Ref { reference } => pascal_ident(resolve_ref(reference, types)?),
+    TypeSignature::List { element } => {
+      let element = ts_type(element, types)?;
+      if element.contains(' ') {
+        format!("Array<{}>", element)
+      } else {
+        format!("{}[]", element)
+      }
+    }
+    TypeSignature::Array { element, length } if *length <= 32 => {
+      let element = ts_type(element, types)?;
+      format!("[{}]", vec![element; *length as usize].join(", "))
+    }
+    TypeSignature::Array { element, .. } => format!("Array<{}>", ts_type(element, types)?),
+    TypeSignature::Tuple { elements } => {
+      let elements = elements
+        .iter()
+        .map(|element| ts_type(element, types))
+        .collect::<Result<Vec<_>, _>>()?;
+      format!("[{}]", elements.join(", "))
+    }
+    // Constraints are checked by validation rather than the type system.
+    TypeSignature::Constrained { base, .. } => ts_type(base, types)?,
+    TypeSignature::Optional { option } => format!("{} | null", ts_type(option, types)?),
+    // JSON object keys are always strings, whatever the key type.
+    TypeSignature::Map { value, .. } => format!("Record<string, {}>", ts_type(value, types)?),
+  };
+  Ok(ty)
+}
+
+/// Added to the documentation of fields that hold 64-bit integers, which JSON carries as plain numbers.
+const PRECISION_NOTE: &str =
+  "\nIntegers beyond `Number.MAX_SAFE_INTEGER` lose precision when parsed as a JavaScript `number`.";
+
+/// Returns true if the signature holds an [TypeSignature::I64] or [TypeSignature::U64] outside of a
+/// referenced type, which documents its own fields.
+fn has_wide_integer(sig: &TypeSignature) -> bool {
+  match sig {
+    TypeSignature::I64 | TypeSignature::U64 => true,
+    TypeSignature::List { element } | TypeSignature::Array { element, .. } => has_wide_integer(element),
+    TypeSignature::Optional { option } => has_wide_integer(option),
+    TypeSignature::Constrained { base, .. } => has_wide_integer(base),
+    TypeSignature::Tuple { elements } => elements.iter().any(has_wide_integer),
+    TypeSignature::Map { value, .. } => has_wide_integer(value),
+    TypeSignature::Internal(t) => has_wide_integer(&t.value_type()),
+    _ => false,
+  }
+}
+
+/// A string literal, quoted and escaped the way JSON (and so TypeScript) expects.
+fn quote(value: &str) -> String {
+  serde_json::Value::from(value).to_string()
+}
+
+/// A property name, quoted when it is not a valid identifier.
+fn property_name(name: &str) -> String {
+  let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+  if valid {
+    name.to_owned()
+  } else {
+    quote(name)
+  }
+}
+
+/// A JSDoc comment for a signature's description, or the fallback when it has none. A `*/` in the text is
+/// written as `*\/` so it does not end the comment.
+fn jsdoc(description: Option<&String>, fallback: &str, deprecated: bool, indent: &str) -> String {
+  let text = description.map_or(fallback, String::as_str).replace("*/", "*\\/");
+  if !deprecated && !text.contains('\n') {
+    return format!("{}/** {} */\n", indent, text);
+  }
+  let mut lines: Vec<&str> = text.lines().collect();
+  if deprecated {
+    lines.push("@deprecated");
+  }
+  let body: String = lines
+    .iter()
+    .map(|line| format!("{} * {}\n", indent, line).replace(" * \n", " *\n"))
+    .collect();
+  format!("{i}/**\n{}{i} */\n", body, i = indent)
+}
@@ -0,0 +1,43 @@
+//! Compiles the generated code for a native provider.
+//!
+//! The generated file is checked in so this crate can `include!` it. If the generator changes, regenerate
+//! `tests/native/generated.rs` from `tests/compile.json`.
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_codegen::{Generator, Target};
+use vino_provider::native::prelude::{Dispatch, TransportMap};
+
+include!("native/generated.rs");
+
+/// The provider state the generated dispatcher passes to every job.
+#[derive(Debug, Clone)]
+pub struct Context;
+
+mod jobs {
+  pub mod plot {
+    use vino_provider::native::prelude::JobResult;
+
+    use crate::components::plot::{Inputs, Outputs};
+
+    pub async fn job(inputs: Inputs, outputs: Outputs, _context: crate::Context) -> JobResult {
+      outputs.count.done_value(&(inputs.points.len() as u64))?;
+      outputs.rendered.done_value(&inputs.image)?;
+      Ok(())
+    }
+  }
+}
+
+#[test_log::test]
+fn test_generated_is_current() -> Result<()> {
+  let code = Generator::new(Target::Native).generate_json(include_str!("compile.json"))?;
+  assert_eq!(code, include_str!("native/generated.rs"));
+  Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_dispatch() -> Result<()> {
+  let result = Dispatcher::dispatch("missing", Context, TransportMap::default()).await;
+  assert!(result.is_err());
+  Ok(())
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_codegen::{Error, Generator, Target};
+
+static INTERFACE: &str = include_str!("interface.json");
+
+static KEYWORDS: &str = r##"{
+  "name": "keywords",
+  "types": {
+    "user-record": {
+      "name": "user-record",
+      "fields": {
+        "type": { "type": "string" },
+        "tags": { "type": "list", "element": { "type": "string" } },
+        "created": { "type": "datetime" },
+        "id": { "type": "uuid" },
+        "position": { "type": "tuple", "elements": [{ "type": "f32" }, { "type": "array", "element": { "type": "f32" }, "length": 3 }] },
+        "rating": { "type": "constrained", "base": { "type": "u8" }, "constraints": { "maximum": 5 } }
+      }
+    }
+  },
+  "components": {
+    "match": {
+      "name": "match",
+      "inputs": {
+        "userRecord": { "type": "ref", "ref": "#/types/user-record" },
+        "limit": { "type": "optional", "option": { "type": "u32" }, "description": "The most records to match." }
+      },
+      "outputs": {}
+    }
+  }
+}"##;
+
+fn parse(code: &str) -> Result<syn::File> {
+  Ok(syn::parse_file(code)?)
+}
+
+#[test_log::test]
+fn test_native() -> Result<()> {
+  let code = Generator::new(Target::Native)
+    .context_type("crate::State")
+    .generate_json(INTERFACE)?;
+  parse(&code)?;
+  assert!(code.contains("pub struct Blog {"));
+  assert!(code.contains("pub mod new {"));
+  assert!(code.contains("pub kv: vino_provider::ProviderLink,"));
+  assert!(code.contains("pub struct BlogIdSender {"));
+  assert!(code.contains("type Context = crate::State;"));
+  assert!(code.contains("crate::jobs::new::job(inputs, outputs, context)"));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_wasm() -> Result<()> {
+  let code = Generator::new(Target::Wasm)
+    .jobs_module("crate::components")
+    .generate_json(INTERFACE)?;
+  parse(&code)?;
+  assert!(code.contains("type PayloadType = String;"));
+  assert!(code.contains("components::new::Outputs::new(payload.id())"));
+  assert!(code.contains("crate::components::new::job(inputs, outputs)?;"));
+  assert!(code.contains(r#"ComponentNotFound(op.to_owned(), "new".to_owned())"#));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_deterministic() -> Result<()> {
+  for target in [Target::Native, Target::Wasm] {
+    let generator = Generator::new(target);
+    assert_eq!(generator.generate_json(INTERFACE)?, generator.generate_json(INTERFACE)?);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_identifiers() -> Result<()> {
+  for target in [Target::Native, Target::Wasm] {
+    let code = Generator::new(target).generate_json(KEYWORDS)?;
+    parse(&code)?;
+    assert!(code.contains("pub struct UserRecord {"));
+    assert!(code.contains("pub r#type: String,"));
+    assert!(code.contains("pub mod r#match {"));
+    assert!(code.contains("pub user_record: super::super::types::UserRecord,"));
+    assert!(code.contains(r#"#[serde(rename = "limit", default)]"#));
+    assert!(code.contains("pub limit: Option<u32>,"));
+    assert!(code.contains("pub created: vino_types::Datetime,"));
+    assert!(code.contains("pub id: vino_types::Uuid,"));
+    assert!(code.contains("pub position: (f32, [f32; 3]),"));
+    assert!(code.contains("pub rating: u8,"));
+    assert!(code.contains("/// The most records to match.\n"));
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_unresolved_reference() -> Result<()> {
+  let json = KEYWORDS.replace("#/types/user-record", "#/types/missing");
+  let result = Generator::new(Target::Native).generate_json(&json);
+  assert!(matches!(result, Err(Error::UnresolvedReference(r)) if r == "#/types/missing"));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_unhashable_map_key() -> Result<()> {
+  for key in [
+    r#"{ "type": "f64" }"#,
+    r##"{ "type": "ref", "ref": "#/types/user-record" }"##,
+  ] {
+    let json = KEYWORDS.replace(
+      r#""type": { "type": "string" },"#,
+      &format!(
+        r#""type": {{ "type": "map", "key": {}, "value": {{ "type": "string" }} }},"#,
+        key
+      ),
+    );
+    let result = Generator::new(Target::Native).generate_json(&json);
+    assert!(matches!(result, Err(Error::InvalidMapKey(_))), "{:?}", result);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_identifier_collision() -> Result<()> {
+  let collides = |result: Result<String, Error>, first: &str, second: &str, ident: &str| matches!(&result, Err(Error::IdentifierCollision(a, b, c)) if a == first && b == second && c == ident);
+
+  let json = KEYWORDS.replace(r#""limit": {"#, r#""user_record": { "type": "string" }, "limit": {"#);
+  let result = Generator::new(Target::Native).generate_json(&json);
+  assert!(collides(result, "userRecord", "user_record", "user_record"));
+
+  let json = KEYWORDS.replace(
+    r#""components": {"#,
+    r#""components": { "Match": { "name": "Match", "inputs": {}, "outputs": {} },"#,
+  );
+  let result = Generator::new(Target::Wasm).generate_json(&json);
+  assert!(collides(result, "Match", "match", "r#match"));
+
+  let json = KEYWORDS.replace(
+    r#""types": {"#,
+    r#""types": { "UserRecord": { "name": "UserRecord", "fields": {} },"#,
+  );
+  let result = Generator::new(Target::Native).generate_json(&json);
+  assert!(collides(result, "UserRecord", "user-record", "UserRecord"));
+  let result = vino_codegen::TypeScript::new().generate_json(&json);
+  assert!(collides(result, "UserRecord", "user-record", "UserRecord"));
+  Ok(())
+}
@@ -0,0 +1,206 @@
+// This file is generated by vino-codegen. Do not edit it by hand.
+
+/// The types defined by the provider signature.
+pub mod types {
+  /// The `Point` type.
+  #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+  pub struct Point {
+    /// The `x` field.
+    #[serde(rename = "x")]
+    pub x: f64,
+    /// The `y` field.
+    #[serde(rename = "y")]
+    pub y: f64,
+    /// The `label` field.
+    #[serde(rename = "label", default)]
+    pub label: Option<String>,
+  }
+
+}
+
+/// The components defined by the provider signature.
+pub mod components {
+  /// Generated code for the `plot` component.
+  pub mod plot {
+    /// The inputs of the `plot` component.
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Inputs {
+      /// The `points` field.
+      #[serde(rename = "points")]
+      pub points: Vec<super::super::types::Point>,
+      /// The `weights` field.
+      #[serde(rename = "weights")]
+      pub weights: std::collections::HashMap<u32, f32>,
+      /// The `created` field.
+      #[serde(rename = "created")]
+      pub created: vino_types::Datetime,
+      /// The `id` field.
+      #[serde(rename = "id")]
+      pub id: vino_types::Uuid,
+      /// The `timeout` field.
+      #[serde(rename = "timeout")]
+      pub timeout: vino_types::Duration,
+      /// The `scale` field.
+      #[serde(rename = "scale")]
+      pub scale: vino_types::Decimal,
+      /// The `source` field.
+      #[serde(rename = "source")]
+      pub source: vino_types::Url,
+      /// The `image` field.
+      #[serde(rename = "image")]
+      pub image: Vec<u8>,
+      /// The `extra` field.
+      #[serde(rename = "extra")]
+      pub extra: serde_value::Value,
+      /// The `size` field.
+      #[serde(rename = "size")]
+      pub size: (u16, u16),
+      /// The `origin` field.
+      #[serde(rename = "origin")]
+      pub origin: [i32; 2],
+    }
+
+    impl Inputs {
+      /// Read the component's inputs out of a [vino_provider::native::prelude::TransportMap].
+      pub fn from_transport_map(mut map: vino_provider::native::prelude::TransportMap) -> Result<Self, vino_provider::native::prelude::TransportError> {
+        Ok(Self {
+          points: map.consume("points")?,
+          weights: map.consume("weights")?,
+          created: map.consume("created")?,
+          id: map.consume("id")?,
+          timeout: map.consume("timeout")?,
+          scale: map.consume("scale")?,
+          source: map.consume("source")?,
+          image: map.consume("image")?,
+          extra: map.consume("extra")?,
+          size: map.consume("size")?,
+          origin: map.consume("origin")?,
+        })
+      }
+    }
+
+    /// Sender for the `rendered` output port.
+    #[derive(Debug, Clone)]
+    pub struct RenderedSender {
+      port: vino_provider::native::prelude::PortChannel,
+    }
+
+    impl Default for RenderedSender {
+      fn default() -> Self {
+        Self {
+          port: vino_provider::native::prelude::PortChannel::new("rendered"),
+        }
+      }
+    }
+
+    impl vino_provider::native::prelude::PortSender for RenderedSender {
+      fn get_port(&self) -> Result<&vino_provider::native::prelude::PortChannel, vino_provider::native::prelude::ProviderError> {
+        if self.port.is_closed() {
+          Err(vino_provider::native::prelude::ProviderError::SendChannelClosed)
+        } else {
+          Ok(&self.port)
+        }
+      }
+
+      fn get_port_name(&self) -> &str {
+        &self.port.name
+      }
+    }
+
+    impl RenderedSender {
+      /// Send a value out of the port.
+      pub fn send_value(&self, value: &Vec<u8>) -> Result<(), vino_provider::native::prelude::ProviderError> {
+        vino_provider::native::prelude::PortSender::send(self, vino_provider::native::prelude::Payload::success(value))
+      }
+
+      /// Send a value out of the port then close it.
+      pub fn done_value(&self, value: &Vec<u8>) -> Result<(), vino_provider::native::prelude::ProviderError> {
+        vino_provider::native::prelude::PortSender::done(self, vino_provider::native::prelude::Payload::success(value))
+      }
+    }
+
+    /// Sender for the `count` output port.
+    #[derive(Debug, Clone)]
+    pub struct CountSender {
+      port: vino_provider::native::prelude::PortChannel,
+    }
+
+    impl Default for CountSender {
+      fn default() -> Self {
+        Self {
+          port: vino_provider::native::prelude::PortChannel::new("count"),
+        }
+      }
+    }
+
+    impl vino_provider::native::prelude::PortSender for CountSender {
+      fn get_port(&self) -> Result<&vino_provider::native::prelude::PortChannel, vino_provider::native::prelude::ProviderError> {
+        if self.port.is_closed() {
+          Err(vino_provider::native::prelude::ProviderError::SendChannelClosed)
+        } else {
+          Ok(&self.port)
+        }
+      }
+
+      fn get_port_name(&self) -> &str {
+        &self.port.name
+      }
+    }
+
+    impl CountSender {
+      /// Send a value out of the port.
+      pub fn send_value(&self, value: &u64) -> Result<(), vino_provider::native::prelude::ProviderError> {
+        vino_provider::native::prelude::PortSender::send(self, vino_provider::native::prelude::Payload::success(value))
+      }
+
+      /// Send a value out of the port then close it.
+      pub fn done_value(&self, value: &u64) -> Result<(), vino_provider::native::prelude::ProviderError> {
+        vino_provider::native::prelude::PortSender::done(self, vino_provider::native::prelude::Payload::success(value))
+      }
+    }
+
+    /// The output ports of the `plot` component.
+    #[derive(Debug, Default)]
+    pub struct Outputs {
+      /// The `rendered` port.
+      pub rendered: RenderedSender,
+      /// The `count` port.
+      pub count: CountSender,
+    }
+
+    impl Outputs {
+      /// Open every output port and merge them into a single stream.
+      pub fn stream(&mut self) -> vino_provider::native::prelude::TransportStream {
+        vino_provider::native::prelude::PortChannel::merge_all(&mut [&mut self.rendered.port, &mut self.count.port])
+      }
+    }
+  }
+
+}
+
+/// Routes operation names to the provider's components.
+#[derive(Debug, Clone, Copy)]
+pub struct Dispatcher;
+
+#[vino_provider::native::prelude::async_trait]
+impl vino_provider::native::prelude::Dispatch for Dispatcher {
+  type Context = crate::Context;
+  async fn dispatch(
+    op: &str,
+    context: Self::Context,
+    data: vino_provider::native::prelude::TransportMap,
+  ) -> Result<vino_provider::native::prelude::TransportStream, Box<vino_provider::native::prelude::NativeComponentError>> {
+    use vino_provider::native::prelude::NativeComponentError;
+    match op {
+      "plot" => {
+        let inputs = components::plot::Inputs::from_transport_map(data)
+          .map_err(|e| NativeComponentError::boxed(e.to_string()))?;
+        let mut outputs = components::plot::Outputs::default();
+        let stream = outputs.stream();
+        crate::jobs::plot::job(inputs, outputs, context).await.map_err(Box::new)?;
+        Ok(stream)
+      }
+      _ => Err(NativeComponentError::boxed(format!("Component '{}' not found on this provider", op))),
+    }
+  }
+}
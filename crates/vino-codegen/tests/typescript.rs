@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use vino_codegen::{Error, TypeScript};
+use vino_transport::{MessageTransport, TransportWrapper};
+use vino_types::{Datetime, Decimal, Duration, Url, Uuid};
+
+static INTERFACE: &str = include_str!("interface.json");
+
+static SHAPES: &str = r##"{
+  "name": "shapes",
+  "types": {
+    "Circle": {
+      "name": "Circle",
+      "fields": { "radius": { "type": "f64" } }
+    },
+    "Square": {
+      "name": "Square",
+      "description": "A square.\nAll sides are equal.",
+      "fields": { "side": { "type": "f64" } }
+    }
+  },
+  "components": {
+    "total-area": {
+      "name": "total-area",
+      "description": "Add up the area of shapes.",
+      "inputs": {
+        "shapes": { "type": "list", "element": { "type": "union", "tag": "kind", "variants": ["#/types/Circle", "Square"] } },
+        "units": { "type": "enum", "values": ["cm", "in"], "default": "cm" },
+        "weights": { "type": "map", "key": { "type": "string" }, "value": { "type": "optional", "option": { "type": "f32" } } },
+        "scale": { "type": "optional", "option": { "type": "u8" }, "deprecated": true }
+      },
+      "outputs": {
+        "area": { "type": "f64" },
+        "largest": { "type": "ref", "ref": "#/types/Circle" }
+      }
+    }
+  }
+}"##;
+
+#[test_log::test]
+fn test_declarations() -> Result<()> {
+  let code = TypeScript::new().generate_json(SHAPES)?;
+  let (envelope, declarations) = code.split_once("/** The `Circle` type. */").unwrap();
+  assert!(envelope.contains("export interface TransportJson<T> {"));
+  assert!(envelope.contains("export type JsonError = \"None\" | \"Exception\" | \"Error\" | \"InternalError\";"));
+  assert_eq!(
+    declarations,
+    r#"
+export interface Circle {
+  /** The `radius` field. */
+  radius: number;
+}
+
+/**
+ * A square.
+ * All sides are equal.
+ */
+export interface Square {
+  /** The `side` field. */
+  side: number;
+}
+
+/** Add up the area of shapes. */
+export interface TotalAreaInputs {
+  /** The `shapes` field. */
+  shapes: Array<(Circle & { kind: "Circle" }) | (Square & { kind: "Square" })>;
+  /** The `units` field. */
+  units?: "cm" | "in";
+  /** The `weights` field. */
+  weights: Record<string, number | null>;
+  /**
+   * The `scale` field.
+   * @deprecated
+   */
+  scale?: number | null;
+}
+
+/** The outputs of the `total-area` component. */
+export interface TotalAreaOutputs {
+  /** The `area` field. */
+  area: number;
+  /** The `largest` field. */
+  largest: Circle;
+}
+
+/** A message from one of the `total-area` component's output ports, as produced by `TransportWrapper::as_json`. */
+export type TotalAreaOutput =
+  | { area: TransportJson<number> }
+  | { largest: TransportJson<Circle> };
+
+"#
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_blog() -> Result<()> {
+  let code = TypeScript::new().generate_json(INTERFACE)?;
+  assert!(code.contains("export interface Blog {\n  /** The `blog_id` field. */\n  blog_id: string;\n"));
+  assert!(code.contains("  kv: Record<string, unknown>;\n"));
+  assert!(code.contains("export type NewOutput =\n  | { blog_id: TransportJson<string> };\n"));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_quoting() -> Result<()> {
+  let json = r#"{
+    "name": "odd",
+    "components": {
+      "op": {
+        "name": "op",
+        "description": "Matches paths like src/**/*.rs",
+        "inputs": {
+          "user-id": { "type": "string" },
+          "mode": { "type": "enum", "values": ["a \"b\""] },
+          "glob*/": { "type": "string", "description": "Ends */ early\nand */ again" }
+        },
+        "outputs": {}
+      }
+    }
+  }"#;
+  let code = TypeScript::new().generate_json(json)?;
+  assert!(code.contains("  \"user-id\": string;\n"));
+  assert!(code.contains("  mode: \"a \\\"b\\\"\";\n"));
+  assert!(code.contains("export type OpOutput = never;\n"));
+  assert!(code.contains("/** Matches paths like src/**\\/*.rs */\nexport interface OpInputs {\n"));
+  assert!(code.contains("  /**\n   * Ends *\\/ early\n   * and *\\/ again\n   */\n  \"glob*/\": string;\n"));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_unresolved_reference() -> Result<()> {
+  let json = SHAPES.replace("\"Square\"]", "\"Triangle\"]");
+  let result = TypeScript::new().generate_json(&json);
+  assert!(matches!(result, Err(Error::UnresolvedReference(r)) if r == "Triangle"));
+  Ok(())
+}
+
+static WIRE: &str = r##"{
+  "name": "wire",
+  "types": {
+    "Point": { "name": "Point", "fields": { "x": { "type": "f64" }, "label": { "type": "optional", "option": { "type": "string" } } } }
+  },
+  "components": {
+    "everything": {
+      "name": "everything",
+      "inputs": {},
+      "outputs": {
+        "small": { "type": "i8" },
+        "big": { "type": "i64" },
+        "huge": { "type": "u64" },
+        "count": { "type": "u64" },
+        "ratio": { "type": "f64" },
+        "flag": { "type": "bool" },
+        "at": { "type": "datetime" },
+        "id": { "type": "uuid" },
+        "wait": { "type": "duration" },
+        "price": { "type": "decimal" },
+        "site": { "type": "url" },
+        "blob": { "type": "bytes" },
+        "maybe": { "type": "optional", "option": { "type": "u32" } },
+        "pair": { "type": "tuple", "elements": [{ "type": "u8" }, { "type": "datetime" }] },
+        "scores": { "type": "map", "key": { "type": "u32" }, "value": { "type": "i64" } },
+        "point": { "type": "ref", "ref": "#/types/Point" },
+        "mode": { "type": "enum", "values": ["fast", "slow"] }
+      }
+    }
+  }
+}"##;
+
+/// Interface names to their fields' names, whether they are optional, and types.
+type Interfaces = HashMap<String, Vec<(String, bool, String)>>;
+
+/// Split a TypeScript type on a separator that is not nested in brackets.
+fn split_top(ty: &str, separator: &str) -> Vec<String> {
+  let (mut parts, mut depth, mut start) = (Vec::new(), 0, 0);
+  for (i, c) in ty.char_indices() {
+    match c {
+      '<' | '[' | '(' | '{' => depth += 1,
+      '>' | ']' | ')' | '}' => depth -= 1,
+      _ if depth == 0 && ty[i..].starts_with(separator) => {
+        parts.push(ty[start..i].trim().to_owned());
+        start = i + separator.len();
+      }
+      _ => {}
+    }
+  }
+  parts.push(ty[start..].trim().to_owned());
+  parts
+}
+
+/// Whether a JSON value has the shape of a generated TypeScript type. Interfaces are looked up by name.
+fn conforms(ty: &str, value: &Value, interfaces: &Interfaces) -> bool {
+  let alternatives = split_top(ty, " | ");
+  if alternatives.len() > 1 {
+    return alternatives.iter().any(|alt| conforms(alt, value, interfaces));
+  }
+  match ty {
+    "number" => value.is_number(),
+    "string" => value.is_string(),
+    "boolean" => value.is_boolean(),
+    "null" => value.is_null(),
+    "unknown" => true,
+    _ if ty.starts_with('"') => value == &serde_json::from_str::<Value>(ty).unwrap(),
+    _ if ty.ends_with("[]") => value
+      .as_array()
+      .is_some_and(|items| items.iter().all(|v| conforms(&ty[..ty.len() - 2], v, interfaces))),
+    _ if ty.starts_with("Record<string, ") => value
+      .as_object()
+      .is_some_and(|map| map.values().all(|v| conforms(&ty[15..ty.len() - 1], v, interfaces))),
+    _ if ty.starts_with('[') => {
+      let elements = split_top(&ty[1..ty.len() - 1], ", ");
+      value.as_array().is_some_and(|items| {
+        items.len() == elements.len() && items.iter().zip(&elements).all(|(v, t)| conforms(t, v, interfaces))
+      })
+    }
+    _ => match (interfaces.get(ty), value.as_object()) {
+      (Some(fields), Some(map)) => fields.iter().all(|(name, optional, field_ty)| match map.get(name) {
+        Some(v) => conforms(field_ty, v, interfaces),
+        None => *optional,
+      }),
+      _ => panic!("unexpected type '{}' for {}", ty, value),
+    },
+  }
+}
+
+/// The fields of every interface and the payload type of every output port in generated declarations.
+fn declarations(code: &str) -> (Interfaces, HashMap<String, String>) {
+  let mut interfaces = HashMap::new();
+  let mut current: Option<(String, Vec<_>)> = None;
+  let mut ports = HashMap::new();
+  for line in code.lines() {
+    if let Some(name) = line
+      .strip_prefix("export interface ")
+      .and_then(|l| l.strip_suffix(" {"))
+    {
+      current = Some((name.to_owned(), Vec::new()));
+    } else if line == "}" {
+      if let Some((name, fields)) = current.take() {
+        interfaces.insert(name, fields);
+      }
+    } else if let Some((_, fields)) = current.as_mut() {
+      if let Some((name, ty)) = line.trim().strip_suffix(';').and_then(|l| l.split_once(": ")) {
+        let (name, optional) = name.strip_suffix('?').map_or((name, false), |n| (n, true));
+        fields.push((name.to_owned(), optional, ty.to_owned()));
+      }
+    } else if let Some(port) = line
+      .strip_prefix("  | { ")
+      .and_then(|l| l.strip_suffix(" }").or_else(|| l.strip_suffix(" };")))
+    {
+      let (name, ty) = port.split_once(": TransportJson<").unwrap();
+      ports.insert(name.to_owned(), ty.strip_suffix('>').unwrap().to_owned());
+    }
+  }
+  (interfaces, ports)
+}
+
+#[test_log::test]
+fn test_wire_shapes() -> Result<()> {
+  let code = TypeScript::new().generate_json(WIRE)?;
+  let (interfaces, ports) = declarations(&code);
+  let datetime = Datetime::from_unix(1_636_025_400, 500_000_000);
+  let payloads = vec![
+    ("small", MessageTransport::success(&-5_i8)),
+    ("big", MessageTransport::success(&i64::MIN)),
+    ("huge", MessageTransport::success(&u64::MAX)),
+    ("count", MessageTransport::success(&42_u64)),
+    ("ratio", MessageTransport::success(&0.5_f64)),
+    ("flag", MessageTransport::success(&true)),
+    ("at", MessageTransport::success(&datetime)),
+    ("id", MessageTransport::success(&Uuid::from_random_bytes([7; 16]))),
+    ("wait", MessageTransport::success(&Duration::new(1, 500_000_000))),
+    ("price", MessageTransport::success(&"1.50".parse::<Decimal>()?)),
+    ("site", MessageTransport::success(&"https://vino.dev".parse::<Url>()?)),
+    (
+      "blob",
+      MessageTransport::success(&serde_value::Value::Bytes(vec![0, 255])),
+    ),
+    ("maybe", MessageTransport::success(&None::<u32>)),
+    ("pair", MessageTransport::success(&(7_u8, datetime))),
+    ("scores", MessageTransport::success(&HashMap::from([(1_u32, i64::MAX)]))),
+    ("point", MessageTransport::success(&json!({ "x": 1.5 }))),
+    ("mode", MessageTransport::success(&"slow")),
+  ];
+  assert_eq!(payloads.len(), ports.len());
+  assert!(!conforms(&ports["huge"], &json!(true), &interfaces));
+  assert!(!conforms(&ports["point"], &json!({ "label": "no x" }), &interfaces));
+  for (port, payload) in payloads {
+    let json = TransportWrapper::new(port, payload).as_json();
+    let envelope = &json[port];
+    assert!(envelope.get("error_kind").is_none(), "{}: {}", port, envelope);
+    let ty = &ports[port];
+    assert!(
+      conforms(ty, &envelope["value"], &interfaces),
+      "{}: {} is not a {}",
+      port,
+      envelope["value"],
+      ty
+    );
+  }
+
+  let json = TransportWrapper::new("huge", MessageTransport::success(&u64::MAX)).as_json();
+  assert_eq!(json["huge"]["value"], json!(u64::MAX));
+  assert!(code.contains(
+    "  /**\n   * The `huge` field.\n   * Integers beyond `Number.MAX_SAFE_INTEGER` lose precision when parsed as a JavaScript `number`.\n   */\n  huge: number;\n"
+  ));
+  assert!(code.contains("  /** The `small` field. */\n  small: number;\n"));
+  let json = TransportWrapper::new(
+    "blob",
+    MessageTransport::success(&serde_value::Value::Bytes(vec![0, 255])),
+  )
+  .as_json();
+  assert_eq!(json["blob"]["value"], json!([0, 255]));
+  Ok(())
+}
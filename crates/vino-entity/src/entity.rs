@@ -1,12 +1,15 @@
 use std::fmt::Display;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::error::EntityError as Error;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// The entity being referenced across systems or services.
+///
+/// Entity data is reference-counted so cloning an [Entity] never copies its strings.
 #[must_use]
 pub enum Entity {
   /// A [SystemEntity] with the name "invalid". Used only for situations where a default is more useful than an error.
@@ -14,19 +17,19 @@ pub enum Entity {
   /// The [SystemEntity] is used when communicating to or from the internals of another component. Used mostly by library developers.
   System(SystemEntity),
   /// A [SystemEntity] with the name "test". Used as the originating entity for tests.
-  Test(String),
+  Test(Arc<str>),
   /// A client entity used for requests.
-  Client(String),
+  Client(Arc<str>),
   /// A Host entity used for entities that serve responses to requests.
-  Host(String),
+  Host(Arc<str>),
   /// A schematic.
-  Schematic(String),
+  Schematic(Arc<str>),
   /// A component or anything that can be invoked like a component.
-  Component(String, String),
+  Component(Arc<str>, Arc<str>),
   /// A provider (an entity that hosts a collection of components).
-  Provider(String),
+  Provider(Arc<str>),
   /// A reference to an instance of an entity.
-  Reference(String),
+  Reference(Arc<str>),
 }
 
 impl Serialize for Entity {
@@ -48,24 +51,34 @@ impl<'de> Deserialize<'de> for Entity {
   }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 /// A struct to hold additional data for [SystemEntity]s.
 pub struct SystemEntity {
   /// The name of the [SystemEntity].
-  pub name: String,
+  pub name: Arc<str>,
   /// A freefrom string.
-  pub value: String,
+  pub value: Arc<str>,
 }
 
 impl Default for Entity {
   fn default() -> Self {
-    Self::Test("default".to_owned())
+    Self::Test("default".into())
   }
 }
 
 impl Display for Entity {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}", self.url())
+    match self {
+      Entity::Test(msg) => write!(f, "{}://test.sys/?msg={}", URL_SCHEME, msg),
+      Entity::Schematic(name) => write!(f, "{}://{}.schem/", URL_SCHEME, name),
+      Entity::Component(ns, id) => write!(f, "{}://{}.prov/{}", URL_SCHEME, ns, id),
+      Entity::Provider(name) => write!(f, "{}://{}.prov/", URL_SCHEME, name),
+      Entity::Client(id) => write!(f, "{}://{}.client/", URL_SCHEME, id),
+      Entity::Host(id) => write!(f, "{}://{}.host/", URL_SCHEME, id),
+      Entity::System(e) => write!(f, "{}://{}.sys/?msg={}", URL_SCHEME, e.name, e.value),
+      Entity::Invalid => write!(f, "{}://invalid.sys/", URL_SCHEME),
+      Entity::Reference(id) => write!(f, "{}://{}.ref/", URL_SCHEME, id),
+    }
   }
 }
 
@@ -125,74 +138,64 @@ impl Entity {
 
   /// Constructor for [Entity::Component].
   pub fn component<T: AsRef<str>, U: AsRef<str>>(ns: T, name: U) -> Self {
-    Self::Component(ns.as_ref().to_owned(), name.as_ref().to_owned())
+    Self::Component(ns.as_ref().into(), name.as_ref().into())
   }
 
   /// Constructor for [Entity::Component] on the local namespace, used when
   /// the namespace is irrelevant. Caution: this is not portable.
   pub fn local_component<T: AsRef<str>>(name: T) -> Self {
-    Self::Component(Self::LOCAL.to_owned(), name.as_ref().to_owned())
+    Self::Component(Self::LOCAL.into(), name.as_ref().into())
   }
 
   /// Constructor for [Entity::Component] without a namespace, used when
   /// the namespace is irrelevant. Caution: this is not portable.
   #[deprecated(note = "please use `local_component()` instead")]
   pub fn component_direct<T: AsRef<str>>(name: T) -> Self {
-    Self::Component(Self::LOCAL.to_owned(), name.as_ref().to_owned())
+    Self::Component(Self::LOCAL.into(), name.as_ref().into())
   }
 
   /// Constructor for Entity::System.
   pub fn system<T: AsRef<str>, U: AsRef<str>>(name: T, value: U) -> Self {
     Self::System(SystemEntity {
-      name: name.as_ref().to_owned(),
-      value: value.as_ref().to_owned(),
+      name: name.as_ref().into(),
+      value: value.as_ref().into(),
     })
   }
 
   /// Constructor for Entity::Test.
   pub fn test<T: AsRef<str>>(msg: T) -> Self {
-    Self::Test(msg.as_ref().to_owned())
+    Self::Test(msg.as_ref().into())
   }
 
   /// Constructor for Entity::Provider.
   pub fn provider<T: AsRef<str>>(id: T) -> Self {
-    Self::Provider(id.as_ref().to_owned())
+    Self::Provider(id.as_ref().into())
   }
 
   /// Constructor for Entity::Schematic.
   pub fn schematic<T: AsRef<str>>(id: T) -> Self {
-    Self::Schematic(id.as_ref().to_owned())
+    Self::Schematic(id.as_ref().into())
   }
 
   /// Constructor for Entity::Host.
   pub fn host<T: AsRef<str>>(id: T) -> Self {
-    Self::Host(id.as_ref().to_owned())
+    Self::Host(id.as_ref().into())
   }
 
   /// Constructor for Entity::Client.
   pub fn client<T: AsRef<str>>(id: T) -> Self {
-    Self::Client(id.as_ref().to_owned())
+    Self::Client(id.as_ref().into())
   }
 
   /// Constructor for Entity::Client.
   pub fn reference<T: AsRef<str>>(id: T) -> Self {
-    Self::Reference(id.as_ref().to_owned())
+    Self::Reference(id.as_ref().into())
   }
 
   /// The URL of the entity.
   #[must_use]
   pub fn url(&self) -> String {
-    match self {
-      Entity::Test(msg) => format!("{}://test.sys/?msg={}", URL_SCHEME, msg),
-      Entity::Schematic(name) => format!("{}://{}.schem/", URL_SCHEME, name),
-      Entity::Component(ns, id) => format!("{}://{}.prov/{}", URL_SCHEME, ns, id),
-      Entity::Provider(name) => format!("{}://{}.prov/", URL_SCHEME, name),
-      Entity::Client(id) => format!("{}://{}.client/", URL_SCHEME, id),
-      Entity::Host(id) => format!("{}://{}.host/", URL_SCHEME, id),
-      Entity::System(e) => format!("{}://{}.sys/?msg={}", URL_SCHEME, e.name, e.value),
-      Entity::Invalid => format!("{}://invalid.sys/", URL_SCHEME),
-      Entity::Reference(id) => format!("{}://{}.ref/", URL_SCHEME, id),
-    }
+    self.to_string()
   }
 
   /// The name of the entity.
@@ -266,4 +269,46 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn test_clone_and_hash() -> Result<(), Error> {
+    use std::collections::HashMap;
+
+    let entity = Entity::component("namespace", "comp_name");
+    let cloned = entity.clone();
+    match (&entity, &cloned) {
+      (Entity::Component(ns, id), Entity::Component(cloned_ns, cloned_id)) => {
+        assert!(Arc::ptr_eq(ns, cloned_ns));
+        assert!(Arc::ptr_eq(id, cloned_id));
+      }
+      _ => panic!("clone changed the entity kind"),
+    }
+
+    let mut routes = HashMap::new();
+    routes.insert(entity, 1);
+    routes.insert(Entity::provider("namespace"), 2);
+    assert_eq!(routes.get(&Entity::from_str("ofp://namespace.prov/comp_name")?), Some(&1));
+    assert_eq!(routes.get(&Entity::from_str("ofp://namespace.prov/")?), Some(&2));
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_url_roundtrip() -> Result<(), Error> {
+    let entities = vec![
+      Entity::component("namespace", "comp_name"),
+      Entity::schematic("schem_id"),
+      Entity::provider("prov_ns"),
+      Entity::host("host_id"),
+      Entity::reference("ref_id"),
+      Entity::client("client_id"),
+      Entity::test("Hello"),
+      Entity::system("other", "Else"),
+    ];
+    for entity in entities {
+      assert_eq!(Entity::from_str(&entity.url())?, entity);
+    }
+
+    Ok(())
+  }
 }
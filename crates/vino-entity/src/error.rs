@@ -7,4 +7,8 @@ pub enum EntityError {
   /// Error used when trying to parse a URL into an entity.
   #[error("URL parse error {0}")]
   ParseError(String),
+
+  /// Error used when a [crate::Policy] could not be loaded.
+  #[error("Policy error {0}")]
+  PolicyError(String),
 }
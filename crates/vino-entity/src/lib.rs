@@ -81,7 +81,11 @@ mod entity;
 /// Crate errors.
 pub mod error;
 
+/// Access-control policies between entities.
+mod policy;
+
 pub use entity::{Entity, SystemEntity};
+pub use policy::{DenialReason, Effect, EntityPattern, Policy, PolicyDenial, PolicyRule};
 pub use error::EntityError as Error;
 
 #[macro_use]
@@ -0,0 +1,426 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{Entity, URL_SCHEME};
+#[cfg(any(feature = "json", feature = "yaml"))]
+use crate::error::EntityError as Error;
+
+/// Whether a rule (or a policy's default) allows or denies an invocation.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Effect {
+  /// Allow the invocation.
+  Allow,
+  /// Deny the invocation.
+  #[default]
+  Deny,
+}
+
+/// A pattern matched against an [Entity]'s URL, segment by segment.
+///
+/// Patterns take the form `[scheme://]namespace.kind/name`, where each segment is matched on its own and a
+/// `*` matches any run of characters within its segment, e.g. `ofp://tenant_a.prov/*` matches every component
+/// on the `tenant_a` provider but not on `tenant_a_admin`. A pattern of only `*` matches every entity.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+#[must_use]
+pub struct EntityPattern {
+  pattern: String,
+  /// The pattern split into segments, or `None` for a pattern that matches everything.
+  segments: Option<Segments>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Segments {
+  scheme: String,
+  namespace: String,
+  kind: String,
+  name: String,
+}
+
+impl EntityPattern {
+  /// Create a new [EntityPattern] from a pattern string.
+  pub fn new<T: AsRef<str>>(pattern: T) -> Self {
+    let pattern = pattern.as_ref();
+    let segments = (pattern != "*").then(|| {
+      let (scheme, rest) = pattern.split_once("://").unwrap_or(("*", pattern));
+      let (authority, name) = rest.split_once('/').unwrap_or((rest, ""));
+      let (namespace, kind) = authority.split_once('.').unwrap_or((authority, ""));
+      Segments {
+        scheme: scheme.to_owned(),
+        namespace: namespace.to_owned(),
+        kind: kind.to_owned(),
+        name: name.to_owned(),
+      }
+    });
+    Self {
+      pattern: pattern.to_owned(),
+      segments,
+    }
+  }
+
+  /// A pattern that matches every [Entity].
+  pub fn any() -> Self {
+    Self::new("*")
+  }
+
+  /// Returns true if the passed [Entity] matches this pattern.
+  #[must_use]
+  pub fn matches(&self, entity: &Entity) -> bool {
+    let segments = match &self.segments {
+      Some(segments) => segments,
+      None => return true,
+    };
+    let (namespace, kind, name, msg): (&str, &str, &str, Option<&str>) = match entity {
+      Entity::Test(msg) => ("test", "sys", "", Some(msg)),
+      Entity::System(e) => (&e.name, "sys", "", Some(&e.value)),
+      Entity::Invalid => ("invalid", "sys", "", None),
+      Entity::Schematic(name) => (name, "schem", "", None),
+      Entity::Component(ns, id) => (ns, "prov", id, None),
+      Entity::Provider(name) => (name, "prov", "", None),
+      Entity::Client(id) => (id, "client", "", None),
+      Entity::Host(id) => (id, "host", "", None),
+      Entity::Reference(id) => (id, "ref", "", None),
+    };
+    // Messages are part of the name segment, as they are in the entity's URL.
+    let name: &[&str] = match msg {
+      Some(msg) => &[name, "?msg=", msg],
+      None => &[name],
+    };
+    wildcard_match(&segments.scheme, &[URL_SCHEME])
+      && wildcard_match(&segments.namespace, &[namespace])
+      && wildcard_match(&segments.kind, &[kind])
+      && wildcard_match(&segments.name, name)
+  }
+}
+
+impl Default for EntityPattern {
+  fn default() -> Self {
+    Self::any()
+  }
+}
+
+impl Display for EntityPattern {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.pattern)
+  }
+}
+
+impl From<&Entity> for EntityPattern {
+  fn from(entity: &Entity) -> Self {
+    Self::new(entity.url())
+  }
+}
+
+impl From<String> for EntityPattern {
+  fn from(pattern: String) -> Self {
+    Self::new(pattern)
+  }
+}
+
+impl From<EntityPattern> for String {
+  fn from(pattern: EntityPattern) -> Self {
+    pattern.pattern
+  }
+}
+
+/// A single rule in a [Policy].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[must_use]
+pub struct PolicyRule {
+  /// Whether matching invocations are allowed or denied.
+  pub effect: Effect,
+  /// The pattern the invocation's origin must match.
+  #[serde(default)]
+  pub origin: EntityPattern,
+  /// The pattern the invocation's target must match.
+  #[serde(default)]
+  pub target: EntityPattern,
+}
+
+impl PolicyRule {
+  /// Create a rule that allows `origin` to invoke `target`.
+  pub fn allow(origin: EntityPattern, target: EntityPattern) -> Self {
+    Self {
+      effect: Effect::Allow,
+      origin,
+      target,
+    }
+  }
+
+  /// Create a rule that denies `origin` from invoking `target`.
+  pub fn deny(origin: EntityPattern, target: EntityPattern) -> Self {
+    Self {
+      effect: Effect::Deny,
+      origin,
+      target,
+    }
+  }
+
+  /// Returns true if this rule applies to the passed origin and target.
+  #[must_use]
+  pub fn matches(&self, origin: &Entity, target: &Entity) -> bool {
+    self.origin.matches(origin) && self.target.matches(target)
+  }
+}
+
+/// A set of rules deciding whether an origin [Entity] may invoke a target [Entity].
+///
+/// Deny rules take precedence over allow rules. When no rule matches, the policy's
+/// default effect applies, which is [Effect::Deny] unless configured otherwise.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[must_use]
+pub struct Policy {
+  /// The effect applied when no rule matches.
+  #[serde(default)]
+  pub default: Effect,
+  /// The policy's rules.
+  #[serde(default)]
+  pub rules: Vec<PolicyRule>,
+}
+
+impl Policy {
+  /// Create a new, empty [Policy] that denies everything.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a rule to the policy.
+  pub fn with_rule(mut self, rule: PolicyRule) -> Self {
+    self.rules.push(rule);
+    self
+  }
+
+  /// Set the effect applied when no rule matches.
+  pub fn with_default(mut self, default: Effect) -> Self {
+    self.default = default;
+    self
+  }
+
+  /// Parse a [Policy] from a JSON string.
+  #[cfg(feature = "json")]
+  pub fn from_json(src: &str) -> Result<Self, Error> {
+    serde_json::from_str(src).map_err(|e| Error::PolicyError(e.to_string()))
+  }
+
+  /// Parse a [Policy] from a YAML string.
+  #[cfg(feature = "yaml")]
+  pub fn from_yaml(src: &str) -> Result<Self, Error> {
+    serde_yaml::from_str(src).map_err(|e| Error::PolicyError(e.to_string()))
+  }
+
+  /// Decide whether `origin` may invoke `target`.
+  pub fn evaluate(&self, origin: &Entity, target: &Entity) -> Result<(), PolicyDenial> {
+    let mut allowed = false;
+    for (index, rule) in self.rules.iter().enumerate() {
+      if !rule.matches(origin, target) {
+        continue;
+      }
+      match rule.effect {
+        Effect::Deny => {
+          return Err(PolicyDenial {
+            origin: origin.clone(),
+            target: target.clone(),
+            reason: DenialReason::DeniedByRule(index),
+          })
+        }
+        Effect::Allow => allowed = true,
+      }
+    }
+    if allowed || self.default == Effect::Allow {
+      Ok(())
+    } else {
+      Err(PolicyDenial {
+        origin: origin.clone(),
+        target: target.clone(),
+        reason: DenialReason::NoMatchingRule,
+      })
+    }
+  }
+}
+
+/// Why a [Policy] denied an invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenialReason {
+  /// The rule at the contained index explicitly denied the invocation.
+  DeniedByRule(usize),
+  /// No rule allowed the invocation and the policy denies by default.
+  NoMatchingRule,
+}
+
+/// The structured result of a denied [Policy] evaluation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyDenial {
+  /// The origin of the denied invocation.
+  pub origin: Entity,
+  /// The target of the denied invocation.
+  pub target: Entity,
+  /// Why the invocation was denied.
+  pub reason: DenialReason,
+}
+
+impl std::error::Error for PolicyDenial {}
+
+impl Display for PolicyDenial {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.reason {
+      DenialReason::DeniedByRule(index) => write!(
+        f,
+        "{} may not invoke {}: denied by rule {}",
+        self.origin, self.target, index
+      ),
+      DenialReason::NoMatchingRule => write!(f, "{} may not invoke {}: no rule allows it", self.origin, self.target),
+    }
+  }
+}
+
+/// Match a pattern against text made of several parts, without joining them.
+fn wildcard_match(pattern: &str, text: &[&str]) -> bool {
+  let pattern = pattern.as_bytes();
+  let len: usize = text.iter().map(|part| part.len()).sum();
+  let byte = |mut i: usize| {
+    for part in text {
+      if i < part.len() {
+        return part.as_bytes()[i];
+      }
+      i -= part.len();
+    }
+    0
+  };
+  let (mut p, mut t) = (0, 0);
+  let mut backtrack: Option<(usize, usize)> = None;
+  while t < len {
+    if p < pattern.len() && pattern[p] == b'*' {
+      backtrack = Some((p, t));
+      p += 1;
+    } else if p < pattern.len() && pattern[p] == byte(t) {
+      p += 1;
+      t += 1;
+    } else if let Some((star, matched)) = backtrack {
+      p = star + 1;
+      t = matched + 1;
+      backtrack = Some((star, matched + 1));
+    } else {
+      return false;
+    }
+  }
+  pattern[p..].iter().all(|c| *c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn tenant_policy() -> Policy {
+    Policy::new()
+      .with_rule(PolicyRule::allow(
+        EntityPattern::new("ofp://tenant_a.client/"),
+        EntityPattern::new("ofp://tenant_a.prov/*"),
+      ))
+      .with_rule(PolicyRule::deny(
+        EntityPattern::any(),
+        EntityPattern::new("ofp://tenant_a.prov/admin_*"),
+      ))
+  }
+
+  #[test]
+  fn test_patterns() {
+    let entity = Entity::component("ns", "comp");
+    assert!(EntityPattern::new("ofp://ns.prov/comp").matches(&entity));
+    assert!(EntityPattern::new("ofp://ns.prov/*").matches(&entity));
+    assert!(EntityPattern::new("*.prov/*").matches(&entity));
+    assert!(EntityPattern::any().matches(&entity));
+    assert!(!EntityPattern::new("ofp://other.prov/*").matches(&entity));
+    assert!(!EntityPattern::new("ofp://ns.prov/").matches(&entity));
+    assert!(!EntityPattern::new("ofp://n*").matches(&entity));
+
+    // A `*` stays within its segment, and an entity's fields are never split on separators.
+    assert!(!EntityPattern::new("ofp://ns.prov/*").matches(&Entity::component("ns.prov/x", "comp")));
+    assert!(!EntityPattern::new("ofp://ns*comp").matches(&entity));
+    assert!(EntityPattern::new("ofp://test.sys/?msg=*").matches(&Entity::test("hello")));
+    assert!(EntityPattern::from(&Entity::test("hello")).matches(&Entity::test("hello")));
+    assert!(!EntityPattern::from(&Entity::test("hello")).matches(&Entity::test("bye")));
+  }
+
+  #[test]
+  fn test_evaluate() {
+    let policy = tenant_policy();
+    let client_a = Entity::client("tenant_a");
+    let client_b = Entity::client("tenant_b");
+
+    assert_eq!(
+      policy.evaluate(&client_a, &Entity::component("tenant_a", "get")),
+      Ok(())
+    );
+    assert_eq!(
+      policy
+        .evaluate(&client_b, &Entity::component("tenant_a", "get"))
+        .map_err(|e| e.reason),
+      Err(DenialReason::NoMatchingRule)
+    );
+    assert_eq!(
+      policy
+        .evaluate(&client_a, &Entity::component("tenant_a", "admin_drop"))
+        .map_err(|e| e.reason),
+      Err(DenialReason::DeniedByRule(1))
+    );
+    for namespace in ["tenant_attacker", "tenant_a_admin"] {
+      assert_eq!(
+        policy
+          .evaluate(&client_a, &Entity::component(namespace, "get"))
+          .map_err(|e| e.reason),
+        Err(DenialReason::NoMatchingRule),
+        "{} is another tenant",
+        namespace
+      );
+    }
+  }
+
+  #[test]
+  fn test_default_allow() {
+    let policy = tenant_policy().with_default(Effect::Allow);
+    let client_b = Entity::client("tenant_b");
+
+    assert_eq!(
+      policy.evaluate(&client_b, &Entity::component("tenant_b", "get")),
+      Ok(())
+    );
+    assert!(policy
+      .evaluate(&client_b, &Entity::component("tenant_a", "admin_drop"))
+      .is_err());
+  }
+
+  #[cfg(feature = "json")]
+  #[test]
+  fn test_from_json() -> Result<(), Error> {
+    let policy = Policy::from_json(
+      r#"{
+        "rules": [
+          { "effect": "allow", "origin": "ofp://tenant_a.client/", "target": "ofp://tenant_a.prov/*" },
+          { "effect": "deny", "target": "ofp://tenant_a.prov/admin_*" }
+        ]
+      }"#,
+    )?;
+    assert_eq!(policy, tenant_policy());
+    Ok(())
+  }
+
+  #[cfg(feature = "yaml")]
+  #[test]
+  fn test_from_yaml() -> Result<(), Error> {
+    let policy = Policy::from_yaml(
+      r#"
+default: deny
+rules:
+  - effect: allow
+    origin: ofp://tenant_a.client/
+    target: ofp://tenant_a.prov/*
+  - effect: deny
+    target: ofp://tenant_a.prov/admin_*
+"#,
+    )?;
+    assert_eq!(policy, tenant_policy());
+    Ok(())
+  }
+}
@@ -18,6 +18,18 @@ pub enum TransportError {
   #[error("Invalid payload")]
   Invalid,
 
+  /// Error used when a configuration payload does not match its schema.
+  #[error("Invalid config: {0}")]
+  InvalidConfig(String),
+
+  /// Error used when a port that carries at most one value sent more.
+  #[error("Port '{0}' sent more than one value")]
+  ExtraMessage(String),
+
+  /// Error used when a port that requires a value did not send one.
+  #[error("Port '{0}' did not send a value")]
+  MissingMessage(String),
+
   /// Error from the actual payload.
   #[error("{0}")]
   Error(String),
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use vino_entity::Entity;
+use vino_entity::{Entity, Policy, PolicyDenial};
 
 use crate::TransportMap;
 
@@ -97,6 +97,11 @@ impl Invocation {
   pub fn origin_url(&self) -> String {
     self.origin.url()
   }
+
+  /// Check whether the invocation's origin is allowed to invoke its target under the passed [Policy].
+  pub fn authorize(&self, policy: &Policy) -> Result<(), PolicyDenial> {
+    policy.evaluate(&self.origin, &self.target)
+  }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
@@ -121,4 +126,26 @@ pub(crate) fn get_uuid() -> Uuid {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+  use vino_entity::{DenialReason, EntityPattern, PolicyRule};
+
+  use super::*;
+
+  #[test]
+  fn test_authorize() {
+    let policy = Policy::new().with_rule(PolicyRule::allow(
+      EntityPattern::new("ofp://tenant_a.client/"),
+      EntityPattern::new("ofp://tenant_a.prov/*"),
+    ));
+    let invocation = |origin: Entity, target: Entity| Invocation::new(origin, target, TransportMap::new(), None);
+
+    let allowed = invocation(Entity::client("tenant_a"), Entity::component("tenant_a", "get"));
+    assert_eq!(allowed.authorize(&policy), Ok(()));
+
+    let denied = invocation(Entity::client("tenant_a"), Entity::component("tenant_attacker", "get"));
+    let denial = denied.authorize(&policy).unwrap_err();
+    assert_eq!(denial.reason, DenialReason::NoMatchingRule);
+    assert_eq!(denial.origin, denied.origin);
+    assert_eq!(denial.target, denied.target);
+  }
+}
@@ -92,6 +92,7 @@ pub mod invocation;
 
 #[cfg(feature = "invocation")]
 pub use invocation::{InherentData, Invocation};
+pub use message_transport::config::ConfigSchema;
 #[cfg(feature = "async")]
 pub use message_transport::stream::{BoxedTransportStream, TransportStream};
 #[cfg(feature = "json")]
@@ -3,6 +3,9 @@
 #[cfg(feature = "async")]
 pub(super) mod stream;
 
+/// The module that reads a [crate::TransportMap]'s configuration into typed values.
+pub(super) mod config;
+
 /// JSON-related module.
 #[cfg(feature = "json")]
 pub(super) mod transport_json;
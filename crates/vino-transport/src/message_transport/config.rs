@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serde::de::value::MapDeserializer;
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_value::Value;
+
+use crate::error::TransportError;
+
+/// A schema a configuration payload is checked against before [crate::TransportMap::get_config_typed] reads it.
+///
+/// `vino_types` implements this for its provider and component signatures.
+pub trait ConfigSchema {
+  /// Check the payload and return it with each value converted into its declared type, or a description of
+  /// everything that is wrong with it.
+  fn check(&self, config: &HashMap<String, Value>) -> Result<HashMap<String, Value>, String>;
+}
+
+/// Deserialize a configuration payload into `T`, naming the config in any value that does not fit.
+pub(crate) fn deserialize<T: DeserializeOwned>(config: &HashMap<String, Value>) -> Result<T, TransportError> {
+  let values = config
+    .iter()
+    .map(|(key, value)| (key.as_str(), ConfigValue { key, value }));
+  T::deserialize(MapDeserializer::new(values)).map_err(|e| TransportError::DeserializationError(e.0))
+}
+
+#[derive(Debug)]
+pub(crate) struct ConfigError(String);
+
+impl std::error::Error for ConfigError {}
+impl Display for ConfigError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+impl de::Error for ConfigError {
+  fn custom<T: Display>(msg: T) -> Self {
+    Self(msg.to_string())
+  }
+}
+
+/// A single configuration value and the key it was stored under.
+#[derive(Clone, Copy)]
+struct ConfigValue<'de> {
+  key: &'de str,
+  value: &'de Value,
+}
+
+impl<'de> ConfigValue<'de> {
+  fn error<E: Display>(&self, e: E) -> ConfigError {
+    ConfigError(format!("Invalid value for config '{}': {}", self.key, e))
+  }
+}
+
+impl<'de> IntoDeserializer<'de, ConfigError> for ConfigValue<'de> {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+impl<'de> de::Deserializer<'de> for ConfigValue<'de> {
+  type Error = ConfigError;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConfigError> {
+    de::Deserializer::deserialize_any(self.value.clone(), visitor).map_err(|e| self.error(e))
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ConfigError> {
+    de::Deserializer::deserialize_option(self.value.clone(), visitor).map_err(|e| self.error(e))
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, ConfigError> {
+    de::Deserializer::deserialize_newtype_struct(self.value.clone(), name, visitor).map_err(|e| self.error(e))
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, ConfigError> {
+    de::Deserializer::deserialize_enum(self.value.clone(), name, variants, visitor).map_err(|e| self.error(e))
+  }
+
+  forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+    tuple_struct map struct identifier ignored_any
+  }
+}
@@ -8,7 +8,7 @@ use parking_lot::Mutex;
 use tokio_stream::{Stream, StreamExt};
 
 use super::transport_wrapper::TransportWrapper;
-use crate::{MessageSignal, MessageTransport};
+use crate::{Error, MessageSignal, MessageTransport};
 
 /// A boxed [Stream] that produces [TransportWrapper]s
 pub type BoxedTransportStream = Pin<Box<dyn Stream<Item = TransportWrapper> + Send + Sync + 'static>>;
@@ -103,6 +103,27 @@ impl TransportStream {
       .collect()
   }
 
+  /// Collect the [TransportWrapper] from a port that sends exactly one value.
+  ///
+  /// Fails if the port sent no value or more than one.
+  pub async fn collect_single<T: AsRef<str> + Send>(&mut self, port: T) -> crate::Result<TransportWrapper> {
+    self
+      .collect_optional(port.as_ref())
+      .await?
+      .ok_or_else(|| Error::MissingMessage(port.as_ref().to_owned()))
+  }
+
+  /// Collect the [TransportWrapper] from a port that sends at most one value.
+  ///
+  /// Fails if the port sent more than one value.
+  pub async fn collect_optional<T: AsRef<str> + Send>(&mut self, port: T) -> crate::Result<Option<TransportWrapper>> {
+    let mut messages: Vec<TransportWrapper> = self.collect_port(port.as_ref()).await;
+    if messages.len() > 1 {
+      return Err(Error::ExtraMessage(port.as_ref().to_owned()));
+    }
+    Ok(messages.pop())
+  }
+
   /// Returns the buffered number of ports and total number of messages.
   pub fn buffered_size(&self) -> (u8, usize) {
     let mut num_keys = 0;
@@ -151,4 +172,29 @@ mod tests {
     assert_eq!(b_msgs.len(), 2);
     Ok(())
   }
+
+  #[test_log::test(tokio::test)]
+  async fn test_cardinality() -> Result<(), Box<dyn std::error::Error>> {
+    let (tx, rx) = unbounded_channel();
+    let message = MessageTransport::success(&String::from("Test"));
+
+    tx.send(TransportWrapper::new("single", message.clone()))?;
+    tx.send(TransportWrapper::done("single"))?;
+    tx.send(TransportWrapper::new("many", message.clone()))?;
+    tx.send(TransportWrapper::new("many", message.clone()))?;
+    tx.send(TransportWrapper::new_system_close())?;
+    let mut stream = TransportStream::new(UnboundedReceiverStream::new(rx));
+
+    assert_eq!(stream.collect_single("single").await?.payload, message);
+    assert!(stream.collect_optional("none").await?.is_none());
+    assert!(matches!(
+      stream.collect_single("none").await,
+      Err(Error::MissingMessage(port)) if port == "none"
+    ));
+    assert!(matches!(
+      stream.collect_optional("many").await,
+      Err(Error::ExtraMessage(port)) if port == "many"
+    ));
+    Ok(())
+  }
 }
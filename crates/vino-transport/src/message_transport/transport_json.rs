@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Serialize};
+use vino_codec::timestamp::{self, ExtData, TIMESTAMP_EXT};
 
 use crate::{Error, Failure, MessageSignal, MessageTransport, Success};
 /// A simplified JSON representation of a MessageTransport
@@ -125,19 +127,28 @@ fn handle_result_conversion(result: Result<serde_json::Value, String>) -> Transp
 impl MessageTransport {
   /// Converts a [MessageTransport] into [serde_json::Value]
   /// representation of a [TransportJson]
+  ///
+  /// MessagePack timestamps become RFC3339 strings, binary data becomes an array of byte values and map keys
+  /// that are not strings are written as text.
   #[must_use]
   pub fn as_json(&self) -> serde_json::Value {
     let output = match self {
       MessageTransport::Success(success) => match success {
         Success::MessagePack(bytes) => handle_result_conversion(
-          vino_codec::messagepack::deserialize::<serde_json::Value>(&bytes).map_err(|e| e.to_string()),
+          vino_codec::messagepack::deserialize::<JsonValue>(bytes)
+            .map(|v| v.0)
+            .map_err(|e| e.to_string()),
         ),
         Success::Serialized(v) => handle_result_conversion(
-          vino_codec::raw::deserialize::<serde_json::Value>(v.clone()).map_err(|e| e.to_string()),
+          vino_codec::raw::deserialize::<JsonValue>(v.clone())
+            .map(|v| v.0)
+            .map_err(|e| e.to_string()),
+        ),
+        Success::Json(v) => handle_result_conversion(
+          vino_codec::json::deserialize::<JsonValue>(v)
+            .map(|v| v.0)
+            .map_err(|e| e.to_string()),
         ),
-        Success::Json(v) => {
-          handle_result_conversion(vino_codec::json::deserialize::<serde_json::Value>(&v).map_err(|e| e.to_string()))
-        }
       },
       MessageTransport::Failure(failure) => match &failure {
         Failure::Invalid => TransportJson {
@@ -196,3 +207,100 @@ impl MessageTransport {
       .collect()
   }
 }
+
+/// A [serde_json::Value] read from any format, including the MessagePack values JSON has no equivalent for.
+struct JsonValue(serde_json::Value);
+
+impl<'de> Deserialize<'de> for JsonValue {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(JsonVisitor { ext: false }).map(JsonValue)
+  }
+}
+
+struct JsonVisitor {
+  /// MessagePack extensions arrive as a newtype around a sequence of their type and data.
+  ext: bool,
+}
+
+impl<'de> Visitor<'de> for JsonVisitor {
+  type Value = serde_json::Value;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("any value")
+  }
+
+  fn visit_bool<E: de::Error>(self, v: bool) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::Bool(v))
+  }
+
+  fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::from(v))
+  }
+
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::from(v))
+  }
+
+  fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::from(v))
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::String(v.to_owned()))
+  }
+
+  fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::String(v))
+  }
+
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::Array(v.iter().map(|&b| b.into()).collect()))
+  }
+
+  fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::Null)
+  }
+
+  fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+    Ok(serde_json::Value::Null)
+  }
+
+  fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    JsonValue::deserialize(deserializer).map(|v| v.0)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(JsonVisitor { ext: true })
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+    let mut items = Vec::new();
+    if self.ext {
+      match seq.next_element::<JsonValue>()? {
+        Some(JsonValue(tag)) if tag.as_i64() == Some(i64::from(TIMESTAMP_EXT)) => {
+          let data: ExtData = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+          let (seconds, nanos) = timestamp::decode(&data.0).map_err(de::Error::custom)?;
+          return Ok(serde_json::Value::String(timestamp::to_rfc3339(seconds, nanos)));
+        }
+        Some(JsonValue(first)) => items.push(first),
+        None => {}
+      }
+    }
+    while let Some(JsonValue(item)) = seq.next_element()? {
+      items.push(item);
+    }
+    Ok(serde_json::Value::Array(items))
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+    let mut entries = serde_json::Map::new();
+    while let Some((JsonValue(key), JsonValue(value))) = map.next_entry()? {
+      let key = match key {
+        serde_json::Value::String(key) => key,
+        other => other.to_string(),
+      };
+      entries.insert(key, value);
+    }
+    Ok(serde_json::Value::Object(entries))
+  }
+}
@@ -10,6 +10,7 @@ use vino_codec::messagepack;
 #[cfg(feature = "raw")]
 use vino_codec::raw;
 
+use super::config::ConfigSchema;
 #[cfg(feature = "json")]
 use super::transport_json::TransportJson;
 use crate::error::TransportError;
@@ -19,7 +20,10 @@ pub(crate) type Result<T> = std::result::Result<T, TransportError>;
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[must_use]
 /// A wrapper for a map of [String]s to [MessageTransport]
-pub struct TransportMap(HashMap<String, MessageTransport>, Option<HashMap<String, String>>);
+pub struct TransportMap(
+  HashMap<String, MessageTransport>,
+  Option<HashMap<String, serde_value::Value>>,
+);
 
 impl TransportMap {
   /// Constructor for [TransportMap] with initial map
@@ -33,16 +37,28 @@ impl TransportMap {
   }
 
   /// Add a configuration payload to the [TransportMap].
-  pub fn with_config(&mut self, map: HashMap<String, String>) {
+  pub fn with_config(&mut self, map: HashMap<String, serde_value::Value>) {
     self.1 = Some(map);
   }
 
   /// Add a configuration payload to the [TransportMap].
   #[must_use]
-  pub fn get_config(&self) -> &Option<HashMap<String, String>> {
+  pub fn get_config(&self) -> &Option<HashMap<String, serde_value::Value>> {
     &self.1
   }
 
+  /// Check the configuration payload against `schema` and deserialize it into a typed configuration struct.
+  ///
+  /// The schema converts each value into its declared type, and the converted values must then fit the
+  /// fields of `T`. A missing payload reads as an empty one. A payload that does not match the schema fails
+  /// with [Error::InvalidConfig].
+  pub fn get_config_typed<T: DeserializeOwned>(&self, schema: &impl ConfigSchema) -> Result<T> {
+    let config = schema
+      .check(self.1.as_ref().unwrap_or(&HashMap::new()))
+      .map_err(Error::InvalidConfig)?;
+    super::config::deserialize(&config)
+  }
+
   /// Deserialize a CLI output JSON Object into a [TransportMap].
   #[cfg(feature = "json")]
   pub fn from_json_output(json: &str) -> Result<Self> {
@@ -74,6 +90,10 @@ impl TransportMap {
 
   #[cfg(feature = "json")]
   /// Turn a list of "field=value" strings into a [TransportMap] of [MessageTransport::Json] items.
+  ///
+  /// The values are passed along untyped. To convert each value into its port's declared type, use
+  /// `vino_types::ComponentSignature::coerce_kv`, which lives in `vino-types` because that crate depends on
+  /// this one and signatures cannot be read here.
   pub fn from_kv_json(values: &[String]) -> Result<Self> {
     let mut payload = TransportMap::new();
     for input in values {
@@ -356,6 +376,8 @@ fn de_err<T: Display>(e: T) -> Error {
 
 #[cfg(test)]
 mod tests {
+  use serde_value::Value;
+
   use super::*;
   use crate::MessageTransport;
 
@@ -373,4 +395,111 @@ mod tests {
 
     Ok(())
   }
+
+  /// A schema that only checks for unexpected keys.
+  struct Keys(&'static [&'static str]);
+
+  impl ConfigSchema for Keys {
+    fn check(&self, config: &HashMap<String, Value>) -> std::result::Result<HashMap<String, Value>, String> {
+      config
+        .keys()
+        .find(|key| !self.0.contains(&key.as_str()))
+        .map_or_else(|| Ok(config.clone()), |key| Err(format!("unexpected config '{}'", key)))
+    }
+  }
+
+  fn config(entries: Vec<(&str, Value)>) -> HashMap<String, Value> {
+    entries.into_iter().map(|(k, v)| (k.to_owned(), v)).collect()
+  }
+
+  #[test_log::test]
+  fn test_config_typed() -> Result<()> {
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Mode {
+      Fast,
+      Safe,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+      host: String,
+      port: u16,
+      verbose: bool,
+      ratio: f32,
+      mode: Mode,
+      retries: Option<u8>,
+      #[serde(default)]
+      tags: Vec<String>,
+    }
+
+    let mut map = TransportMap::new();
+    map.with_config(config(vec![
+      ("host", Value::String("localhost".to_owned())),
+      ("port", Value::U64(8080)),
+      ("verbose", Value::Bool(true)),
+      ("ratio", Value::F64(0.5)),
+      ("mode", Value::String("safe".to_owned())),
+      ("retries", Value::Option(Some(Box::new(Value::U8(3))))),
+    ]));
+    let schema = Keys(&["host", "port", "verbose", "ratio", "mode", "retries", "tags"]);
+    let typed: Config = map.get_config_typed(&schema)?;
+    assert_eq!(
+      typed,
+      Config {
+        host: "localhost".to_owned(),
+        port: 8080,
+        verbose: true,
+        ratio: 0.5,
+        mode: Mode::Safe,
+        retries: Some(3),
+        tags: vec![],
+      }
+    );
+
+    map.with_config(config(vec![("port", Value::U64(80000))]));
+    let result = map.get_config_typed::<Config>(&schema);
+    assert!(matches!(result, Err(Error::DeserializationError(e)) if e.contains("'port'")));
+
+    map.with_config(config(vec![("port", Value::String("8080".to_owned()))]));
+    let result = map.get_config_typed::<Config>(&schema);
+    assert!(matches!(result, Err(Error::DeserializationError(e)) if e.contains("'port'")));
+
+    let result = TransportMap::new().get_config_typed::<Config>(&schema);
+    assert!(matches!(result, Err(Error::DeserializationError(e)) if e.contains("missing field `host`")));
+
+    map.with_config(config(vec![("hots", Value::String("localhost".to_owned()))]));
+    let result = map.get_config_typed::<Config>(&schema);
+    assert!(matches!(result, Err(Error::InvalidConfig(e)) if e.contains("'hots'")));
+    Ok(())
+  }
+
+  #[test_log::test]
+  fn test_config_compound() -> Result<()> {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+      tags: Vec<String>,
+      limits: HashMap<String, u32>,
+    }
+
+    let mut map = TransportMap::new();
+    map.with_config(config(vec![
+      (
+        "tags",
+        Value::Seq(vec![Value::String("a".to_owned()), Value::String("b".to_owned())]),
+      ),
+      (
+        "limits",
+        Value::Map(
+          [(Value::String("reads".to_owned()), Value::U32(10))]
+            .into_iter()
+            .collect(),
+        ),
+      ),
+    ]));
+    let typed: Config = map.get_config_typed(&Keys(&["tags", "limits"]))?;
+    assert_eq!(typed.tags, vec!["a", "b"]);
+    assert_eq!(typed.limits, HashMap::from([("reads".to_owned(), 10)]));
+    Ok(())
+  }
 }
@@ -0,0 +1,176 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// How many values a component port carries in a single invocation.
+///
+/// Consumers of a single-value port read it with `TransportStream::collect_single`, optional ports
+/// with `TransportStream::collect_optional`, and streams with `TransportStream::collect_port`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cardinality {
+  /// Exactly one value.
+  #[default]
+  Single,
+  /// At most one value.
+  Optional,
+  /// Any number of values.
+  Stream,
+}
+
+impl Cardinality {
+  /// Returns true for [Cardinality::Single], the default.
+  #[must_use]
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  pub fn is_single(&self) -> bool {
+    *self == Cardinality::Single
+  }
+
+  /// Returns true if a port may carry more than one value.
+  #[must_use]
+  pub fn is_stream(self) -> bool {
+    self == Cardinality::Stream
+  }
+
+  /// Returns true if a port may carry no value.
+  #[must_use]
+  pub fn allows_none(self) -> bool {
+    self != Cardinality::Single
+  }
+
+  /// Returns true if every number of values `other` allows is also allowed by this cardinality.
+  #[must_use]
+  pub fn includes(self, other: Cardinality) -> bool {
+    match self {
+      Cardinality::Stream => true,
+      Cardinality::Optional => other != Cardinality::Stream,
+      Cardinality::Single => other == Cardinality::Single,
+    }
+  }
+
+  /// Check the number of values a port carried.
+  pub fn check<T: AsRef<str>>(self, port: T, count: usize) -> Result<(), CardinalityError> {
+    if count == 0 && !self.allows_none() {
+      Err(CardinalityError::Missing(port.as_ref().to_owned()))
+    } else if count > 1 && !self.is_stream() {
+      Err(CardinalityError::Extra(port.as_ref().to_owned()))
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl Display for Cardinality {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Cardinality::Single => write!(f, "single"),
+      Cardinality::Optional => write!(f, "optional"),
+      Cardinality::Stream => write!(f, "stream"),
+    }
+  }
+}
+
+/// A port that carried a different number of values than its [Cardinality] allows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardinalityError {
+  /// A port that requires a value did not send one.
+  Missing(String),
+  /// A port that carries at most one value sent another.
+  Extra(String),
+  /// A message arrived on a port the signature does not declare.
+  Undeclared(String),
+}
+
+impl Error for CardinalityError {}
+impl Display for CardinalityError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CardinalityError::Missing(port) => write!(f, "port '{}' did not send a value", port),
+      CardinalityError::Extra(port) => write!(f, "port '{}' sent more than one value", port),
+      CardinalityError::Undeclared(port) => write!(f, "port '{}' is not declared", port),
+    }
+  }
+}
+
+#[cfg(feature = "transport")]
+pub use tracker::OutputTracker;
+
+#[cfg(feature = "transport")]
+mod tracker {
+  use std::collections::HashMap;
+
+  use vino_transport::{MessageTransport, TransportWrapper, SYSTEM_ID};
+
+  use super::CardinalityError;
+  use crate::maps::{MapWrapper, TypeMap};
+  use crate::signatures::ComponentSignature;
+
+  /// Checks the messages a component sends against the [super::Cardinality] of its outputs.
+  ///
+  /// Hosts [OutputTracker::record] each message as it arrives, which fails on the second value for a
+  /// port that carries at most one, and call [OutputTracker::finish] once the component is done.
+  #[derive(Debug)]
+  #[must_use]
+  pub struct OutputTracker<'a> {
+    outputs: &'a TypeMap,
+    counts: HashMap<String, usize>,
+    failed: bool,
+  }
+
+  impl ComponentSignature {
+    /// Create an [OutputTracker] for one invocation of the component.
+    pub fn track_outputs(&self) -> OutputTracker<'_> {
+      OutputTracker {
+        outputs: &self.outputs,
+        counts: HashMap::new(),
+        failed: false,
+      }
+    }
+  }
+
+  impl<'a> OutputTracker<'a> {
+    /// Count a message from the component. Signals and system messages are ignored.
+    pub fn record(&mut self, message: &TransportWrapper) -> Result<(), CardinalityError> {
+      if message.is_component_error() {
+        self.failed = true;
+        return Ok(());
+      }
+      if message.port == SYSTEM_ID || matches!(message.payload, MessageTransport::Signal(_)) {
+        return Ok(());
+      }
+      if !self.outputs.contains_key(&message.port) {
+        return Err(CardinalityError::Undeclared(message.port.clone()));
+      }
+      let count = self.counts.entry(message.port.clone()).or_default();
+      *count += 1;
+      if *count > 1 && !self.outputs.cardinality(&message.port).is_stream() {
+        return Err(CardinalityError::Extra(message.port.clone()));
+      }
+      Ok(())
+    }
+
+    /// Check the number of values every port sent, including the ports that sent none.
+    ///
+    /// A component-wide error ends an invocation early, so nothing is reported after one.
+    pub fn finish(self) -> Result<(), Vec<CardinalityError>> {
+      if self.failed {
+        return Ok(());
+      }
+      let errors: Vec<_> = self
+        .outputs
+        .ordered()
+        .into_iter()
+        .filter_map(|(port, _)| {
+          let count = self.counts.get(port).copied().unwrap_or_default();
+          self.outputs.cardinality(port).check(port, count).err()
+        })
+        .collect();
+      if errors.is_empty() {
+        Ok(())
+      } else {
+        Err(errors)
+      }
+    }
+  }
+}
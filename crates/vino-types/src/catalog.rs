@@ -0,0 +1,152 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+
+use crate::maps::{ComponentMap, ProviderMap};
+
+/// Names that two maps being merged both declare, with different signatures.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeConflict {
+  /// Provider namespaces declared with different [crate::ProviderSignature]s.
+  Providers(Vec<String>),
+  /// Component names declared with different [crate::ComponentSignature]s.
+  Components(Vec<String>),
+}
+
+impl Error for MergeConflict {}
+impl Display for MergeConflict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let (kind, names) = match self {
+      MergeConflict::Providers(names) => ("providers", names),
+      MergeConflict::Components(names) => ("components", names),
+    };
+    write!(f, "{} declared with different signatures: {}", kind, names.join(", "))
+  }
+}
+
+impl ProviderMap {
+  /// Add the providers of `other` under their namespaces.
+  ///
+  /// A namespace both maps declare must have equal signatures in each. If any does not, nothing is added.
+  pub fn merge(&mut self, other: ProviderMap) -> Result<(), MergeConflict> {
+    merge_entries(&mut self.0, other.0).map_err(MergeConflict::Providers)
+  }
+
+  /// Merge several maps into one, in order. See [ProviderMap::merge].
+  pub fn merge_all<I: IntoIterator<Item = ProviderMap>>(maps: I) -> Result<Self, MergeConflict> {
+    let mut merged = Self::default();
+    for map in maps {
+      merged.merge(map)?;
+    }
+    Ok(merged)
+  }
+}
+
+impl ComponentMap {
+  /// Add the components of `other`.
+  ///
+  /// A name both maps declare must have equal signatures in each. If any does not, nothing is added.
+  pub fn merge(&mut self, other: ComponentMap) -> Result<(), MergeConflict> {
+    merge_entries(&mut self.0, other.0).map_err(MergeConflict::Components)
+  }
+}
+
+/// Insert the entries of `other` that `target` lacks, or return the names whose values differ, in name order.
+fn merge_entries<T: PartialEq>(
+  target: &mut IndexMap<String, T>,
+  other: IndexMap<String, T>,
+) -> Result<(), Vec<String>> {
+  let mut conflicts: Vec<String> = other
+    .iter()
+    .filter(|(name, value)| target.get(*name).is_some_and(|existing| existing != *value))
+    .map(|(name, _)| name.clone())
+    .collect();
+  conflicts.sort();
+  if !conflicts.is_empty() {
+    return Err(conflicts);
+  }
+  for (name, value) in other {
+    target.entry(name).or_insert(value);
+  }
+  Ok(())
+}
+
+#[cfg(feature = "entity")]
+mod entities {
+  use std::sync::Arc;
+
+  use vino_entity::{Entity, EntityPattern};
+
+  use crate::maps::{ComponentMap, MapWrapper, ProviderMap};
+  use crate::signatures::ComponentSignature;
+
+  impl ProviderMap {
+    /// Look up the [ComponentSignature] an [Entity::Component] refers to, by namespace and name.
+    #[must_use]
+    pub fn resolve(&self, entity: &Entity) -> Option<&ComponentSignature> {
+      match entity {
+        Entity::Component(namespace, _) => self.get(namespace)?.components.resolve(entity),
+        _ => None,
+      }
+    }
+
+    /// Every component of every provider, with the [Entity] that refers to it.
+    pub fn entities(&self) -> impl Iterator<Item = (Entity, &ComponentSignature)> {
+      self
+        .ordered()
+        .into_iter()
+        .flat_map(|(namespace, provider)| provider.components.entities(namespace))
+    }
+
+    /// The providers with only the components whose [Entity] matches `pattern`.
+    /// Providers with no matching components are left out.
+    pub fn filter(&self, pattern: &EntityPattern) -> Self {
+      let mut filtered = Self::default();
+      for (namespace, provider) in self.ordered() {
+        let components = provider.components.filter(namespace, pattern);
+        if !components.is_empty() {
+          let mut provider = provider.clone();
+          provider.components = components;
+          filtered.insert(namespace, provider);
+        }
+      }
+      filtered
+    }
+  }
+
+  impl ComponentMap {
+    /// Look up the [ComponentSignature] an [Entity::Component] refers to, by name.
+    ///
+    /// The map holds a single provider's components, so the entity's namespace is not checked.
+    #[must_use]
+    pub fn resolve(&self, entity: &Entity) -> Option<&ComponentSignature> {
+      match entity {
+        Entity::Component(_, name) => self.get(name),
+        _ => None,
+      }
+    }
+
+    /// Every component, with the [Entity] that refers to it in `namespace`.
+    pub fn entities<'a>(&'a self, namespace: &str) -> impl Iterator<Item = (Entity, &'a ComponentSignature)> + 'a {
+      let namespace: Arc<str> = namespace.into();
+      self.ordered().into_iter().map(move |(name, component)| {
+        (
+          Entity::Component(Arc::clone(&namespace), name.as_str().into()),
+          component,
+        )
+      })
+    }
+
+    /// The components whose [Entity] in `namespace` matches `pattern`.
+    pub fn filter(&self, namespace: &str, pattern: &EntityPattern) -> Self {
+      Self(
+        self
+          .entities(namespace)
+          .filter(|(entity, _)| pattern.matches(entity))
+          .map(|(entity, component)| (entity.name().to_owned(), component.clone()))
+          .collect(),
+      )
+    }
+  }
+}
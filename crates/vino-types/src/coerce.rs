@@ -0,0 +1,653 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde_json::{Map, Number, Value};
+
+use crate::datetime::Datetime;
+use crate::decimal::Decimal;
+use crate::duration::Duration;
+use crate::maps::{MapWrapper, StructMap, TypeMap};
+use crate::signatures::{ComponentSignature, ProviderSignature, StructSignature, TypeSignature};
+use crate::url::Url;
+use crate::uuid::Uuid;
+use crate::validate::ref_name;
+
+/// A value that could not be converted into the type it was coerced to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoercionError {
+  /// The location of the value, e.g. `$.users[2].age` or the port name.
+  pub path: String,
+  /// Why the value could not be converted.
+  pub message: String,
+}
+
+impl CoercionError {
+  fn new<T: AsRef<str>>(path: &str, message: T) -> Self {
+    Self {
+      path: path.to_owned(),
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for CoercionError {}
+impl Display for CoercionError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.path, self.message)
+  }
+}
+
+impl TypeSignature {
+  /// Convert a loosely typed JSON value into the shape this signature declares.
+  ///
+  /// Numeric strings become numbers, integers become floats, RFC3339 strings become canonical [Datetime]s
+  /// and base64 strings become bytes. UUIDs, decimals, URLs and ISO 8601 durations are rewritten in their
+  /// canonical form, numbers become [Decimal] strings and numbers of seconds become [Duration]s.
+  /// Conversions that would lose information, like an overflowing integer or a float with a fractional
+  /// part going into an integer type, fail.
+  pub fn coerce(&self, value: &Value, types: &StructMap) -> Result<Value, CoercionError> {
+    Coercer { types }.coerce(self, value, "$")
+  }
+
+  /// Convert a raw string, e.g. a command line argument, into the shape this signature declares.
+  ///
+  /// The string is read as JSON where it can be, so `42`, `[1, 2]` and `"quoted"` all work, and taken
+  /// verbatim otherwise. See [TypeSignature::coerce].
+  pub fn coerce_str(&self, value: &str, types: &StructMap) -> Result<Value, CoercionError> {
+    Coercer { types }.coerce(self, &from_raw(self, value), "$")
+  }
+
+  /// Returns true if the signature's values are written as plain strings.
+  fn is_textual(&self) -> bool {
+    match self {
+      TypeSignature::String
+      | TypeSignature::Datetime
+      | TypeSignature::Uuid
+      | TypeSignature::Decimal
+      | TypeSignature::Url
+      | TypeSignature::Bytes
+      | TypeSignature::Enum { .. } => true,
+      TypeSignature::Optional { option } => option.is_textual(),
+      TypeSignature::Constrained { base, .. } => base.is_textual(),
+      _ => false,
+    }
+  }
+}
+
+impl ComponentSignature {
+  /// Turn a list of "port=value" strings into a [vino_transport::TransportMap], coercing each value into its
+  /// input's type.
+  ///
+  /// This is the typed counterpart of [vino_transport::TransportMap::from_kv_json]. Returns every malformed
+  /// pair, unknown port and failed conversion rather than stopping at the first.
+  #[cfg(feature = "transport")]
+  pub fn coerce_kv(
+    &self,
+    values: &[String],
+    types: &StructMap,
+  ) -> Result<vino_transport::TransportMap, Vec<CoercionError>> {
+    let coercer = Coercer { types };
+    let mut payload = vino_transport::TransportMap::new();
+    let mut errors = Vec::new();
+    for input in values {
+      let (port, raw) = match input.split_once('=') {
+        Some(pair) => pair,
+        None => {
+          errors.push(CoercionError::new(input, "invalid port=value pair"));
+          continue;
+        }
+      };
+      let sig = match self.inputs.get(port) {
+        Some(sig) => sig,
+        None => {
+          errors.push(CoercionError::new(port, "unexpected input"));
+          continue;
+        }
+      };
+      match coercer.coerce(sig, &from_raw(sig, raw), port) {
+        Ok(value) => {
+          let value = to_wire(sig, &value, types);
+          payload.insert(port, vino_transport::MessageTransport::success(&value));
+        }
+        Err(e) => errors.push(e),
+      }
+    }
+    if errors.is_empty() {
+      Ok(payload)
+    } else {
+      Err(errors)
+    }
+  }
+}
+
+impl ComponentSignature {
+  /// Check a configuration payload against the component's `config` schema and convert each value
+  /// into its declared type.
+  ///
+  /// String values, e.g. from the command line, are read as in [TypeSignature::coerce_str]. Missing keys
+  /// with a default are filled in and missing optional keys are left out.
+  pub fn coerce_config(
+    &self,
+    config: &HashMap<String, serde_value::Value>,
+    types: &StructMap,
+  ) -> Result<HashMap<String, serde_value::Value>, Vec<CoercionError>> {
+    coerce_config(&self.config, config, types)
+  }
+
+  /// Replace the [vino_transport::TransportMap]'s configuration with its coerced form.
+  /// See [ComponentSignature::coerce_config].
+  #[cfg(feature = "transport")]
+  pub fn configure(
+    &self,
+    payload: &mut vino_transport::TransportMap,
+    types: &StructMap,
+  ) -> Result<(), Vec<CoercionError>> {
+    let config = self.coerce_config(payload.get_config().as_ref().unwrap_or(&HashMap::new()), types)?;
+    payload.with_config(config);
+    Ok(())
+  }
+
+  /// The component's `config` schema in the form `TransportMap::get_config_typed` checks payloads against.
+  #[cfg(feature = "transport")]
+  #[must_use]
+  pub fn config_schema<'a>(&'a self, types: &'a StructMap) -> impl vino_transport::ConfigSchema + 'a {
+    ConfigSchema {
+      schema: &self.config,
+      types,
+    }
+  }
+}
+
+impl ProviderSignature {
+  /// Check a configuration payload against the provider's `config` schema and convert each value
+  /// into its declared type. See [ComponentSignature::coerce_config].
+  pub fn coerce_config(
+    &self,
+    config: &HashMap<String, serde_value::Value>,
+  ) -> Result<HashMap<String, serde_value::Value>, Vec<CoercionError>> {
+    coerce_config(&self.config, config, &self.types)
+  }
+}
+
+#[cfg(feature = "transport")]
+impl vino_transport::ConfigSchema for ProviderSignature {
+  fn check(&self, config: &HashMap<String, serde_value::Value>) -> Result<HashMap<String, serde_value::Value>, String> {
+    self.coerce_config(config).map_err(|e| join_errors(&e))
+  }
+}
+
+/// A `config` schema paired with the types its references resolve against.
+#[cfg(feature = "transport")]
+struct ConfigSchema<'a> {
+  schema: &'a TypeMap,
+  types: &'a StructMap,
+}
+
+#[cfg(feature = "transport")]
+impl vino_transport::ConfigSchema for ConfigSchema<'_> {
+  fn check(&self, config: &HashMap<String, serde_value::Value>) -> Result<HashMap<String, serde_value::Value>, String> {
+    coerce_config(self.schema, config, self.types).map_err(|e| join_errors(&e))
+  }
+}
+
+#[cfg(feature = "transport")]
+fn join_errors(errors: &[CoercionError]) -> String {
+  errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn coerce_config(
+  schema: &TypeMap,
+  config: &HashMap<String, serde_value::Value>,
+  types: &StructMap,
+) -> Result<HashMap<String, serde_value::Value>, Vec<CoercionError>> {
+  let coercer = Coercer { types };
+  let mut coerced = HashMap::new();
+  let mut errors: Vec<CoercionError> = config
+    .keys()
+    .filter(|key| schema.get(key.as_str()).is_none())
+    .map(|key| CoercionError::new(key, "unexpected config"))
+    .collect();
+  errors.sort_by(|a, b| a.path.cmp(&b.path));
+  for (key, sig) in schema.ordered() {
+    let value = match (config.get(key), schema.metadata(key).and_then(|m| m.default.as_ref())) {
+      (Some(serde_value::Value::String(raw)), _) => coercer.coerce(sig, &from_raw(sig, raw), key),
+      (Some(value), _) => serde_json::to_value(value)
+        .map_err(|e| CoercionError::new(key, e.to_string()))
+        .and_then(|value| coercer.coerce(sig, &value, key)),
+      (None, Some(default)) => coercer.coerce(sig, default, key),
+      (None, None) if matches!(sig, TypeSignature::Optional { .. }) => continue,
+      (None, None) => Err(CoercionError::new(key, "missing config")),
+    };
+    match value {
+      Ok(Value::Null) => {}
+      Ok(value) => {
+        coerced.insert(key.clone(), to_wire(sig, &value, types));
+      }
+      Err(e) => errors.push(e),
+    }
+  }
+  if errors.is_empty() {
+    Ok(coerced)
+  } else {
+    Err(errors)
+  }
+}
+
+/// The form of a coerced value to send over the wire, with [TypeSignature::Bytes] as binary rather than the
+/// list of numbers JSON has to use.
+fn to_wire(sig: &TypeSignature, value: &Value, types: &StructMap) -> serde_value::Value {
+  use serde_value::Value as Wire;
+  match (sig, value) {
+    (TypeSignature::Bytes, Value::Array(items)) => Wire::Bytes(
+      items
+        .iter()
+        .filter_map(|item| item.as_u64().and_then(|n| u8::try_from(n).ok()))
+        .collect(),
+    ),
+    (TypeSignature::Internal(t), _) => to_wire(&t.value_type(), value, types),
+    (TypeSignature::Optional { option }, _) if !value.is_null() => to_wire(option, value, types),
+    (TypeSignature::Constrained { base, .. }, _) => to_wire(base, value, types),
+    (TypeSignature::List { element } | TypeSignature::Array { element, .. }, Value::Array(items)) => {
+      Wire::Seq(items.iter().map(|item| to_wire(element, item, types)).collect())
+    }
+    (TypeSignature::Tuple { elements }, Value::Array(items)) => Wire::Seq(
+      elements
+        .iter()
+        .zip(items)
+        .map(|(element, item)| to_wire(element, item, types))
+        .collect(),
+    ),
+    (TypeSignature::Map { value: value_sig, .. }, Value::Object(map)) => Wire::Map(
+      map
+        .iter()
+        .map(|(k, v)| (Wire::String(k.clone()), to_wire(value_sig, v, types)))
+        .collect(),
+    ),
+    (TypeSignature::Ref { reference }, Value::Object(map)) => struct_to_wire(types.resolve(reference), map, types),
+    (TypeSignature::Union { tag, variants }, Value::Object(map)) => {
+      let def = map
+        .get(tag)
+        .and_then(Value::as_str)
+        .and_then(|name| variants.iter().find(|v| ref_name(v) == name))
+        .and_then(|variant| types.resolve(variant));
+      struct_to_wire(def, map, types)
+    }
+    _ => serde_value::to_value(value).unwrap_or(Wire::Unit),
+  }
+}
+
+/// See [to_wire]. Fields the struct does not declare, like a union's tag, are sent as they are.
+fn struct_to_wire(def: Option<&StructSignature>, map: &Map<String, Value>, types: &StructMap) -> serde_value::Value {
+  serde_value::Value::Map(
+    map
+      .iter()
+      .map(|(field, v)| {
+        let v = def.and_then(|def| def.fields.get(field)).map_or_else(
+          || serde_value::to_value(v).unwrap_or(serde_value::Value::Unit),
+          |sig| to_wire(sig, v, types),
+        );
+        (serde_value::Value::String(field.clone()), v)
+      })
+      .collect(),
+  )
+}
+
+/// Read a raw string as JSON, unless the signature expects text and the string is not a quoted JSON string.
+fn from_raw(sig: &TypeSignature, raw: &str) -> Value {
+  match serde_json::from_str::<Value>(raw) {
+    Ok(Value::Null) if matches!(sig, TypeSignature::Optional { .. }) => Value::Null,
+    Ok(Value::String(s)) => Value::String(s),
+    Ok(_) | Err(_) if sig.is_textual() => Value::String(raw.to_owned()),
+    Ok(value) => value,
+    Err(_) => Value::String(raw.to_owned()),
+  }
+}
+
+struct Coercer<'a> {
+  types: &'a StructMap,
+}
+
+impl<'a> Coercer<'a> {
+  fn coerce(&self, sig: &TypeSignature, value: &Value, path: &str) -> Result<Value, CoercionError> {
+    let mismatch = || CoercionError::new(path, format!("expected {}, found {}", sig, describe(value)));
+    let coerced = match sig {
+      TypeSignature::Raw | TypeSignature::Value | TypeSignature::Struct | TypeSignature::Link { .. } => value.clone(),
+      TypeSignature::Internal(t) => self.coerce(&t.value_type(), value, path)?,
+      TypeSignature::I8 => bounded::<i8>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::I16 => bounded::<i16>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::I32 => bounded::<i32>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::I64 => bounded::<i64>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::U8 => bounded::<u8>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::U16 => bounded::<u16>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::U32 => bounded::<u32>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::U64 => bounded::<u64>(sig, integer(sig, value, path)?, path)?,
+      TypeSignature::F32 => Value::from(float(
+        sig,
+        value,
+        path,
+        f32::MAX.into(),
+        1_i128 << f32::MANTISSA_DIGITS,
+      )?),
+      TypeSignature::F64 => Value::from(float(sig, value, path, f64::MAX, 1_i128 << f64::MANTISSA_DIGITS)?),
+      TypeSignature::Bool => match value {
+        Value::Bool(_) => value.clone(),
+        Value::String(s) if s == "true" || s == "false" => Value::Bool(s == "true"),
+        _ => return Err(mismatch()),
+      },
+      TypeSignature::String => match value {
+        Value::String(_) => value.clone(),
+        Value::Number(n) => Value::String(n.to_string()),
+        Value::Bool(b) => Value::String(b.to_string()),
+        _ => return Err(mismatch()),
+      },
+      TypeSignature::Datetime => canonical::<Datetime>(sig, value, path, "an RFC3339 datetime")?,
+      TypeSignature::Uuid => canonical::<Uuid>(sig, value, path, "a UUID")?,
+      TypeSignature::Url => canonical::<Url>(sig, value, path, "a URL")?,
+      TypeSignature::Decimal => match value {
+        Value::Number(n) => canonical::<Decimal>(sig, &Value::String(n.to_string()), path, "a decimal number")?,
+        _ => canonical::<Decimal>(sig, value, path, "a decimal number")?,
+      },
+      TypeSignature::Duration => match value {
+        Value::Number(n) => Value::String(seconds(n, path)?.to_string()),
+        _ => canonical::<Duration>(sig, value, path, "an ISO 8601 duration")?,
+      },
+      TypeSignature::Bytes => match value {
+        Value::String(s) => match base64::decode(s) {
+          Ok(bytes) => Value::from(bytes),
+          Err(e) => return Err(CoercionError::new(path, format!("invalid base64: {}", e))),
+        },
+        Value::Array(items) => Value::Array(
+          items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| self.coerce(&TypeSignature::U8, item, &format!("{}[{}]", path, i)))
+            .collect::<Result<_, _>>()?,
+        ),
+        _ => return Err(mismatch()),
+      },
+      TypeSignature::Enum { values } => match value {
+        Value::String(s) if values.contains(s) => value.clone(),
+        Value::String(s) => {
+          return Err(CoercionError::new(
+            path,
+            format!("'{}' is not one of {}", s, values.join(", ")),
+          ))
+        }
+        _ => return Err(mismatch()),
+      },
+      TypeSignature::Optional { option } => match value {
+        Value::Null => Value::Null,
+        _ => self.coerce(option, value, path)?,
+      },
+      TypeSignature::List { .. } | TypeSignature::Tuple { .. } | TypeSignature::Array { .. } => {
+        self.coerce_sequence(sig, value, path)?
+      }
+      TypeSignature::Constrained { base, constraints } => {
+        let coerced = self.coerce(base, value, path)?;
+        constraints
+          .check_json(&coerced)
+          .map_err(|e| CoercionError::new(path, e))?;
+        coerced
+      }
+      TypeSignature::Map { key, value: value_sig } => match value {
+        Value::Object(map) => self.coerce_map(key, value_sig, map, path)?,
+        _ => return Err(mismatch()),
+      },
+      TypeSignature::Ref { reference } => match self.types.resolve(reference) {
+        Some(def) => self.coerce_struct(def, value, path, None)?,
+        None => {
+          return Err(CoercionError::new(
+            path,
+            format!("unresolved reference '{}'", reference),
+          ))
+        }
+      },
+      TypeSignature::Union { tag, variants } => self.coerce_union(tag, variants, value, path)?,
+    };
+    Ok(coerced)
+  }
+
+  /// Coerce a [TypeSignature::List], [TypeSignature::Tuple] or [TypeSignature::Array], checking the length of the latter two.
+  fn coerce_sequence(&self, sig: &TypeSignature, value: &Value, path: &str) -> Result<Value, CoercionError> {
+    let items = match value {
+      Value::Array(items) => items,
+      _ => {
+        return Err(CoercionError::new(
+          path,
+          format!("expected {}, found {}", sig, describe(value)),
+        ))
+      }
+    };
+    let length = match sig {
+      TypeSignature::Tuple { elements } => Some(elements.len() as u64),
+      TypeSignature::Array { length, .. } => Some(*length),
+      _ => None,
+    };
+    if let Some(length) = length.filter(|length| *length != items.len() as u64) {
+      return Err(CoercionError::new(
+        path,
+        format!("expected {} items, found {}", length, items.len()),
+      ));
+    }
+    let item_sig = |i: usize| match sig {
+      TypeSignature::Tuple { elements } => &elements[i],
+      TypeSignature::List { element } | TypeSignature::Array { element, .. } => element.as_ref(),
+      _ => sig,
+    };
+    Ok(Value::Array(
+      items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| self.coerce(item_sig(i), item, &format!("{}[{}]", path, i)))
+        .collect::<Result<_, _>>()?,
+    ))
+  }
+
+  fn coerce_map(
+    &self,
+    key_sig: &TypeSignature,
+    value_sig: &TypeSignature,
+    map: &Map<String, Value>,
+    path: &str,
+  ) -> Result<Value, CoercionError> {
+    let mut coerced = Map::new();
+    for (key, value) in map {
+      let key_path = format!("{}[{}]", path, key);
+      // Keys stay strings on the wire, but must still read as the declared key type.
+      let key = match self.coerce(key_sig, &from_raw(key_sig, key), &key_path)? {
+        Value::String(s) => s,
+        other => other.to_string(),
+      };
+      coerced.insert(key, self.coerce(value_sig, value, &key_path)?);
+    }
+    Ok(Value::Object(coerced))
+  }
+
+  fn coerce_union(&self, tag: &str, variants: &[String], value: &Value, path: &str) -> Result<Value, CoercionError> {
+    let tag_path = format!("{}.{}", path, tag);
+    let name = match value.get(tag) {
+      Some(Value::String(name)) => name,
+      Some(other) => {
+        return Err(CoercionError::new(
+          &tag_path,
+          format!("expected string, found {}", describe(other)),
+        ))
+      }
+      None if value.is_object() => return Err(CoercionError::new(&tag_path, "missing tag")),
+      None => {
+        return Err(CoercionError::new(
+          path,
+          format!("expected union, found {}", describe(value)),
+        ))
+      }
+    };
+    let variant = variants
+      .iter()
+      .find(|v| ref_name(v) == name)
+      .ok_or_else(|| CoercionError::new(&tag_path, format!("unknown variant '{}'", name)))?;
+    let def = self
+      .types
+      .resolve(variant)
+      .ok_or_else(|| CoercionError::new(path, format!("unresolved reference '{}'", variant)))?;
+    self.coerce_struct(def, value, path, Some(tag))
+  }
+
+  /// Coerce each field of a map into a struct's field types, filling in missing fields that have a default.
+  /// A union's `tag` field is kept as is.
+  fn coerce_struct(
+    &self,
+    def: &StructSignature,
+    value: &Value,
+    path: &str,
+    tag: Option<&str>,
+  ) -> Result<Value, CoercionError> {
+    let map = value
+      .as_object()
+      .ok_or_else(|| CoercionError::new(path, format!("expected struct {}, found {}", def.name, describe(value))))?;
+    let mut coerced = Map::new();
+    for (field, v) in map {
+      let field_path = format!("{}.{}", path, field);
+      match def.fields.get(field) {
+        Some(sig) => {
+          coerced.insert(field.clone(), self.coerce(sig, v, &field_path)?);
+        }
+        None if tag == Some(field.as_str()) => {
+          coerced.insert(field.clone(), v.clone());
+        }
+        None => return Err(CoercionError::new(&field_path, "unexpected field")),
+      }
+    }
+    for (field, sig) in def.fields.ordered() {
+      if map.contains_key(field) {
+        continue;
+      }
+      let field_path = format!("{}.{}", path, field);
+      match def.fields.metadata(field).and_then(|m| m.default.as_ref()) {
+        Some(default) => {
+          coerced.insert(field.clone(), self.coerce(sig, default, &field_path)?);
+        }
+        None if matches!(sig, TypeSignature::Optional { .. }) => {}
+        None => return Err(CoercionError::new(&field_path, "missing field")),
+      }
+    }
+    Ok(Value::Object(coerced))
+  }
+}
+
+/// Read a JSON number or numeric string as an integer, rejecting anything with a fractional part.
+fn integer(sig: &TypeSignature, value: &Value, path: &str) -> Result<i128, CoercionError> {
+  let not_integer = |text: &str| CoercionError::new(path, format!("{} is not an integer", text));
+  match value {
+    Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+      (Some(i), _) => Ok(i.into()),
+      (_, Some(u)) => Ok(u.into()),
+      _ => n.as_f64().and_then(whole).ok_or_else(|| not_integer(&n.to_string())),
+    },
+    Value::String(s) => {
+      let trimmed = s.trim();
+      trimmed
+        .parse::<i128>()
+        .ok()
+        .or_else(|| trimmed.parse::<f64>().ok().and_then(whole))
+        .ok_or_else(|| not_integer(&format!("'{}'", s)))
+    }
+    _ => Err(CoercionError::new(
+      path,
+      format!("expected {}, found {}", sig, describe(value)),
+    )),
+  }
+}
+
+/// The integer value of a float, if it has no fractional part.
+fn whole(n: f64) -> Option<i128> {
+  (n.is_finite() && n.fract() == 0.0 && n.abs() < 2f64.powi(127)).then_some(n as i128)
+}
+
+fn bounded<T>(sig: &TypeSignature, n: i128, path: &str) -> Result<Value, CoercionError>
+where
+  T: TryFrom<i128> + Into<Value>,
+{
+  T::try_from(n)
+    .map(Into::into)
+    .map_err(|_| CoercionError::new(path, format!("{} overflows {}", n, sig)))
+}
+
+/// Read a JSON number or numeric string as a float no larger than `max`.
+///
+/// Integers must be smaller than `exact`, the point past which the float type can no longer represent every integer.
+fn float(sig: &TypeSignature, value: &Value, path: &str, max: f64, exact: i128) -> Result<f64, CoercionError> {
+  let n = match value {
+    Value::Number(n) if n.is_f64() => n.as_f64().unwrap_or(f64::NAN),
+    Value::Number(_) => {
+      let n = integer(sig, value, path)?;
+      if n.abs() > exact {
+        return Err(CoercionError::new(
+          path,
+          format!("{} cannot be represented exactly as {}", n, sig),
+        ));
+      }
+      n as f64
+    }
+    Value::String(s) => s
+      .trim()
+      .parse::<f64>()
+      .map_err(|_| CoercionError::new(path, format!("'{}' is not a number", s)))?,
+    _ => {
+      return Err(CoercionError::new(
+        path,
+        format!("expected {}, found {}", sig, describe(value)),
+      ))
+    }
+  };
+  if !n.is_finite() {
+    Err(CoercionError::new(path, format!("{} is not a finite number", value)))
+  } else if n.abs() > max {
+    Err(CoercionError::new(path, format!("{} overflows {}", value, sig)))
+  } else {
+    Ok(n)
+  }
+}
+
+/// Read a string as `T` and write it back in its canonical form.
+fn canonical<T: FromStr + Display>(
+  sig: &TypeSignature,
+  value: &Value,
+  path: &str,
+  format: &str,
+) -> Result<Value, CoercionError> {
+  match value {
+    Value::String(s) => s
+      .parse::<T>()
+      .map(|parsed| Value::String(parsed.to_string()))
+      .map_err(|_| CoercionError::new(path, format!("'{}' is not {}", s, format))),
+    _ => Err(CoercionError::new(
+      path,
+      format!("expected {}, found {}", sig, describe(value)),
+    )),
+  }
+}
+
+/// Read a number of seconds as a [Duration].
+fn seconds(n: &Number, path: &str) -> Result<Duration, CoercionError> {
+  if let Some(seconds) = n.as_u64() {
+    return Ok(Duration::new(seconds, 0));
+  }
+  n.as_f64()
+    .and_then(|seconds| std::time::Duration::try_from_secs_f64(seconds).ok())
+    .map(Duration::from)
+    .ok_or_else(|| CoercionError::new(path, format!("{} is not a number of seconds", n)))
+}
+
+fn describe(value: &Value) -> &'static str {
+  match value {
+    Value::Null => "null",
+    Value::Bool(_) => "bool",
+    Value::Number(n) if n.is_f64() => "float",
+    Value::Number(_) => "integer",
+    Value::String(_) => "string",
+    Value::Array(_) => "list",
+    Value::Object(_) => "map",
+  }
+}
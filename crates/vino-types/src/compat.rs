@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+
+use crate::cardinality::Cardinality;
+use crate::maps::{MapWrapper, TypeMap};
+use crate::signatures::{ComponentSignature, Constraints, ProviderSignature, StructSignature, TypeSignature};
+use crate::validate::ref_name;
+
+/// The kind of difference between two versions of a signature.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+  /// A component was added.
+  ComponentAdded,
+  /// A component was removed.
+  ComponentRemoved,
+  /// An input port was added.
+  InputAdded(TypeSignature),
+  /// An input port was removed.
+  InputRemoved(TypeSignature),
+  /// An input port's type changed.
+  InputChanged {
+    /// The previous type.
+    from: TypeSignature,
+    /// The new type.
+    to: TypeSignature,
+  },
+  /// An output port was added.
+  OutputAdded(TypeSignature),
+  /// An output port was removed.
+  OutputRemoved(TypeSignature),
+  /// An output port's type changed.
+  OutputChanged {
+    /// The previous type.
+    from: TypeSignature,
+    /// The new type.
+    to: TypeSignature,
+  },
+  /// The number of values a port carries changed.
+  CardinalityChanged {
+    /// The previous cardinality.
+    from: Cardinality,
+    /// The new cardinality.
+    to: Cardinality,
+  },
+  /// A config entry was added.
+  ConfigAdded(TypeSignature),
+  /// A config entry was removed.
+  ConfigRemoved(TypeSignature),
+  /// A config entry's type changed.
+  ConfigChanged {
+    /// The previous type.
+    from: TypeSignature,
+    /// The new type.
+    to: TypeSignature,
+  },
+  /// An input, config entry or struct field no longer has the default it had.
+  DefaultRemoved(serde_json::Value),
+  /// A type was added to the provider's types.
+  TypeAdded,
+  /// A type was removed from the provider's types.
+  TypeRemoved,
+  /// A struct field was added.
+  FieldAdded(TypeSignature),
+  /// A struct field was removed.
+  FieldRemoved(TypeSignature),
+  /// A struct field was removed and a field of the same type was added in its place.
+  FieldRenamed {
+    /// The previous name of the field.
+    from: String,
+    /// The new name of the field.
+    to: String,
+  },
+  /// A struct field's type changed.
+  FieldChanged {
+    /// The previous type.
+    from: TypeSignature,
+    /// The new type.
+    to: TypeSignature,
+  },
+}
+
+impl Display for ChangeKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      ChangeKind::ComponentAdded => write!(f, "component added"),
+      ChangeKind::ComponentRemoved => write!(f, "component removed"),
+      ChangeKind::InputAdded(sig) => write!(f, "input added ({})", sig),
+      ChangeKind::InputRemoved(sig) => write!(f, "input removed ({})", sig),
+      ChangeKind::InputChanged { from, to } => write!(f, "input changed from {} to {}", from, to),
+      ChangeKind::OutputAdded(sig) => write!(f, "output added ({})", sig),
+      ChangeKind::OutputRemoved(sig) => write!(f, "output removed ({})", sig),
+      ChangeKind::OutputChanged { from, to } => write!(f, "output changed from {} to {}", from, to),
+      ChangeKind::CardinalityChanged { from, to } => write!(f, "cardinality changed from {} to {}", from, to),
+      ChangeKind::ConfigAdded(sig) => write!(f, "config added ({})", sig),
+      ChangeKind::ConfigRemoved(sig) => write!(f, "config removed ({})", sig),
+      ChangeKind::ConfigChanged { from, to } => write!(f, "config changed from {} to {}", from, to),
+      ChangeKind::DefaultRemoved(default) => write!(f, "default removed ({})", default),
+      ChangeKind::TypeAdded => write!(f, "type added"),
+      ChangeKind::TypeRemoved => write!(f, "type removed"),
+      ChangeKind::FieldAdded(sig) => write!(f, "field added ({})", sig),
+      ChangeKind::FieldRemoved(sig) => write!(f, "field removed ({})", sig),
+      ChangeKind::FieldRenamed { from, to } => write!(f, "field renamed from {} to {}", from, to),
+      ChangeKind::FieldChanged { from, to } => write!(f, "field changed from {} to {}", from, to),
+    }
+  }
+}
+
+/// A single difference between two versions of a [ProviderSignature].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+  /// Where the change occurred, e.g. `components.add.inputs.left` or `types.Blog.fields.title`.
+  pub path: String,
+  /// What changed.
+  pub kind: ChangeKind,
+  /// Whether the change can break existing consumers.
+  pub breaking: bool,
+}
+
+impl Display for Change {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let severity = if self.breaking { "breaking" } else { "non-breaking" };
+    write!(f, "{}: {} [{}]", self.path, self.kind, severity)
+  }
+}
+
+/// Every difference found between two versions of a [ProviderSignature].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[must_use]
+pub struct CompatibilityReport {
+  /// The changes, ordered by path.
+  pub changes: Vec<Change>,
+}
+
+impl CompatibilityReport {
+  /// Returns true if no change is breaking.
+  #[must_use]
+  pub fn is_compatible(&self) -> bool {
+    !self.changes.iter().any(|c| c.breaking)
+  }
+
+  /// The breaking changes in the report.
+  pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+    self.changes.iter().filter(|c| c.breaking)
+  }
+}
+
+impl Display for CompatibilityReport {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for change in &self.changes {
+      writeln!(f, "{}", change)?;
+    }
+    Ok(())
+  }
+}
+
+impl ProviderSignature {
+  /// Compare this signature to a newer version of it and classify every difference.
+  ///
+  /// A change is breaking when a consumer written against this version could fail
+  /// against `next`: removed components or ports, new required inputs or config, inputs or config that
+  /// accept fewer values or lost their default, or outputs that produce values the old type did not allow.
+  pub fn check_compatibility(&self, next: &ProviderSignature) -> CompatibilityReport {
+    let mut changes = Vec::new();
+    for (name, old, new) in pairs(self.components.inner(), next.components.inner()) {
+      let path = format!("components.{}", name);
+      match (old, new) {
+        (Some(old), Some(new)) => compare_components(&path, old, new, &mut changes),
+        (Some(_), None) => changes.push(change(path, ChangeKind::ComponentRemoved, true)),
+        (None, Some(_)) => changes.push(change(path, ChangeKind::ComponentAdded, false)),
+        (None, None) => unreachable!(),
+      }
+    }
+    compare_sent("config", &self.config, &next.config, &CONFIG, &mut changes);
+    for (name, old, new) in pairs(self.types.inner(), next.types.inner()) {
+      let path = format!("types.{}", name);
+      match (old, new) {
+        (Some(old), Some(new)) => compare_structs(&path, old, new, &mut changes),
+        (Some(_), None) => changes.push(change(path, ChangeKind::TypeRemoved, true)),
+        (None, Some(_)) => changes.push(change(path, ChangeKind::TypeAdded, false)),
+        (None, None) => unreachable!(),
+      }
+    }
+    CompatibilityReport { changes }
+  }
+}
+
+fn change(path: String, kind: ChangeKind, breaking: bool) -> Change {
+  Change { path, kind, breaking }
+}
+
+/// The union of keys in two maps, sorted, with the value from each side.
+fn pairs<'a, T>(
+  old: &'a IndexMap<String, T>,
+  new: &'a IndexMap<String, T>,
+) -> Vec<(&'a String, Option<&'a T>, Option<&'a T>)> {
+  let mut keys: Vec<&String> = old.keys().chain(new.keys().filter(|k| !old.contains_key(*k))).collect();
+  keys.sort();
+  keys.into_iter().map(|k| (k, old.get(k), new.get(k))).collect()
+}
+
+/// Returns true if consumers may leave the entry out, because it is optional or has a default.
+fn is_omittable(map: &TypeMap, name: &str, sig: &TypeSignature) -> bool {
+  matches!(sig, TypeSignature::Optional { .. }) || map.metadata(name).is_some_and(|m| m.default.is_some())
+}
+
+/// The kinds of change reported for a map of entries consumers send.
+struct SentKinds {
+  added: fn(TypeSignature) -> ChangeKind,
+  removed: fn(TypeSignature) -> ChangeKind,
+  changed: fn(TypeSignature, TypeSignature) -> ChangeKind,
+}
+
+const INPUTS: SentKinds = SentKinds {
+  added: ChangeKind::InputAdded,
+  removed: ChangeKind::InputRemoved,
+  changed: |from, to| ChangeKind::InputChanged { from, to },
+};
+
+const CONFIG: SentKinds = SentKinds {
+  added: ChangeKind::ConfigAdded,
+  removed: ChangeKind::ConfigRemoved,
+  changed: |from, to| ChangeKind::ConfigChanged { from, to },
+};
+
+/// Compare entries consumers send, like inputs and config, under `path`.
+fn compare_sent(path: &str, old: &TypeMap, new: &TypeMap, kinds: &SentKinds, changes: &mut Vec<Change>) {
+  for (name, old_sig, new_sig) in pairs(old.inner(), new.inner()) {
+    let path = format!("{}.{}", path, name);
+    let (from, to) = (old.cardinality(name), new.cardinality(name));
+    if old_sig.is_some() && new_sig.is_some() && from != to {
+      // Consumers keep sending as many values as before, so the new cardinality must allow it.
+      changes.push(change(
+        path.clone(),
+        ChangeKind::CardinalityChanged { from, to },
+        !to.includes(from),
+      ));
+    }
+    match (old_sig, new_sig) {
+      (Some(from), Some(to)) => {
+        if from != to {
+          // Consumers keep sending what the old type allowed, so the new type must accept it.
+          let breaking = !is_assignable(from, to);
+          changes.push(change(
+            path.clone(),
+            (kinds.changed)(from.clone(), to.clone()),
+            breaking,
+          ));
+        }
+        compare_default(path, old, new, name, to, changes);
+      }
+      (Some(from), None) => changes.push(change(path, (kinds.removed)(from.clone()), true)),
+      (None, Some(to)) => changes.push(change(path, (kinds.added)(to.clone()), !is_omittable(new, name, to))),
+      (None, None) => {}
+    }
+  }
+}
+
+/// Consumers that left an entry out relied on its default, so losing it breaks them unless the entry is
+/// still optional.
+fn compare_default(
+  path: String,
+  old: &TypeMap,
+  new: &TypeMap,
+  name: &str,
+  to: &TypeSignature,
+  changes: &mut Vec<Change>,
+) {
+  let had = old.metadata(name).and_then(|m| m.default.as_ref());
+  let has = new.metadata(name).and_then(|m| m.default.as_ref());
+  if let (Some(default), None) = (had, has) {
+    changes.push(change(
+      path,
+      ChangeKind::DefaultRemoved(default.clone()),
+      !is_omittable(new, name, to),
+    ));
+  }
+}
+
+fn compare_components(path: &str, old: &ComponentSignature, new: &ComponentSignature, changes: &mut Vec<Change>) {
+  compare_sent(&format!("{}.config", path), &old.config, &new.config, &CONFIG, changes);
+  compare_sent(&format!("{}.inputs", path), &old.inputs, &new.inputs, &INPUTS, changes);
+  for (port, old_sig, new_sig) in pairs(old.outputs.inner(), new.outputs.inner()) {
+    let path = format!("{}.outputs.{}", path, port);
+    let (from, to) = (old.outputs.cardinality(port), new.outputs.cardinality(port));
+    if old_sig.is_some() && new_sig.is_some() && from != to {
+      // Consumers expect as many values as before, so the new cardinality must not send a different number.
+      changes.push(change(
+        path.clone(),
+        ChangeKind::CardinalityChanged { from, to },
+        !from.includes(to),
+      ));
+    }
+    match (old_sig, new_sig) {
+      (Some(from), Some(to)) if from != to => {
+        // Consumers expect what the old type allowed, so the new type must not produce anything else.
+        let breaking = !is_assignable(to, from);
+        changes.push(change(
+          path,
+          ChangeKind::OutputChanged {
+            from: from.clone(),
+            to: to.clone(),
+          },
+          breaking,
+        ));
+      }
+      (Some(from), None) => changes.push(change(path, ChangeKind::OutputRemoved(from.clone()), true)),
+      (None, Some(to)) => changes.push(change(path, ChangeKind::OutputAdded(to.clone()), false)),
+      _ => {}
+    }
+  }
+}
+
+/// Structs can be used as both inputs and outputs so only additive, optional changes are safe.
+fn compare_structs(path: &str, old: &StructSignature, new: &StructSignature, changes: &mut Vec<Change>) {
+  let renames = renamed_fields(&old.fields, &new.fields);
+  for (field, old_sig, new_sig) in pairs(old.fields.inner(), new.fields.inner()) {
+    let field_path = format!("{}.fields.{}", path, field);
+    match (old_sig, new_sig) {
+      (Some(from), Some(to)) => {
+        if from != to {
+          changes.push(change(
+            field_path.clone(),
+            ChangeKind::FieldChanged {
+              from: from.clone(),
+              to: to.clone(),
+            },
+            true,
+          ));
+        }
+        compare_default(field_path, &old.fields, &new.fields, field, to, changes);
+      }
+      (Some(from), None) => match renames.get(field.as_str()) {
+        Some(to) => changes.push(change(
+          field_path,
+          ChangeKind::FieldRenamed {
+            from: field.clone(),
+            to: (*to).to_owned(),
+          },
+          true,
+        )),
+        None => changes.push(change(field_path, ChangeKind::FieldRemoved(from.clone()), true)),
+      },
+      (None, Some(to)) if !renames.values().any(|renamed| *renamed == field.as_str()) => {
+        changes.push(change(
+          field_path,
+          ChangeKind::FieldAdded(to.clone()),
+          !is_omittable(&new.fields, field, to),
+        ));
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Pair up removed and added fields that have the same type when the match is unambiguous.
+fn renamed_fields<'a>(old: &'a TypeMap, new: &'a TypeMap) -> HashMap<&'a str, &'a str> {
+  let removed: Vec<_> = old.inner().iter().filter(|(k, _)| !new.contains_key(k)).collect();
+  let added: Vec<_> = new.inner().iter().filter(|(k, _)| !old.contains_key(k)).collect();
+  let mut renames = HashMap::new();
+  for (from, sig) in &removed {
+    let same_removed = removed.iter().filter(|(_, s)| s == sig).count();
+    let candidates: Vec<_> = added.iter().filter(|(_, s)| s == sig).collect();
+    if same_removed == 1 && candidates.len() == 1 {
+      renames.insert(from.as_str(), candidates[0].0.as_str());
+    }
+  }
+  renames
+}
+
+/// Returns true if every value of type `from` is also a valid value of type `to`.
+#[must_use]
+pub fn is_assignable(from: &TypeSignature, to: &TypeSignature) -> bool {
+  if from == to {
+    return true;
+  }
+  match (from, to) {
+    (_, TypeSignature::Raw | TypeSignature::Value) => true,
+    (
+      TypeSignature::Constrained {
+        base: f,
+        constraints: fc,
+      },
+      TypeSignature::Constrained {
+        base: t,
+        constraints: tc,
+      },
+    ) => is_assignable(f, t) && narrows(fc, tc),
+    (TypeSignature::Constrained { base, .. }, _) => is_assignable(base, to),
+    (TypeSignature::Optional { option: f }, TypeSignature::Optional { option: t }) => is_assignable(f, t),
+    (_, TypeSignature::Optional { option }) => is_assignable(from, option),
+    (
+      TypeSignature::List { element: f } | TypeSignature::Array { element: f, .. },
+      TypeSignature::List { element: t },
+    ) => is_assignable(f, t),
+    (TypeSignature::Array { element: f, length: n }, TypeSignature::Array { element: t, length: m }) => {
+      n == m && is_assignable(f, t)
+    }
+    (TypeSignature::Tuple { elements: f }, TypeSignature::Tuple { elements: t }) => {
+      f.len() == t.len() && f.iter().zip(t).all(|(f, t)| is_assignable(f, t))
+    }
+    (TypeSignature::Tuple { elements }, TypeSignature::List { element }) => {
+      elements.iter().all(|f| is_assignable(f, element))
+    }
+    (TypeSignature::Map { key: fk, value: fv }, TypeSignature::Map { key: tk, value: tv }) => {
+      is_assignable(fk, tk) && is_assignable(fv, tv)
+    }
+    (TypeSignature::Ref { reference: f }, TypeSignature::Ref { reference: t }) => ref_name(f) == ref_name(t),
+    (TypeSignature::Enum { values: f }, TypeSignature::Enum { values: t }) => f.iter().all(|v| t.contains(v)),
+    (TypeSignature::Enum { .. }, TypeSignature::String) => true,
+    (
+      TypeSignature::Union {
+        tag: from_tag,
+        variants: f,
+      },
+      TypeSignature::Union {
+        tag: to_tag,
+        variants: t,
+      },
+    ) => from_tag == to_tag && f.iter().all(|v| t.iter().any(|other| ref_name(v) == ref_name(other))),
+    (TypeSignature::Link { .. }, TypeSignature::Link { provider: None }) => true,
+    (TypeSignature::Datetime | TypeSignature::Decimal | TypeSignature::Url, TypeSignature::String)
+    | (TypeSignature::F32, TypeSignature::F64) => true,
+    // Decimals accept integers of any size.
+    (_, TypeSignature::Decimal) => integer_range(from).is_some(),
+    _ => match (integer_range(from), integer_range(to)) {
+      (Some((fmin, fmax)), Some((tmin, tmax))) => fmin >= tmin && fmax <= tmax,
+      _ => false,
+    },
+  }
+}
+
+/// Returns true if every value `from` allows is also allowed by `to`.
+fn narrows(from: &Constraints, to: &Constraints) -> bool {
+  let at_least = |f: Option<f64>, t: Option<f64>| t.is_none() || f.zip(t).is_some_and(|(f, t)| f >= t);
+  let at_most = |f: Option<f64>, t: Option<f64>| t.is_none() || f.zip(t).is_some_and(|(f, t)| f <= t);
+  let length = |n: Option<u64>| n.map(|n| n as f64);
+  at_least(from.minimum, to.minimum)
+    && at_most(from.maximum, to.maximum)
+    && at_least(length(from.min_length), length(to.min_length))
+    && at_most(length(from.max_length), length(to.max_length))
+    && (to.pattern.is_none() || from.pattern == to.pattern)
+}
+
+pub(crate) fn integer_range(sig: &TypeSignature) -> Option<(i128, i128)> {
+  let range = match sig {
+    TypeSignature::I8 => (i8::MIN.into(), i8::MAX.into()),
+    TypeSignature::I16 => (i16::MIN.into(), i16::MAX.into()),
+    TypeSignature::I32 => (i32::MIN.into(), i32::MAX.into()),
+    TypeSignature::I64 => (i64::MIN.into(), i64::MAX.into()),
+    TypeSignature::U8 => (0, u8::MAX.into()),
+    TypeSignature::U16 => (0, u16::MAX.into()),
+    TypeSignature::U32 => (0, u32::MAX.into()),
+    TypeSignature::U64 => (0, u64::MAX.into()),
+    _ => return None,
+  };
+  Some(range)
+}
@@ -0,0 +1,297 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+pub use vino_codec::timestamp::TIMESTAMP_EXT;
+use vino_codec::timestamp::{self, ExtData, EXT_STRUCT_NAME};
+
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A point in time, the canonical value of a [crate::TypeSignature::Datetime].
+///
+/// Human readable formats like JSON and [serde_value] encode a [Datetime] as an RFC3339 string in UTC,
+/// e.g. `2021-11-04T11:30:00.500Z`. MessagePack encodes it as the timestamp extension type.
+/// Both encodings are accepted when deserializing, whatever the format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[must_use]
+pub struct Datetime {
+  seconds: i64,
+  nanos: u32,
+}
+
+impl Datetime {
+  /// 1970-01-01T00:00:00Z.
+  pub const UNIX_EPOCH: Datetime = Datetime { seconds: 0, nanos: 0 };
+
+  /// The current time.
+  pub fn now() -> Self {
+    SystemTime::now().into()
+  }
+
+  /// Create a [Datetime] from seconds and nanoseconds since the Unix epoch. Nanoseconds past a second carry over,
+  /// saturating at the latest representable second.
+  pub fn from_unix(seconds: i64, nanos: u32) -> Self {
+    Self {
+      seconds: seconds.saturating_add(i64::from(nanos / NANOS_PER_SECOND)),
+      nanos: nanos % NANOS_PER_SECOND,
+    }
+  }
+
+  /// Create a [Datetime] from milliseconds since the Unix epoch.
+  pub fn from_unix_millis(millis: i64) -> Self {
+    Self::from_unix(millis.div_euclid(1000), millis.rem_euclid(1000) as u32 * 1_000_000)
+  }
+
+  /// Whole seconds since the Unix epoch.
+  #[must_use]
+  pub fn unix_seconds(&self) -> i64 {
+    self.seconds
+  }
+
+  /// Milliseconds since the Unix epoch, saturating at the limits of an [i64].
+  #[must_use]
+  pub fn unix_millis(&self) -> i64 {
+    self
+      .seconds
+      .saturating_mul(1000)
+      .saturating_add(i64::from(self.nanos / 1_000_000))
+  }
+
+  /// Nanoseconds past the whole second.
+  #[must_use]
+  pub fn subsec_nanos(&self) -> u32 {
+    self.nanos
+  }
+
+  /// Encode as the data of a MessagePack timestamp extension, using the smallest of the 32, 64 or 96 bit layouts.
+  #[must_use]
+  pub fn to_timestamp_ext(&self) -> Vec<u8> {
+    timestamp::encode(self.seconds, self.nanos)
+  }
+
+  /// Decode the data of a MessagePack timestamp extension.
+  pub fn from_timestamp_ext(data: &[u8]) -> Result<Self, DatetimeError> {
+    let (seconds, nanos) = timestamp::decode(data).map_err(|e| DatetimeError::new(e.to_string()))?;
+    Ok(Self { seconds, nanos })
+  }
+}
+
+/// An invalid RFC3339 string, timestamp, or a [Datetime] out of range of the type it was converted to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DatetimeError {
+  message: String,
+}
+
+impl DatetimeError {
+  fn new<T: AsRef<str>>(message: T) -> Self {
+    Self {
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for DatetimeError {}
+impl Display for DatetimeError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid datetime: {}", self.message)
+  }
+}
+
+impl From<SystemTime> for Datetime {
+  fn from(time: SystemTime) -> Self {
+    match time.duration_since(UNIX_EPOCH) {
+      Ok(since) => Self::from_unix(since.as_secs() as i64, since.subsec_nanos()),
+      Err(e) => {
+        let before = e.duration();
+        let seconds = -(before.as_secs() as i64);
+        match before.subsec_nanos() {
+          0 => Self::from_unix(seconds, 0),
+          nanos => Self::from_unix(seconds - 1, NANOS_PER_SECOND - nanos),
+        }
+      }
+    }
+  }
+}
+
+impl TryFrom<Datetime> for SystemTime {
+  type Error = DatetimeError;
+
+  fn try_from(time: Datetime) -> Result<Self, Self::Error> {
+    let since = Duration::new(time.seconds.unsigned_abs(), 0);
+    let whole = if time.seconds < 0 {
+      UNIX_EPOCH.checked_sub(since)
+    } else {
+      UNIX_EPOCH.checked_add(since)
+    };
+    whole
+      .and_then(|t| t.checked_add(Duration::from_nanos(time.nanos.into())))
+      .ok_or_else(|| DatetimeError::new(format!("{} is out of range for SystemTime", time)))
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Datetime {
+  fn from(time: chrono::DateTime<chrono::Utc>) -> Self {
+    Self::from_unix(time.timestamp(), time.timestamp_subsec_nanos())
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Datetime> for chrono::DateTime<chrono::Utc> {
+  type Error = DatetimeError;
+
+  fn try_from(time: Datetime) -> Result<Self, Self::Error> {
+    use chrono::TimeZone;
+    chrono::Utc
+      .timestamp_opt(time.seconds, time.nanos)
+      .single()
+      .ok_or_else(|| DatetimeError::new(format!("{} is out of range for chrono", time)))
+  }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Datetime {
+  fn from(time: time::OffsetDateTime) -> Self {
+    Self::from_unix(time.unix_timestamp(), time.nanosecond())
+  }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Datetime> for time::OffsetDateTime {
+  type Error = DatetimeError;
+
+  fn try_from(time: Datetime) -> Result<Self, Self::Error> {
+    let nanos = i128::from(time.seconds) * i128::from(NANOS_PER_SECOND) + i128::from(time.nanos);
+    time::OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|e| DatetimeError::new(e.to_string()))
+  }
+}
+
+/// Formats as RFC3339 in UTC, with 3, 6 or 9 fractional digits when there is a fraction.
+impl Display for Datetime {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(&timestamp::to_rfc3339(self.seconds, self.nanos))
+  }
+}
+
+/// Parses RFC3339 strings like `2021-11-04T12:30:00.5+01:00`. Fractions beyond nanoseconds are truncated.
+impl FromStr for Datetime {
+  type Err = DatetimeError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_rfc3339(s).ok_or_else(|| DatetimeError::new(format!("'{}' is not an RFC3339 datetime", s)))
+  }
+}
+
+fn parse_rfc3339(s: &str) -> Option<Datetime> {
+  let b = s.as_bytes();
+  let number = |range: std::ops::Range<usize>| -> Option<i64> {
+    let part = s.get(range)?;
+    if !part.bytes().all(|c| c.is_ascii_digit()) {
+      return None;
+    }
+    part.parse().ok()
+  };
+  if b.len() < 20
+    || b[4] != b'-'
+    || b[7] != b'-'
+    || !matches!(b[10], b'T' | b't' | b' ')
+    || b[13] != b':'
+    || b[16] != b':'
+  {
+    return None;
+  }
+  let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+  let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+  let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+  let days_in_month = match month {
+    2 if leap => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    1..=12 => 31,
+    _ => return None,
+  };
+  // A leap second (:60) reads as the first instant of the next minute.
+  if !(1..=days_in_month).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+    return None;
+  }
+
+  let mut rest = s.get(19..)?;
+  let mut nanos = 0;
+  if let Some(fraction) = rest.strip_prefix('.') {
+    let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+      return None;
+    }
+    let digits = &fraction[..len.min(9)];
+    nanos = digits.parse::<u32>().ok()? * 10_u32.pow(9 - digits.len() as u32);
+    rest = &fraction[len..];
+  }
+  let offset = match rest.as_bytes() {
+    [b'Z' | b'z'] => 0,
+    [sign @ (b'+' | b'-'), ..] if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+      let (hours, minutes) = (number(s.len() - 5..s.len() - 3)?, number(s.len() - 2..s.len())?);
+      if hours > 23 || minutes > 59 {
+        return None;
+      }
+      let offset = hours * 3600 + minutes * 60;
+      if *sign == b'-' {
+        -offset
+      } else {
+        offset
+      }
+    }
+    _ => return None,
+  };
+
+  let seconds =
+    timestamp::days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second - offset;
+  Some(Datetime::from_unix(seconds, nanos))
+}
+
+impl Serialize for Datetime {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+      serializer.collect_str(self)
+    } else {
+      serializer.serialize_newtype_struct(EXT_STRUCT_NAME, &(TIMESTAMP_EXT, ExtData(self.to_timestamp_ext())))
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Datetime {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(DatetimeVisitor)
+  }
+}
+
+struct DatetimeVisitor;
+
+impl<'de> Visitor<'de> for DatetimeVisitor {
+  type Value = Datetime;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("an RFC3339 string or a MessagePack timestamp")
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    v.parse().map_err(E::custom)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(self)
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+    let tag: i8 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+    if tag != TIMESTAMP_EXT {
+      return Err(de::Error::custom(format!("extension type {} is not a timestamp", tag)));
+    }
+    let data: ExtData = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+    Datetime::from_timestamp_ext(&data.0).map_err(de::Error::custom)
+  }
+}
@@ -0,0 +1,281 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// The largest exponent accepted when parsing, which bounds how many digits an exponent can expand into.
+const MAX_EXPONENT: i64 = 1000;
+
+/// An arbitrary precision decimal number, the canonical value of a [crate::TypeSignature::Decimal].
+///
+/// Every format encodes a [Decimal] as a string of its digits, e.g. `-12.50`, so no precision is lost to
+/// floating point. Integers are accepted when deserializing too.
+///
+/// A [Decimal] keeps the number of fractional digits it was written with, so `12.50` prints as `12.50`,
+/// but it compares equal to `12.5`.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Decimal {
+  negative: bool,
+  /// The digits of the unscaled value, without leading zeros. Zero is an empty string.
+  digits: String,
+  /// How many of the digits are after the decimal point. Zeros are implied when there are fewer digits.
+  scale: u32,
+}
+
+impl Decimal {
+  /// Zero.
+  pub const ZERO: Decimal = Decimal {
+    negative: false,
+    digits: String::new(),
+    scale: 0,
+  };
+
+  /// Returns true if the number is less than zero.
+  #[must_use]
+  pub fn is_negative(&self) -> bool {
+    self.negative
+  }
+
+  /// The number of digits after the decimal point.
+  #[must_use]
+  pub fn scale(&self) -> u32 {
+    self.scale
+  }
+
+  /// The same number without trailing zeros after the decimal point.
+  pub fn normalize(&self) -> Self {
+    if self.digits.is_empty() {
+      return Self::ZERO;
+    }
+    let trailing = self.digits.bytes().rev().take_while(|&d| d == b'0').count();
+    let strip = trailing.min(self.scale as usize);
+    Self {
+      negative: self.negative,
+      digits: self.digits[..self.digits.len() - strip].to_owned(),
+      scale: self.scale - strip as u32,
+    }
+  }
+
+  /// The nearest `f64`, which may lose precision.
+  #[must_use]
+  pub fn to_f64(&self) -> f64 {
+    self.to_string().parse().unwrap_or_default()
+  }
+
+  /// The position of the most significant digit relative to the decimal point, for comparing magnitudes.
+  fn magnitude(&self) -> i64 {
+    self.digits.len() as i64 - i64::from(self.scale)
+  }
+}
+
+impl Default for Decimal {
+  fn default() -> Self {
+    Self::ZERO
+  }
+}
+
+/// A string that is not a decimal number, or a float that is not finite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecimalError {
+  message: String,
+}
+
+impl DecimalError {
+  fn new<T: AsRef<str>>(message: T) -> Self {
+    Self {
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for DecimalError {}
+impl Display for DecimalError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid decimal: {}", self.message)
+  }
+}
+
+impl From<i64> for Decimal {
+  fn from(n: i64) -> Self {
+    let mut decimal = Self::from(n.unsigned_abs());
+    decimal.negative = n < 0;
+    decimal
+  }
+}
+
+impl From<u64> for Decimal {
+  fn from(n: u64) -> Self {
+    Self {
+      negative: false,
+      digits: if n == 0 { String::new() } else { n.to_string() },
+      scale: 0,
+    }
+  }
+}
+
+/// Converts the shortest decimal representation of the float, so `0.1` becomes exactly `0.1`.
+impl TryFrom<f64> for Decimal {
+  type Error = DecimalError;
+
+  fn try_from(n: f64) -> Result<Self, Self::Error> {
+    if n.is_finite() {
+      n.to_string().parse()
+    } else {
+      Err(DecimalError::new(format!("{} is not finite", n)))
+    }
+  }
+}
+
+impl PartialEq for Decimal {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Decimal {}
+
+impl Hash for Decimal {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    let normalized = self.normalize();
+    normalized.negative.hash(state);
+    normalized.digits.hash(state);
+    normalized.scale.hash(state);
+  }
+}
+
+impl PartialOrd for Decimal {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for Decimal {
+  fn cmp(&self, other: &Self) -> Ordering {
+    let (a, b) = (self.normalize(), other.normalize());
+    let magnitude = match (a.digits.is_empty(), b.digits.is_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Less,
+      (false, true) => Ordering::Greater,
+      // Without leading zeros, the number with more integer digits is larger, and otherwise the digits
+      // line up and compare in order.
+      (false, false) => a.magnitude().cmp(&b.magnitude()).then_with(|| a.digits.cmp(&b.digits)),
+    };
+    match (a.negative, b.negative) {
+      (false, false) => magnitude,
+      (true, true) => magnitude.reverse(),
+      (false, true) => Ordering::Greater,
+      (true, false) => Ordering::Less,
+    }
+  }
+}
+
+/// Formats as plain digits with the number's scale, e.g. `-0.050`, never in exponent notation.
+impl Display for Decimal {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.negative {
+      f.write_str("-")?;
+    }
+    let scale = self.scale as usize;
+    let digits = if self.digits.len() <= scale {
+      format!("{}{}", "0".repeat(scale + 1 - self.digits.len()), self.digits)
+    } else {
+      self.digits.clone()
+    };
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+    f.write_str(whole)?;
+    if !fraction.is_empty() {
+      write!(f, ".{}", fraction)?;
+    }
+    Ok(())
+  }
+}
+
+/// Parses numbers like `42`, `-0.50`, `+.5` and `1.25e-3`.
+impl FromStr for Decimal {
+  type Err = DecimalError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_decimal(s).ok_or_else(|| DecimalError::new(format!("'{}' is not a decimal number", s)))
+  }
+}
+
+fn parse_decimal(s: &str) -> Option<Decimal> {
+  let (negative, unsigned) = match s.as_bytes().first()? {
+    b'-' => (true, &s[1..]),
+    b'+' => (false, &s[1..]),
+    _ => (false, s),
+  };
+  let (mantissa, exponent) = unsigned
+    .split_once(['e', 'E'])
+    .map_or((unsigned, None), |(mantissa, exponent)| (mantissa, Some(exponent)));
+  let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+  let all_digits = |part: &str| part.bytes().all(|c| c.is_ascii_digit());
+  if whole.is_empty() && fraction.is_empty() || !all_digits(whole) || !all_digits(fraction) {
+    return None;
+  }
+  let mut scale = fraction.len() as i64;
+  if let Some(exponent) = exponent {
+    let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+    if digits.is_empty() || !all_digits(digits) {
+      return None;
+    }
+    let exponent: i64 = exponent.parse().ok().filter(|e: &i64| e.abs() <= MAX_EXPONENT)?;
+    scale -= exponent;
+  }
+  let mut digits = format!("{}{}", whole, fraction).trim_start_matches('0').to_owned();
+  if scale < 0 {
+    if !digits.is_empty() {
+      digits.push_str(&"0".repeat(scale.unsigned_abs() as usize));
+    }
+    scale = 0;
+  }
+  Some(Decimal {
+    negative: negative && !digits.is_empty(),
+    digits,
+    scale: u32::try_from(scale).ok()?,
+  })
+}
+
+impl Serialize for Decimal {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for Decimal {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(DecimalVisitor)
+  }
+}
+
+struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+  type Value = Decimal;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("a decimal number string or an integer")
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    v.parse().map_err(E::custom)
+  }
+
+  fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+    Ok(Decimal::from(v))
+  }
+
+  fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+    Ok(Decimal::from(v))
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(self)
+  }
+}
@@ -0,0 +1,242 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_HOUR: u64 = 3600;
+const SECONDS_PER_DAY: u64 = 86_400;
+const SECONDS_PER_WEEK: u64 = 604_800;
+
+/// A span of time, the canonical value of a [crate::TypeSignature::Duration].
+///
+/// Every format encodes a [Duration] as an ISO 8601 duration string, e.g. `P1DT2H30M0.5S`.
+/// An array of whole seconds and nanoseconds is accepted too when deserializing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[must_use]
+pub struct Duration {
+  seconds: u64,
+  nanos: u32,
+}
+
+impl Duration {
+  /// No time at all.
+  pub const ZERO: Duration = Duration { seconds: 0, nanos: 0 };
+
+  /// Create a [Duration] from seconds and nanoseconds. Nanoseconds past a second carry over, saturating at the
+  /// longest representable duration.
+  pub fn new(seconds: u64, nanos: u32) -> Self {
+    Self {
+      seconds: seconds.saturating_add(u64::from(nanos / NANOS_PER_SECOND)),
+      nanos: nanos % NANOS_PER_SECOND,
+    }
+  }
+
+  /// Create a [Duration] from milliseconds.
+  pub fn from_millis(millis: u64) -> Self {
+    Self::new(millis / 1000, (millis % 1000) as u32 * 1_000_000)
+  }
+
+  /// Whole seconds.
+  #[must_use]
+  pub fn as_secs(&self) -> u64 {
+    self.seconds
+  }
+
+  /// Milliseconds.
+  #[must_use]
+  pub fn as_millis(&self) -> u128 {
+    u128::from(self.seconds) * 1000 + u128::from(self.nanos / 1_000_000)
+  }
+
+  /// Nanoseconds past the whole second.
+  #[must_use]
+  pub fn subsec_nanos(&self) -> u32 {
+    self.nanos
+  }
+}
+
+/// An invalid ISO 8601 duration string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationError {
+  message: String,
+}
+
+impl DurationError {
+  fn new<T: AsRef<str>>(message: T) -> Self {
+    Self {
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for DurationError {}
+impl Display for DurationError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid duration: {}", self.message)
+  }
+}
+
+impl From<std::time::Duration> for Duration {
+  fn from(duration: std::time::Duration) -> Self {
+    Self::new(duration.as_secs(), duration.subsec_nanos())
+  }
+}
+
+impl From<Duration> for std::time::Duration {
+  fn from(duration: Duration) -> Self {
+    Self::new(duration.seconds, duration.nanos)
+  }
+}
+
+/// Formats as an ISO 8601 duration in days, hours, minutes and seconds, leaving out the parts that are zero
+/// and trailing zeros of the fraction, e.g. `PT1M0.25S`. A zero duration is `PT0S`.
+impl Display for Duration {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if *self == Self::ZERO {
+      return f.write_str("PT0S");
+    }
+    let days = self.seconds / SECONDS_PER_DAY;
+    let hours = self.seconds % SECONDS_PER_DAY / SECONDS_PER_HOUR;
+    let minutes = self.seconds % SECONDS_PER_HOUR / SECONDS_PER_MINUTE;
+    let seconds = self.seconds % SECONDS_PER_MINUTE;
+    f.write_str("P")?;
+    if days > 0 {
+      write!(f, "{}D", days)?;
+    }
+    if hours == 0 && minutes == 0 && seconds == 0 && self.nanos == 0 {
+      return Ok(());
+    }
+    f.write_str("T")?;
+    if hours > 0 {
+      write!(f, "{}H", hours)?;
+    }
+    if minutes > 0 {
+      write!(f, "{}M", minutes)?;
+    }
+    match self.nanos {
+      0 if seconds == 0 => Ok(()),
+      0 => write!(f, "{}S", seconds),
+      n => {
+        let fraction = format!("{:09}", n);
+        write!(f, "{}.{}S", seconds, fraction.trim_end_matches('0'))
+      }
+    }
+  }
+}
+
+/// Parses ISO 8601 durations of weeks, days, hours, minutes and seconds like `P1W2DT3H4M5.5S`.
+/// Years and months are rejected because their length varies. Fractions beyond nanoseconds are truncated.
+impl FromStr for Duration {
+  type Err = DurationError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    parse_iso8601(s).map_err(|reason| DurationError::new(format!("'{}' {}", s, reason)))
+  }
+}
+
+fn parse_iso8601(s: &str) -> Result<Duration, &'static str> {
+  let not_iso = "is not an ISO 8601 duration";
+  let mut rest = s.strip_prefix('P').ok_or(not_iso)?;
+  let mut seconds: u64 = 0;
+  let mut nanos = 0;
+  let mut time = false;
+  // Units must come largest first, each at most once.
+  let mut last_unit = None;
+  while !rest.is_empty() {
+    if let Some(after) = rest.strip_prefix('T') {
+      if time || after.is_empty() {
+        return Err(not_iso);
+      }
+      time = true;
+      rest = after;
+      continue;
+    }
+    let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+      return Err(not_iso);
+    }
+    let number: u64 = rest[..len].parse().map_err(|_| "is too long")?;
+    rest = &rest[len..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+      let len = fraction.bytes().take_while(u8::is_ascii_digit).count();
+      if len == 0 || !time || !fraction[len..].starts_with('S') {
+        return Err(not_iso);
+      }
+      let digits = &fraction[..len.min(9)];
+      nanos = digits.parse::<u32>().map_err(|_| not_iso)? * 10_u32.pow(9 - digits.len() as u32);
+      rest = &fraction[len..];
+    }
+    let unit = match (time, rest.as_bytes().first()) {
+      (false, Some(b'W')) => SECONDS_PER_WEEK,
+      (false, Some(b'D')) => SECONDS_PER_DAY,
+      (true, Some(b'H')) => SECONDS_PER_HOUR,
+      (true, Some(b'M')) => SECONDS_PER_MINUTE,
+      (true, Some(b'S')) => 1,
+      (false, Some(b'Y' | b'M')) => return Err("has years or months, which have no fixed length"),
+      _ => return Err(not_iso),
+    };
+    if last_unit.is_some_and(|last| unit >= last) {
+      return Err(not_iso);
+    }
+    last_unit = Some(unit);
+    seconds = number
+      .checked_mul(unit)
+      .and_then(|n| n.checked_add(seconds))
+      .ok_or("is too long")?;
+    rest = &rest[1..];
+  }
+  if last_unit.is_none() {
+    return Err(not_iso);
+  }
+  Ok(Duration::new(seconds, nanos))
+}
+
+impl Serialize for Duration {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(DurationVisitor)
+  }
+}
+
+struct DurationVisitor;
+
+impl<'de> Visitor<'de> for DurationVisitor {
+  type Value = Duration;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("an ISO 8601 duration string or an array of seconds and nanoseconds")
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    v.parse().map_err(E::custom)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(self)
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+    let seconds: u64 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+    let nanos: u32 = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+    if seq.next_element::<de::IgnoredAny>()?.is_some() {
+      return Err(de::Error::invalid_length(3, &self));
+    }
+    if nanos >= NANOS_PER_SECOND {
+      return Err(de::Error::custom(format!(
+        "{} nanoseconds is more than a second",
+        nanos
+      )));
+    }
+    Ok(Duration { seconds, nanos })
+  }
+}
@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use serde_json::Value;
+
+use crate::maps::{MapWrapper, StructMap, TypeMap};
+use crate::resolve::references;
+use crate::signatures::{ComponentSignature, ProviderSignature, StructSignature, TypeSignature};
+use crate::validate::ref_name;
+
+/// A stable hash of a signature's structure.
+///
+/// Fingerprints are the same on every platform and across releases. They ignore the order maps
+/// and enum values are declared in, names of the signature itself, descriptions and deprecation,
+/// but include everything that changes which values are valid, like defaults and cardinality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[must_use]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+  /// The fingerprint as a number.
+  #[must_use]
+  pub fn as_u64(self) -> u64 {
+    self.0
+  }
+}
+
+impl Display for Fingerprint {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:016x}", self.0)
+  }
+}
+
+impl From<Fingerprint> for u64 {
+  fn from(fingerprint: Fingerprint) -> Self {
+    fingerprint.0
+  }
+}
+
+impl TypeSignature {
+  /// A stable hash of this signature. [TypeSignature::Ref]s are hashed by the name they refer to.
+  pub fn fingerprint(&self) -> Fingerprint {
+    Hasher::digest(|h| h.signature(self))
+  }
+}
+
+impl StructSignature {
+  /// A stable hash of the struct's fields. See [Fingerprint].
+  ///
+  /// Types the fields refer to are hashed by name only, not by their definitions.
+  pub fn fingerprint(&self) -> Fingerprint {
+    Hasher::digest(|h| h.type_map(&self.fields))
+  }
+}
+
+impl ComponentSignature {
+  /// A stable hash of the component's inputs, outputs and configuration. See [Fingerprint].
+  ///
+  /// **This does not cover the types the component refers to.** A [TypeSignature::Ref] is hashed by name
+  /// only, so changing the fields of a referenced type leaves the fingerprint unchanged. Use
+  /// [ComponentSignature::fingerprint_with] whenever the component uses types from a provider.
+  pub fn fingerprint(&self) -> Fingerprint {
+    Hasher::digest(|h| h.component(self))
+  }
+
+  /// A stable hash of the component and the definition of every type it refers to, directly or through
+  /// other types. References missing from `types` are hashed as missing.
+  pub fn fingerprint_with(&self, types: &StructMap) -> Fingerprint {
+    Hasher::digest(|h| {
+      h.component(self);
+      let ports = self.inputs.inner().values();
+      h.referenced(
+        ports
+          .chain(self.outputs.inner().values())
+          .chain(self.config.inner().values()),
+        types,
+      );
+    })
+  }
+}
+
+impl ProviderSignature {
+  /// A stable hash of the provider's types, components and configuration. See [Fingerprint].
+  pub fn fingerprint(&self) -> Fingerprint {
+    Hasher::digest(|h| {
+      h.struct_map(&self.types);
+      h.token("components");
+      let mut components: Vec<_> = self.components.inner().iter().collect();
+      components.sort_by(|a, b| a.0.cmp(b.0));
+      h.write(&(components.len() as u64).to_le_bytes());
+      for (name, component) in components {
+        h.token(name);
+        h.component(component);
+      }
+      h.token("config");
+      h.type_map(&self.config);
+    })
+  }
+}
+
+/// 64-bit FNV-1a over length-prefixed tokens, so that no two structures produce the same stream.
+struct Hasher(u64);
+
+impl Hasher {
+  fn digest<F: FnOnce(&mut Hasher)>(f: F) -> Fingerprint {
+    let mut hasher = Hasher(0xcbf2_9ce4_8422_2325);
+    f(&mut hasher);
+    Fingerprint(hasher.0)
+  }
+
+  fn write(&mut self, bytes: &[u8]) {
+    for byte in bytes {
+      self.0 ^= u64::from(*byte);
+      self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+    }
+  }
+
+  fn token<T: AsRef<str>>(&mut self, token: T) {
+    let token = token.as_ref();
+    self.write(&(token.len() as u64).to_le_bytes());
+    self.write(token.as_bytes());
+  }
+
+  fn signature(&mut self, sig: &TypeSignature) {
+    match sig {
+      TypeSignature::Ref { reference } => {
+        self.token("ref");
+        self.token(ref_name(reference));
+      }
+      TypeSignature::List { element } => {
+        self.token("list");
+        self.signature(element);
+      }
+      TypeSignature::Optional { option } => {
+        self.token("optional");
+        self.signature(option);
+      }
+      TypeSignature::Map { key, value } => {
+        self.token("map");
+        self.signature(key);
+        self.signature(value);
+      }
+      TypeSignature::Tuple { elements } => {
+        self.token("tuple");
+        self.write(&(elements.len() as u64).to_le_bytes());
+        for element in elements {
+          self.signature(element);
+        }
+      }
+      TypeSignature::Array { element, length } => {
+        self.token("array");
+        self.write(&length.to_le_bytes());
+        self.signature(element);
+      }
+      TypeSignature::Constrained { base, constraints } => {
+        self.token("constrained");
+        self.signature(base);
+        // The constraints' display form lists them in a fixed order.
+        self.token(constraints.to_string());
+      }
+      TypeSignature::Enum { values } => {
+        self.token("enum");
+        self.sorted(values.iter().map(String::as_str));
+      }
+      TypeSignature::Union { tag, variants } => {
+        self.token("union");
+        self.token(tag);
+        self.sorted(variants.iter().map(|v| ref_name(v)));
+      }
+      // Everything else is a leaf whose display form fully describes it.
+      _ => self.token(sig.to_string()),
+    }
+  }
+
+  fn sorted<'a, I: Iterator<Item = &'a str>>(&mut self, items: I) {
+    let mut items: Vec<_> = items.collect();
+    items.sort_unstable();
+    items.dedup();
+    self.write(&(items.len() as u64).to_le_bytes());
+    for item in items {
+      self.token(item);
+    }
+  }
+
+  fn type_map(&mut self, map: &TypeMap) {
+    let mut entries: Vec<_> = map.inner().iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    self.write(&(entries.len() as u64).to_le_bytes());
+    for (name, sig) in entries {
+      self.token(name);
+      self.signature(sig);
+      match map.metadata(name).and_then(|m| m.default.as_ref()) {
+        Some(default) => self.value(default),
+        None => self.token("no default"),
+      }
+      self.token(map.cardinality(name).to_string());
+    }
+  }
+
+  /// Hash a JSON value with object keys in sorted order, whatever order the map keeps them in.
+  fn value(&mut self, value: &Value) {
+    match value {
+      Value::Array(items) => {
+        self.token("array");
+        self.write(&(items.len() as u64).to_le_bytes());
+        for item in items {
+          self.value(item);
+        }
+      }
+      Value::Object(map) => {
+        self.token("object");
+        let mut entries: Vec<_> = map.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        self.write(&(entries.len() as u64).to_le_bytes());
+        for (key, value) in entries {
+          self.token(key);
+          self.value(value);
+        }
+      }
+      _ => self.token(value.to_string()),
+    }
+  }
+
+  fn struct_map(&mut self, types: &StructMap) {
+    self.token("types");
+    let mut types: Vec<_> = types.inner().iter().collect();
+    types.sort_by(|a, b| a.0.cmp(b.0));
+    self.write(&(types.len() as u64).to_le_bytes());
+    for (name, def) in types {
+      self.token(name);
+      self.type_map(&def.fields);
+    }
+  }
+
+  /// Hash the definitions of every type reachable from `sigs`, in name order.
+  fn referenced<'a, I: Iterator<Item = &'a TypeSignature>>(&mut self, sigs: I, types: &'a StructMap) {
+    let mut pending = Vec::new();
+    sigs.for_each(|sig| references(sig, &mut pending));
+    let mut found: BTreeMap<&str, Option<&StructSignature>> = BTreeMap::new();
+    while let Some(reference) = pending.pop() {
+      let name = ref_name(reference);
+      if found.contains_key(name) {
+        continue;
+      }
+      let def = types.resolve(reference);
+      if let Some(def) = def {
+        def
+          .fields
+          .inner()
+          .values()
+          .for_each(|sig| references(sig, &mut pending));
+      }
+      found.insert(name, def);
+    }
+    self.token("types");
+    self.write(&(found.len() as u64).to_le_bytes());
+    for (name, def) in found {
+      self.token(name);
+      match def {
+        Some(def) => self.type_map(&def.fields),
+        None => self.token("missing"),
+      }
+    }
+  }
+
+  fn component(&mut self, component: &ComponentSignature) {
+    self.token("inputs");
+    self.type_map(&component.inputs);
+    self.token("outputs");
+    self.type_map(&component.outputs);
+    self.token("config");
+    self.type_map(&component.config);
+  }
+}
@@ -0,0 +1,142 @@
+use crate::maps::{MapWrapper, TypeMap};
+use crate::signatures::{ProviderSignature, TypeSignature};
+use crate::validate::ref_name;
+
+/// The IDL parser.
+mod parse;
+
+pub use parse::{parse, IdlError};
+
+/// Render a [ProviderSignature] as an IDL document that [parse] reads back.
+#[must_use]
+pub fn print(provider: &ProviderSignature) -> String {
+  let mut items = Vec::new();
+  if let Some(name) = &provider.name {
+    items.push(format!("provider {}\n", name_expr(name)));
+  }
+  if !provider.config.is_empty() {
+    items.push(format!("config {{\n{}}}\n", block(&provider.config)));
+  }
+  for (name, def) in provider.types.ordered() {
+    items.push(format!(
+      "{}type {} {{\n{}}}\n",
+      annotations(def.description.as_deref(), def.deprecated, "\n"),
+      name_expr(name),
+      block(&def.fields)
+    ));
+  }
+  for (name, component) in provider.components.ordered() {
+    let mut item = format!(
+      "{}component {}{} -> {}",
+      annotations(component.description.as_deref(), component.deprecated, "\n"),
+      name_expr(name),
+      ports(&component.inputs),
+      ports(&component.outputs)
+    );
+    if !component.config.is_empty() {
+      item.push_str(&format!(" config {{\n{}}}", block(&component.config)));
+    }
+    item.push('\n');
+    items.push(item);
+  }
+  items.join("\n")
+}
+
+/// Ports on one line, or one per line when any of them carries annotations.
+fn ports(map: &TypeMap) -> String {
+  let annotated = map
+    .inner()
+    .keys()
+    .filter_map(|name| map.metadata(name))
+    .any(|m| m.description.is_some() || m.deprecated);
+  if annotated {
+    format!("(\n{})", block(map))
+  } else {
+    let entries: Vec<_> = map
+      .ordered()
+      .into_iter()
+      .map(|(name, sig)| entry(map, name, sig))
+      .collect();
+    format!("({})", entries.join(", "))
+  }
+}
+
+/// Entries indented one per line, each with a trailing comma.
+fn block(map: &TypeMap) -> String {
+  let mut out = String::new();
+  for (name, sig) in map.ordered() {
+    let metadata = map.metadata(name);
+    let docs = annotations(
+      metadata.and_then(|m| m.description.as_deref()),
+      metadata.is_some_and(|m| m.deprecated),
+      "\n  ",
+    );
+    out.push_str(&format!("  {}{},\n", docs, entry(map, name, sig)));
+  }
+  out
+}
+
+fn entry(map: &TypeMap, name: &str, sig: &TypeSignature) -> String {
+  let mut out = format!("{}: ", name_expr(name));
+  let cardinality = map.cardinality(name);
+  if !cardinality.is_single() {
+    out.push_str(&format!("{} ", cardinality));
+  }
+  out.push_str(&type_expr(sig));
+  if let Some(default) = map.metadata(name).and_then(|m| m.default.as_ref()) {
+    out.push_str(&format!(" = {}", default));
+  }
+  out
+}
+
+/// Doc comment lines and attributes, each followed by `separator`.
+fn annotations(description: Option<&str>, deprecated: bool, separator: &str) -> String {
+  let mut out = String::new();
+  for line in description.iter().flat_map(|d| d.lines()) {
+    if line.is_empty() {
+      out.push_str(&format!("///{}", separator));
+    } else {
+      out.push_str(&format!("/// {}{}", line, separator));
+    }
+  }
+  if deprecated {
+    out.push_str(&format!("@deprecated{}", separator));
+  }
+  out
+}
+
+/// A type expression with references written as bare names where they can't be mistaken for a built-in type.
+fn type_expr(sig: &TypeSignature) -> String {
+  match sig {
+    TypeSignature::Ref { reference } => {
+      let name = ref_name(reference);
+      let bare = !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && name.parse::<TypeSignature>().is_err();
+      if bare {
+        name.to_owned()
+      } else {
+        sig.to_string()
+      }
+    }
+    TypeSignature::List { element } => format!("{}[]", type_expr(element)),
+    TypeSignature::Optional { option } => format!("{}?", type_expr(option)),
+    TypeSignature::Map { key, value } => format!("{{{}: {}}}", type_expr(key), type_expr(value)),
+    TypeSignature::Tuple { elements } => {
+      let elements: Vec<_> = elements.iter().map(type_expr).collect();
+      format!("({})", elements.join(", "))
+    }
+    TypeSignature::Array { element, length } => format!("{}[{}]", type_expr(element), length),
+    TypeSignature::Constrained { base, constraints } => format!("{}({})", type_expr(base), constraints),
+    _ => sig.to_string(),
+  }
+}
+
+fn name_expr(name: &str) -> String {
+  let identifier = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+  if identifier {
+    name.to_owned()
+  } else {
+    serde_json::Value::String(name.to_owned()).to_string()
+  }
+}
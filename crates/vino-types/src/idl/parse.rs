@@ -0,0 +1,368 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use serde_json::Value;
+
+use crate::cardinality::Cardinality;
+use crate::maps::{MapWrapper, TypeMap};
+use crate::resolve::references;
+use crate::signatures::parse::Parser;
+use crate::signatures::{ComponentSignature, Metadata, ProviderSignature, StructSignature, TypeSignature};
+
+/// A syntax error in an IDL document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdlError {
+  /// The line the error is on, starting at 1.
+  pub line: usize,
+  /// The character on the line the error is at, starting at 1.
+  pub column: usize,
+  /// A description of the problem.
+  pub message: String,
+}
+
+impl IdlError {
+  fn at<T: AsRef<str>>(src: &str, pos: usize, message: T) -> Self {
+    let before = &src[..pos];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Self {
+      line: before.matches('\n').count() + 1,
+      column: before[line_start..].chars().count() + 1,
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for IdlError {}
+impl Display for IdlError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} at line {}, column {}", self.message, self.line, self.column)
+  }
+}
+
+/// Parse an IDL document into a [ProviderSignature].
+///
+/// ```text
+/// provider math
+///
+/// /// A point on a plane.
+/// type Point { x: f64, y: f64 = 0 }
+///
+/// /// Add two numbers.
+/// component add(left: i64, right: i64) -> (output: i64)
+///
+/// component walk(from: Point, steps: u32?) -> (position: stream Point) config {
+///   speed: f64 = 1.5,
+/// }
+/// ```
+///
+/// A document is a sequence of items: at most one `provider` name, at most one provider `config`
+/// block, `type` declarations and `component` declarations. Entries are written `name: type`
+/// using the [TypeSignature] expression syntax, where any other name refers to a declared type.
+/// Ports may be marked `stream` or `optional` (see [crate::Cardinality]) and any entry may declare a
+/// JSON default after `=`. `///` comments describe the following item or entry, `@deprecated`
+/// deprecates it, and `//` comments are ignored. Names that are not identifiers are written quoted.
+///
+/// Types and components keep the order they are declared in.
+pub fn parse(src: &str) -> Result<ProviderSignature, IdlError> {
+  IdlParser {
+    src,
+    pos: 0,
+    refs: Vec::new(),
+  }
+  .provider()
+}
+
+/// Doc comments and attributes that precede an item or a field.
+#[derive(Default)]
+struct Annotations {
+  description: Option<String>,
+  deprecated: bool,
+}
+
+struct IdlParser<'a> {
+  src: &'a str,
+  pos: usize,
+  /// Every type referenced so far and where, checked once all types are declared.
+  refs: Vec<(usize, String)>,
+}
+
+impl<'a> IdlParser<'a> {
+  fn provider(mut self) -> Result<ProviderSignature, IdlError> {
+    let mut provider = ProviderSignature::default();
+    let mut config = false;
+    loop {
+      let annotations = self.annotations();
+      if self.pos == self.src.len() {
+        return match self
+          .refs
+          .iter()
+          .find(|(_, name)| provider.types.resolve(name).is_none())
+        {
+          Some((pos, name)) => Err(self.error_at(*pos, format!("unknown type '{}'", name))),
+          None => Ok(provider),
+        };
+      }
+      let start = self.pos;
+      match self.word() {
+        "provider" => {
+          if provider.name.is_some() {
+            return Err(self.error_at(start, "provider name declared twice"));
+          }
+          provider.name = Some(self.name()?);
+        }
+        "config" => {
+          if config {
+            return Err(self.error_at(start, "provider config declared twice"));
+          }
+          config = true;
+          self.expect("{")?;
+          provider.config = self.fields("}")?;
+        }
+        "type" => {
+          let name_start = self.skip_trivia();
+          let name = self.name()?;
+          if provider.types.contains_key(&name) {
+            return Err(self.error_at(name_start, format!("type '{}' declared twice", name)));
+          }
+          self.expect("{")?;
+          let fields = self.fields("}")?;
+          provider.types.insert(
+            &name,
+            StructSignature {
+              name: name.clone(),
+              fields,
+              description: annotations.description,
+              deprecated: annotations.deprecated,
+            },
+          );
+        }
+        "component" => {
+          let name_start = self.skip_trivia();
+          let name = self.name()?;
+          if provider.components.contains_key(&name) {
+            return Err(self.error_at(name_start, format!("component '{}' declared twice", name)));
+          }
+          self.expect("(")?;
+          let inputs = self.fields(")")?;
+          self.expect("->")?;
+          self.expect("(")?;
+          let outputs = self.fields(")")?;
+          self.skip_trivia();
+          let config = if self.keyword("config") {
+            self.expect("{")?;
+            self.fields("}")?
+          } else {
+            TypeMap::new()
+          };
+          provider.components.insert(
+            &name,
+            ComponentSignature {
+              name: name.clone(),
+              inputs,
+              outputs,
+              config,
+              description: annotations.description,
+              deprecated: annotations.deprecated,
+            },
+          );
+        }
+        "" => return Err(self.error("expected 'type', 'component', 'config' or 'provider'")),
+        other => return Err(self.error_at(start, format!("unknown item '{}'", other))),
+      }
+    }
+  }
+
+  /// Parse `name: type = default` entries separated by commas, up to and including `close`.
+  fn fields(&mut self, close: &str) -> Result<TypeMap, IdlError> {
+    let mut map = TypeMap::new();
+    loop {
+      let annotations = self.annotations();
+      if self.eat(close) {
+        return Ok(map);
+      }
+      let start = self.pos;
+      let name = self.name()?;
+      if map.contains_key(&name) {
+        return Err(self.error_at(start, format!("'{}' declared twice", name)));
+      }
+      self.expect(":")?;
+      self.skip_trivia();
+      let cardinality = if self.keyword("stream") {
+        Cardinality::Stream
+      } else if self.keyword("optional") {
+        Cardinality::Optional
+      } else {
+        Cardinality::Single
+      };
+      let sig = self.type_expr()?;
+      self.skip_trivia();
+      let default = if self.eat("=") {
+        let start = self.skip_trivia();
+        let value = self.value()?;
+        sig
+          .check_default(&value)
+          .map_err(|e| self.error_at(start, format!("invalid default for '{}': {}", name, e)))?;
+        Some(value)
+      } else {
+        None
+      };
+      map.insert(&name, sig);
+      map.set_metadata(
+        &name,
+        Metadata {
+          description: annotations.description,
+          default,
+          deprecated: annotations.deprecated,
+          cardinality,
+        },
+      );
+      self.skip_trivia();
+      if !self.eat(",") {
+        return self.expect(close).map(|_| map);
+      }
+    }
+  }
+
+  /// Parse a type expression where names that are not built-in types refer to declared types.
+  fn type_expr(&mut self) -> Result<TypeSignature, IdlError> {
+    let mut parser = Parser::at(self.src, self.pos).with_bare_refs();
+    let sig = parser
+      .parse_type()
+      .map_err(|e| IdlError::at(self.src, e.position(), e.message()))?;
+    let mut refs = Vec::new();
+    references(&sig, &mut refs);
+    let pos = self.pos;
+    self.refs.extend(refs.into_iter().map(|name| (pos, name.to_owned())));
+    self.pos = parser.position();
+    Ok(sig)
+  }
+
+  /// Parse a JSON default value.
+  fn value(&mut self) -> Result<Value, IdlError> {
+    let start = self.skip_trivia();
+    let rest = &self.src[start..];
+    let invalid = |e: serde_json::Error| self.error_at(start, format!("invalid default value: {}", e));
+    let (value, len) = if rest.starts_with(['"', '[', '{']) {
+      // Strings and compound values end themselves.
+      let mut values = serde_json::Deserializer::from_str(rest).into_iter::<Value>();
+      let value = values.next().transpose().map_err(invalid)?;
+      (value.unwrap_or_default(), values.byte_offset())
+    } else {
+      let len = rest
+        .find(|c: char| c.is_whitespace() || [',', ')', '}'].contains(&c))
+        .unwrap_or(rest.len());
+      (serde_json::from_str(&rest[..len]).map_err(invalid)?, len)
+    };
+    self.pos += len;
+    Ok(value)
+  }
+
+  /// Parse an identifier or a quoted name.
+  fn name(&mut self) -> Result<String, IdlError> {
+    let start = self.skip_trivia();
+    if self.src[start..].starts_with('"') {
+      let mut names = serde_json::Deserializer::from_str(&self.src[start..]).into_iter::<String>();
+      return match names.next() {
+        Some(Ok(name)) => {
+          self.pos += names.byte_offset();
+          Ok(name)
+        }
+        _ => Err(self.error("invalid quoted name")),
+      };
+    }
+    let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+    if name.is_empty() {
+      Err(self.error("expected a name"))
+    } else {
+      Ok(name.to_owned())
+    }
+  }
+
+  /// Collect `///` doc comments and `@deprecated` attributes.
+  fn annotations(&mut self) -> Annotations {
+    let mut annotations = Annotations::default();
+    let mut docs: Vec<&str> = Vec::new();
+    loop {
+      self.skip_trivia();
+      if self.eat("///") {
+        let line = self.take_while(|c| c != '\n');
+        docs.push(line.strip_prefix(' ').unwrap_or(line).trim_end());
+      } else if self.keyword("@deprecated") {
+        annotations.deprecated = true;
+      } else {
+        break;
+      }
+    }
+    if !docs.is_empty() {
+      annotations.description = Some(docs.join("\n"));
+    }
+    annotations
+  }
+
+  /// Skip whitespace and `//` comments, stopping at doc comments. Returns the new position.
+  fn skip_trivia(&mut self) -> usize {
+    loop {
+      self.take_while(char::is_whitespace);
+      let rest = &self.src[self.pos..];
+      if rest.starts_with("//") && !rest.starts_with("///") {
+        self.take_while(|c| c != '\n');
+      } else {
+        return self.pos;
+      }
+    }
+  }
+
+  /// Consume a keyword if it is not the start of a longer word.
+  fn keyword(&mut self, keyword: &str) -> bool {
+    let matched = self.src[self.pos..]
+      .strip_prefix(keyword)
+      .is_some_and(|after| !after.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '-'));
+    if matched {
+      self.pos += keyword.len();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn word(&mut self) -> &'a str {
+    self.take_while(|c| c.is_alphanumeric() || c == '_')
+  }
+
+  fn take_while<F: Fn(char) -> bool>(&mut self, f: F) -> &'a str {
+    let start = self.pos;
+    let rest = &self.src[start..];
+    self.pos += rest.find(|c| !f(c)).unwrap_or(rest.len());
+    &self.src[start..self.pos]
+  }
+
+  fn eat(&mut self, token: &str) -> bool {
+    if self.src[self.pos..].starts_with(token) {
+      self.pos += token.len();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, token: &str) -> Result<(), IdlError> {
+    self.skip_trivia();
+    if self.eat(token) {
+      Ok(())
+    } else {
+      let found = self.src[self.pos..]
+        .chars()
+        .next()
+        .map_or_else(|| "end of input".to_owned(), |c| format!("'{}'", c));
+      Err(self.error(format!("expected '{}', found {}", token, found)))
+    }
+  }
+
+  fn error<T: AsRef<str>>(&self, message: T) -> IdlError {
+    IdlError::at(self.src, self.pos, message)
+  }
+
+  fn error_at<T: AsRef<str>>(&self, pos: usize, message: T) -> IdlError {
+    IdlError::at(self.src, pos, message)
+  }
+}
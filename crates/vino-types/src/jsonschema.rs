@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::{json, Map, Value};
+
+use crate::compat::integer_range;
+use crate::maps::{MapWrapper, StructMap, TypeMap};
+use crate::signatures::{ComponentSignature, Constraints, ProviderSignature, StructSignature, TypeSignature};
+use crate::validate::{ref_name, unwrap_optional};
+
+/// Conversion of JSON Schema documents into signatures.
+mod import;
+
+pub use import::{import_schema, ImportError};
+
+/// The JSON Schema dialect generated documents declare.
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Render a [ProviderSignature] as a JSON Schema document.
+///
+/// The provider's types are rendered into `$defs` and each component's inputs and outputs
+/// are rendered under `components`, e.g. `#/components/my_component/inputs`.
+#[must_use]
+pub fn provider_schema(provider: &ProviderSignature) -> Value {
+  let mut components = Map::new();
+  for (name, component) in provider.components.ordered() {
+    components.insert(
+      name.clone(),
+      json!({
+        "inputs": ports_schema(&format!("{} inputs", component.name), &component.inputs),
+        "outputs": ports_schema(&format!("{} outputs", component.name), &component.outputs),
+      }),
+    );
+  }
+  let sigs = provider
+    .components
+    .inner()
+    .values()
+    .flat_map(|c| c.inputs.inner().values().chain(c.outputs.inner().values()));
+  let mut schema = document(&provider.types, sigs);
+  if let Some(name) = &provider.name {
+    schema.insert("title".to_owned(), Value::String(name.clone()));
+  }
+  schema.insert("components".to_owned(), Value::Object(components));
+  Value::Object(schema)
+}
+
+/// Render a component's inputs as a standalone JSON Schema document for an object of port values.
+#[must_use]
+pub fn component_inputs_schema(component: &ComponentSignature, types: &StructMap) -> Value {
+  standalone(
+    ports_schema(&format!("{} inputs", component.name), &component.inputs),
+    types,
+    component.inputs.inner().values(),
+  )
+}
+
+/// Render a component's outputs as a standalone JSON Schema document for an object of port values.
+#[must_use]
+pub fn component_outputs_schema(component: &ComponentSignature, types: &StructMap) -> Value {
+  standalone(
+    ports_schema(&format!("{} outputs", component.name), &component.outputs),
+    types,
+    component.outputs.inner().values(),
+  )
+}
+
+/// Render a [StructSignature] as a JSON Schema object definition.
+#[must_use]
+pub fn struct_schema(def: &StructSignature) -> Value {
+  let mut schema = ports_schema(&def.name, &def.fields);
+  annotate(&mut schema, def.description.as_ref(), None, def.deprecated);
+  schema
+}
+
+/// Render a [TypeSignature] as a JSON Schema.
+///
+/// A [TypeSignature::Union] becomes a `oneOf` of its variants' definitions, each requiring the tag
+/// field to hold the variant's name. Documents declare the tag field on every definition used as a variant.
+/// A [TypeSignature::Decimal] is a string with the non-standard `decimal` format, which [import_schema]
+/// reads back.
+#[must_use]
+pub fn type_schema(sig: &TypeSignature) -> Value {
+  match sig {
+    TypeSignature::I8 => integer(i8::MIN.into(), i8::MAX.unsigned_abs().into()),
+    TypeSignature::I16 => integer(i16::MIN.into(), i16::MAX.unsigned_abs().into()),
+    TypeSignature::I32 => integer(i32::MIN.into(), i32::MAX.unsigned_abs().into()),
+    TypeSignature::I64 => integer(i64::MIN, i64::MAX.unsigned_abs()),
+    TypeSignature::U8 => integer(u8::MIN.into(), u8::MAX.into()),
+    TypeSignature::U16 => integer(u16::MIN.into(), u16::MAX.into()),
+    TypeSignature::U32 => integer(u32::MIN.into(), u32::MAX.into()),
+    TypeSignature::U64 => integer(0, u64::MAX),
+    TypeSignature::F32 | TypeSignature::F64 => json!({ "type": "number" }),
+    TypeSignature::Bool => json!({ "type": "boolean" }),
+    TypeSignature::String => json!({ "type": "string" }),
+    TypeSignature::Datetime => json!({ "type": "string", "format": "date-time" }),
+    TypeSignature::Uuid => json!({ "type": "string", "format": "uuid" }),
+    TypeSignature::Duration => json!({ "type": "string", "format": "duration" }),
+    TypeSignature::Decimal => json!({ "type": "string", "format": "decimal" }),
+    TypeSignature::Url => json!({ "type": "string", "format": "uri" }),
+    TypeSignature::Bytes => json!({ "type": "array", "items": integer(0, 255) }),
+    TypeSignature::Raw | TypeSignature::Value => json!({}),
+    TypeSignature::Internal(t) => type_schema(&t.value_type()),
+    TypeSignature::Struct | TypeSignature::Link { .. } => json!({ "type": "object" }),
+    TypeSignature::Ref { reference } => json!({ "$ref": def_pointer(reference) }),
+    TypeSignature::List { element } => json!({ "type": "array", "items": type_schema(element) }),
+    TypeSignature::Optional { option } => json!({ "anyOf": [type_schema(option), { "type": "null" }] }),
+    TypeSignature::Map { key, value } => {
+      let mut schema = json!({ "type": "object", "additionalProperties": type_schema(value) });
+      if **key != TypeSignature::String {
+        schema["propertyNames"] = key_schema(key);
+      }
+      schema
+    }
+    TypeSignature::Tuple { elements } => json!({
+      "type": "array",
+      "prefixItems": elements.iter().map(type_schema).collect::<Vec<_>>(),
+      "items": false,
+      "minItems": elements.len(),
+      "maxItems": elements.len(),
+    }),
+    TypeSignature::Array { element, length } => json!({
+      "type": "array",
+      "items": type_schema(element),
+      "minItems": length,
+      "maxItems": length,
+    }),
+    TypeSignature::Constrained { base, constraints } => constrained_schema(base, constraints),
+    TypeSignature::Enum { values } => json!({ "type": "string", "enum": values }),
+    TypeSignature::Union { tag, variants } => {
+      let variants: Vec<Value> = variants
+        .iter()
+        .map(|variant| {
+          json!({
+            "$ref": def_pointer(variant),
+            "properties": { tag.clone(): { "const": ref_name(variant) } },
+            "required": [tag],
+          })
+        })
+        .collect();
+      json!({ "oneOf": variants })
+    }
+  }
+}
+
+/// The base type's schema with the constraints added as the keywords that apply to it.
+///
+/// Bounds only replace the base schema's own bounds when they are tighter.
+fn constrained_schema(base: &TypeSignature, constraints: &Constraints) -> Value {
+  let mut schema = type_schema(base);
+  let map = match &mut schema {
+    Value::Object(map) => map,
+    _ => return schema,
+  };
+  let mut tighten = |key: &str, bound: f64, tighter: fn(f64, f64) -> bool| match map.get(key).and_then(Value::as_f64) {
+    Some(existing) if !tighter(bound, existing) => {}
+    _ => {
+      map.insert(key.to_owned(), bound_value(bound));
+    }
+  };
+  if let Some(minimum) = constraints.minimum {
+    tighten("minimum", minimum, |new, old| new > old);
+  }
+  if let Some(maximum) = constraints.maximum {
+    tighten("maximum", maximum, |new, old| new < old);
+  }
+  let (min_key, max_key) = match unwrap_optional(base) {
+    TypeSignature::Map { .. } | TypeSignature::Struct => ("minProperties", "maxProperties"),
+    TypeSignature::List { .. } | TypeSignature::Array { .. } | TypeSignature::Tuple { .. } | TypeSignature::Bytes => {
+      ("minItems", "maxItems")
+    }
+    _ => ("minLength", "maxLength"),
+  };
+  if let Some(min_length) = constraints.min_length {
+    map.insert(min_key.to_owned(), Value::from(min_length));
+  }
+  if let Some(max_length) = constraints.max_length {
+    map.insert(max_key.to_owned(), Value::from(max_length));
+  }
+  if let Some(pattern) = &constraints.pattern {
+    map.insert("pattern".to_owned(), Value::from(pattern.as_str()));
+  }
+  schema
+}
+
+/// Whole bounds are written as integers so they read the same as the integer types' own bounds.
+fn bound_value(bound: f64) -> Value {
+  if bound.fract() == 0.0 && bound.abs() < 9_007_199_254_740_992.0 {
+    Value::from(bound as i64)
+  } else {
+    Value::from(bound)
+  }
+}
+
+/// Convert a [TypeSignature::Ref] reference into a pointer into `$defs`, escaping `~` and `/` in the name.
+fn def_pointer(reference: &str) -> String {
+  format!("#/$defs/{}", ref_name(reference).replace('~', "~0").replace('/', "~1"))
+}
+
+/// The `pattern` of property names of maps keyed by signed integers.
+const SIGNED_KEY: &str = "^-?(0|[1-9][0-9]*)$";
+/// The `pattern` of property names of maps keyed by unsigned integers.
+const UNSIGNED_KEY: &str = "^(0|[1-9][0-9]*)$";
+
+/// The schema of a map's property names. Keys that are not strings are written as strings in JSON.
+fn key_schema(key: &TypeSignature) -> Value {
+  match integer_range(key) {
+    Some((min, _)) if min < 0 => json!({ "type": "string", "pattern": SIGNED_KEY }),
+    Some(_) => json!({ "type": "string", "pattern": UNSIGNED_KEY }),
+    None if *key == TypeSignature::Bool => json!({ "type": "string", "enum": ["true", "false"] }),
+    None => type_schema(key),
+  }
+}
+
+/// Add the `description`, `default`, and `deprecated` annotations to a schema.
+fn annotate(schema: &mut Value, description: Option<&String>, default: Option<&Value>, deprecated: bool) {
+  if let Value::Object(map) = schema {
+    if let Some(description) = description {
+      map.insert("description".to_owned(), Value::String(description.clone()));
+    }
+    if let Some(default) = default {
+      map.insert("default".to_owned(), default.clone());
+    }
+    if deprecated {
+      map.insert("deprecated".to_owned(), Value::Bool(true));
+    }
+  }
+}
+
+fn integer(minimum: i64, maximum: u64) -> Value {
+  json!({ "type": "integer", "minimum": minimum, "maximum": maximum })
+}
+
+/// An object schema with one property per entry in the [TypeMap].
+/// Entries that are neither optional nor have a default are required.
+fn ports_schema(title: &str, fields: &TypeMap) -> Value {
+  let mut properties = Map::new();
+  let mut required = Vec::new();
+  for (name, sig) in fields.ordered() {
+    let mut schema = type_schema(sig);
+    let metadata = fields.metadata(name);
+    if let Some(metadata) = metadata {
+      annotate(
+        &mut schema,
+        metadata.description.as_ref(),
+        metadata.default.as_ref(),
+        metadata.deprecated,
+      );
+    }
+    properties.insert(name.clone(), schema);
+    if !matches!(sig, TypeSignature::Optional { .. }) && metadata.and_then(|m| m.default.as_ref()).is_none() {
+      required.push(name.clone());
+    }
+  }
+  required.sort();
+  json!({
+    "title": title,
+    "type": "object",
+    "properties": properties,
+    "required": required,
+    "additionalProperties": false,
+  })
+}
+
+/// The root of a document with the dialect declared and the [StructMap] rendered into `$defs`.
+///
+/// Definitions used as [TypeSignature::Union] variants by `sigs` or by other definitions also
+/// declare the union's tag field so their `additionalProperties: false` accepts it.
+fn document<'a>(types: &'a StructMap, sigs: impl Iterator<Item = &'a TypeSignature>) -> Map<String, Value> {
+  let mut tags = BTreeMap::new();
+  for sig in sigs.chain(types.inner().values().flat_map(|def| def.fields.inner().values())) {
+    union_tags(sig, &mut tags);
+  }
+  let defs: Map<String, Value> = types
+    .ordered()
+    .into_iter()
+    .map(|(name, def)| {
+      let mut schema = struct_schema(def);
+      if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+        for tag in tags.get(name.as_str()).into_iter().flatten() {
+          properties.insert((*tag).to_owned(), json!({ "type": "string" }));
+        }
+      }
+      (name.clone(), schema)
+    })
+    .collect();
+  let mut schema = Map::new();
+  schema.insert("$schema".to_owned(), Value::String(DIALECT.to_owned()));
+  if !defs.is_empty() {
+    schema.insert("$defs".to_owned(), Value::Object(defs));
+  }
+  schema
+}
+
+/// Collect the tag fields of every [TypeSignature::Union] in `sig`, keyed by variant name.
+fn union_tags<'a>(sig: &'a TypeSignature, tags: &mut BTreeMap<&'a str, BTreeSet<&'a str>>) {
+  match sig {
+    TypeSignature::Union { tag, variants } => {
+      for variant in variants {
+        tags.entry(ref_name(variant)).or_default().insert(tag);
+      }
+    }
+    TypeSignature::List { element } | TypeSignature::Array { element, .. } => union_tags(element, tags),
+    TypeSignature::Optional { option } => union_tags(option, tags),
+    TypeSignature::Constrained { base, .. } => union_tags(base, tags),
+    TypeSignature::Map { key, value } => {
+      union_tags(key, tags);
+      union_tags(value, tags);
+    }
+    TypeSignature::Tuple { elements } => elements.iter().for_each(|element| union_tags(element, tags)),
+    _ => {}
+  }
+}
+
+fn standalone<'a>(schema: Value, types: &'a StructMap, sigs: impl Iterator<Item = &'a TypeSignature>) -> Value {
+  let mut root = document(types, sigs);
+  if let Value::Object(map) = schema {
+    root.extend(map);
+  }
+  Value::Object(root)
+}
@@ -0,0 +1,553 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+
+use serde_json::{Map, Value};
+
+use super::{SIGNED_KEY, UNSIGNED_KEY};
+use crate::compat::integer_range;
+use crate::maps::{MapWrapper, StructMap, TypeMap};
+use crate::pattern::Pattern;
+use crate::signatures::{Constraints, Metadata, StructSignature, TypeSignature};
+use crate::validate::{ref_name, TYPES_PREFIX};
+
+/// Error returned when a JSON Schema uses a construct that can not be expressed as a [TypeSignature].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+  /// The JSON pointer to the offending schema, e.g. `#/properties/users/items`.
+  pub path: String,
+  /// A description of the problem.
+  pub message: String,
+}
+
+impl ImportError {
+  fn new<T: AsRef<str>>(path: &str, message: T) -> Self {
+    Self {
+      path: path.to_owned(),
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for ImportError {}
+impl Display for ImportError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Could not import schema at {}: {}", self.path, self.message)
+  }
+}
+
+/// Convert a JSON Schema document into signatures.
+///
+/// Definitions under `$defs` (or `definitions`) become [StructSignature]s and the root object's
+/// properties become the returned [TypeMap]. Properties that are not `required` or that accept
+/// `null` become [TypeSignature::Optional] and inline object schemas are hoisted into the
+/// [StructMap] under a name derived from their location.
+pub fn import_schema(schema: &Value) -> Result<(StructMap, TypeMap), ImportError> {
+  let mut importer = Importer::default();
+  let mut definitions = Vec::new();
+  for key in ["$defs", "definitions"] {
+    if let Some(defs) = schema.get(key) {
+      let defs = defs
+        .as_object()
+        .ok_or_else(|| ImportError::new(&format!("#/{}", key), "expected an object of definitions"))?;
+      for (name, def) in defs {
+        let path = format!("#/{}/{}", key, name);
+        if !importer.reserved.insert(name.clone()) {
+          return Err(ImportError::new(&path, "defined under both '$defs' and 'definitions'"));
+        }
+        definitions.push((name, def, path));
+      }
+    }
+  }
+  // Every definition's name is reserved first, so hoisted inline objects never take one.
+  for (name, def, path) in definitions {
+    let def = importer.import_struct(name, def, &path)?;
+    importer.types.insert(name, def);
+  }
+  let root = importer.import_struct("", schema, "#")?;
+  // Exported definitions declare the tags of unions they belong to, but the tag is not part of the struct.
+  for (variant, tag) in importer.union_tags {
+    if let Some(def) = importer.types.get_inner_mut().get_mut(&variant) {
+      def.fields.remove(&tag);
+    }
+  }
+  Ok((importer.types, root.fields))
+}
+
+#[derive(Default)]
+struct Importer {
+  types: StructMap,
+  /// The names of the document's definitions, which hoisted objects must not use.
+  reserved: HashSet<String>,
+  /// The (variant, tag) pairs of every imported [TypeSignature::Union].
+  union_tags: Vec<(String, String)>,
+}
+
+impl Importer {
+  fn import_struct(&mut self, name: &str, schema: &Value, path: &str) -> Result<StructSignature, ImportError> {
+    let object = schema
+      .as_object()
+      .ok_or_else(|| ImportError::new(path, "expected an object schema"))?;
+    if let Some(t) = object.get("type") {
+      if t != "object" {
+        return Err(ImportError::new(path, format!("expected type 'object', found {}", t)));
+      }
+    }
+    self.import_fields(name, object, path)
+  }
+
+  fn import_fields(
+    &mut self,
+    name: &str,
+    object: &Map<String, Value>,
+    path: &str,
+  ) -> Result<StructSignature, ImportError> {
+    let required: Vec<&str> = object
+      .get("required")
+      .and_then(Value::as_array)
+      .map(|list| list.iter().filter_map(Value::as_str).collect())
+      .unwrap_or_default();
+    let mut fields = TypeMap::new();
+    if let Some(properties) = object.get("properties").and_then(Value::as_object) {
+      for (field, prop) in properties {
+        let prop_path = format!("{}/properties/{}", path, field);
+        let hint = format!("{}{}", name, pascal_case(field));
+        let sig = self.import_type(prop, &prop_path, &hint)?;
+        let metadata = import_metadata(prop);
+        // A property with a default may be omitted without being optional.
+        let sig = if required.contains(&field.as_str()) || metadata.default.is_some() {
+          sig
+        } else {
+          optional(sig)
+        };
+        fields.insert(field, sig);
+        fields.set_metadata(field, metadata);
+      }
+    }
+    Ok(StructSignature {
+      name: name.to_owned(),
+      fields,
+      description: object.get("description").and_then(Value::as_str).map(ToOwned::to_owned),
+      deprecated: object.get("deprecated") == Some(&Value::Bool(true)),
+    })
+  }
+
+  /// Import a schema as a [TypeSignature]. `hint` names any struct hoisted out of an inline object.
+  fn import_type(&mut self, schema: &Value, path: &str, hint: &str) -> Result<TypeSignature, ImportError> {
+    let object = match schema {
+      Value::Bool(true) => return Ok(TypeSignature::Value),
+      Value::Object(object) => object,
+      _ => return Err(ImportError::new(path, "expected a schema object")),
+    };
+    for unsupported in ["allOf", "not", "if", "patternProperties"] {
+      if object.contains_key(unsupported) {
+        return Err(ImportError::new(path, format!("'{}' is not supported", unsupported)));
+      }
+    }
+    if let Some(reference) = object.get("$ref") {
+      return import_ref(reference, path);
+    }
+    for key in ["anyOf", "oneOf"] {
+      if let Some(variants) = object.get(key) {
+        let path = format!("{}/{}", path, key);
+        if let Some(union) = self.import_tagged_union(variants, &path)? {
+          return Ok(union);
+        }
+        return self.import_nullable_union(variants, &path, hint);
+      }
+    }
+    if let Some(variants) = object.get("enum") {
+      return import_enum(variants, path);
+    }
+    if let Some(value) = object.get("const") {
+      return import_enum(&Value::Array(vec![value.clone()]), path);
+    }
+    let nullable = object.get("nullable") == Some(&Value::Bool(true));
+    let sig = match object.get("type") {
+      None => TypeSignature::Value,
+      Some(Value::String(t)) => self.import_typed(t, object, path, hint)?,
+      Some(Value::Array(types)) => {
+        let non_null: Vec<&Value> = types.iter().filter(|t| *t != "null").collect();
+        let sig = match non_null.as_slice() {
+          [] => return Err(ImportError::new(path, "a type of only 'null' is not supported")),
+          [Value::String(t)] => self.import_typed(t, object, path, hint)?,
+          _ => return Err(ImportError::new(path, "unions of multiple types are not supported")),
+        };
+        if non_null.len() < types.len() {
+          optional(sig)
+        } else {
+          sig
+        }
+      }
+      Some(other) => return Err(ImportError::new(path, format!("invalid type {}", other))),
+    };
+    Ok(if nullable { optional(sig) } else { sig })
+  }
+
+  fn import_typed(
+    &mut self,
+    t: &str,
+    object: &Map<String, Value>,
+    path: &str,
+    hint: &str,
+  ) -> Result<TypeSignature, ImportError> {
+    let format = object.get("format").and_then(Value::as_str);
+    let sig = match t {
+      "boolean" => TypeSignature::Bool,
+      "integer" => import_integer(object, format),
+      "number" if format == Some("float") => TypeSignature::F32,
+      "number" => TypeSignature::F64,
+      "string" => match format {
+        Some("date-time") => TypeSignature::Datetime,
+        Some("uuid") => TypeSignature::Uuid,
+        Some("duration") => TypeSignature::Duration,
+        Some("decimal") => TypeSignature::Decimal,
+        Some("uri") => TypeSignature::Url,
+        _ if object.get("contentEncoding").is_some() => TypeSignature::Bytes,
+        _ => TypeSignature::String,
+      },
+      "array" => self.import_array(object, path, hint)?,
+      "object" => self.import_object(object, path, hint)?,
+      "null" => return Err(ImportError::new(path, "a type of only 'null' is not supported")),
+      other => return Err(ImportError::new(path, format!("unknown type '{}'", other))),
+    };
+    import_constraints(sig, object, path)
+  }
+
+  /// Import an array schema as a [TypeSignature::Tuple] if it has `prefixItems`, a
+  /// [TypeSignature::Array] if its length is fixed, and a [TypeSignature::List] otherwise.
+  fn import_array(
+    &mut self,
+    object: &Map<String, Value>,
+    path: &str,
+    hint: &str,
+  ) -> Result<TypeSignature, ImportError> {
+    if let Some(prefix) = object.get("prefixItems") {
+      let prefix_path = format!("{}/prefixItems", path);
+      let prefix = prefix
+        .as_array()
+        .ok_or_else(|| ImportError::new(&prefix_path, "expected an array of schemas"))?;
+      if object.get("items").is_some_and(|items| items != &Value::Bool(false)) {
+        return Err(ImportError::new(
+          path,
+          "'prefixItems' followed by more 'items' is not supported",
+        ));
+      }
+      let elements = prefix
+        .iter()
+        .enumerate()
+        .map(|(i, item)| self.import_type(item, &format!("{}/{}", prefix_path, i), &format!("{}Item{}", hint, i)))
+        .collect::<Result<_, _>>()?;
+      return Ok(TypeSignature::Tuple { elements });
+    }
+    let element = match object.get("items") {
+      Some(items) => self.import_type(items, &format!("{}/items", path), &format!("{}Item", hint))?,
+      None => TypeSignature::Value,
+    };
+    let min = object.get("minItems").and_then(Value::as_u64);
+    match object.get("maxItems").and_then(Value::as_u64) {
+      Some(length) if min == Some(length) => Ok(TypeSignature::Array {
+        element: Box::new(element),
+        length,
+      }),
+      _ => Ok(TypeSignature::List {
+        element: Box::new(element),
+      }),
+    }
+  }
+
+  fn import_object(
+    &mut self,
+    object: &Map<String, Value>,
+    path: &str,
+    hint: &str,
+  ) -> Result<TypeSignature, ImportError> {
+    if object.contains_key("properties") {
+      let name = self.unique_name(hint);
+      let def = self.import_fields(&name, object, path)?;
+      self.types.insert(&name, def);
+      return Ok(TypeSignature::Ref {
+        reference: format!("{}{}", TYPES_PREFIX, name),
+      });
+    }
+    match object.get("additionalProperties") {
+      Some(value @ Value::Object(_)) => {
+        let value = self.import_type(
+          value,
+          &format!("{}/additionalProperties", path),
+          &format!("{}Value", hint),
+        )?;
+        let key = match object.get("propertyNames") {
+          Some(key) => self.import_key(key, &format!("{}/propertyNames", path), &format!("{}Key", hint))?,
+          None => TypeSignature::String,
+        };
+        Ok(TypeSignature::Map {
+          key: Box::new(key),
+          value: Box::new(value),
+        })
+      }
+      _ => Ok(TypeSignature::Struct),
+    }
+  }
+
+  /// Import the `propertyNames` of a map, reading back the patterns exported for integer keys.
+  fn import_key(&mut self, schema: &Value, path: &str, hint: &str) -> Result<TypeSignature, ImportError> {
+    match schema.get("pattern").and_then(Value::as_str) {
+      Some(SIGNED_KEY) => return Ok(TypeSignature::I64),
+      Some(UNSIGNED_KEY) => return Ok(TypeSignature::U64),
+      _ => {}
+    }
+    if schema.get("enum") == Some(&serde_json::json!(["true", "false"])) {
+      return Ok(TypeSignature::Bool);
+    }
+    self.import_type(schema, path, hint)
+  }
+
+  /// Import an `anyOf`/`oneOf` that is a single schema optionally unioned with `null`.
+  fn import_nullable_union(&mut self, variants: &Value, path: &str, hint: &str) -> Result<TypeSignature, ImportError> {
+    let variants = variants
+      .as_array()
+      .ok_or_else(|| ImportError::new(path, "expected an array of schemas"))?;
+    let is_null = |v: &Value| v.get("type").is_some_and(|t| t == "null");
+    let non_null: Vec<(usize, &Value)> = variants.iter().enumerate().filter(|(_, v)| !is_null(v)).collect();
+    match non_null.as_slice() {
+      [(i, schema)] => {
+        let sig = self.import_type(schema, &format!("{}/{}", path, i), hint)?;
+        Ok(if variants.len() > 1 { optional(sig) } else { sig })
+      }
+      _ => Err(ImportError::new(
+        path,
+        "only unions of a single schema with 'null' are supported",
+      )),
+    }
+  }
+
+  /// Import a union whose variants are each a `$ref` plus a single required `const` property, the tag.
+  /// Returns `None` if the schemas are not in that form.
+  fn import_tagged_union(&mut self, variants: &Value, path: &str) -> Result<Option<TypeSignature>, ImportError> {
+    let variants = match variants.as_array() {
+      Some(variants) => variants,
+      None => return Ok(None),
+    };
+    let is_null = |v: &Value| v.get("type").is_some_and(|t| t == "null");
+    let mut tag = None;
+    let mut refs = Vec::new();
+    for (i, variant) in variants.iter().enumerate().filter(|(_, v)| !is_null(v)) {
+      let (reference, properties) = match (
+        variant.get("$ref"),
+        variant.get("properties").and_then(Value::as_object),
+      ) {
+        (Some(reference), Some(properties)) if properties.len() == 1 => (reference, properties),
+        _ => return Ok(None),
+      };
+      let (variant_tag, value) = properties.iter().next().unwrap();
+      let required = variant.get("required").and_then(Value::as_array);
+      if !required.is_some_and(|r| r.iter().any(|v| v == variant_tag))
+        || value.get("const").and_then(Value::as_str).is_none()
+        || tag.is_some_and(|t: &String| t != variant_tag)
+      {
+        return Ok(None);
+      }
+      tag = Some(variant_tag);
+      refs.push(import_ref(reference, &format!("{}/{}", path, i))?);
+    }
+    let tag = match tag {
+      Some(tag) => tag.clone(),
+      None => return Ok(None),
+    };
+    let mut variant_refs = Vec::new();
+    for sig in refs {
+      if let TypeSignature::Ref { reference } = sig {
+        self.union_tags.push((ref_name(&reference).to_owned(), tag.clone()));
+        variant_refs.push(reference);
+      }
+    }
+    let union = TypeSignature::Union {
+      tag,
+      variants: variant_refs,
+    };
+    Ok(Some(if variants.iter().any(is_null) {
+      optional(union)
+    } else {
+      union
+    }))
+  }
+
+  fn unique_name(&self, hint: &str) -> String {
+    let hint = if hint.is_empty() { "Object" } else { hint };
+    let mut name = hint.to_owned();
+    let mut i = 2;
+    while self.types.contains_key(&name) || self.reserved.contains(&name) {
+      name = format!("{}{}", hint, i);
+      i += 1;
+    }
+    name
+  }
+}
+
+fn import_metadata(schema: &Value) -> Metadata {
+  Metadata {
+    description: schema.get("description").and_then(Value::as_str).map(ToOwned::to_owned),
+    default: schema.get("default").cloned(),
+    deprecated: schema.get("deprecated") == Some(&Value::Bool(true)),
+    ..Metadata::default()
+  }
+}
+
+fn import_ref(reference: &Value, path: &str) -> Result<TypeSignature, ImportError> {
+  let reference = reference
+    .as_str()
+    .ok_or_else(|| ImportError::new(path, "expected '$ref' to be a string"))?;
+  let name = reference
+    .strip_prefix("#/$defs/")
+    .or_else(|| reference.strip_prefix("#/definitions/"))
+    .filter(|name| !name.contains('/'))
+    .ok_or_else(|| {
+      ImportError::new(
+        path,
+        format!("only references into '#/$defs' are supported, found '{}'", reference),
+      )
+    })?;
+  Ok(TypeSignature::Ref {
+    reference: format!("{}{}", TYPES_PREFIX, name.replace("~1", "/").replace("~0", "~")),
+  })
+}
+
+/// Enumerations of strings are imported as [TypeSignature::Enum]s and others as the type shared by all of their values.
+fn import_enum(variants: &Value, path: &str) -> Result<TypeSignature, ImportError> {
+  let variants = variants
+    .as_array()
+    .ok_or_else(|| ImportError::new(path, "expected 'enum' to be an array"))?;
+  let nullable = variants.iter().any(Value::is_null);
+  let values: Vec<&Value> = variants.iter().filter(|v| !v.is_null()).collect();
+  let sig = if values.iter().all(|v| v.is_string()) {
+    TypeSignature::Enum {
+      values: values
+        .iter()
+        .filter_map(|v| v.as_str())
+        .map(ToOwned::to_owned)
+        .collect(),
+    }
+  } else if values.iter().all(|v| v.is_boolean()) {
+    TypeSignature::Bool
+  } else if values.iter().all(|v| v.is_i64()) {
+    TypeSignature::I64
+  } else if values.iter().all(|v| v.is_number()) {
+    TypeSignature::F64
+  } else {
+    return Err(ImportError::new(path, "enumerations of mixed types are not supported"));
+  };
+  Ok(if nullable { optional(sig) } else { sig })
+}
+
+/// Pick the integer type matching the schema's `format` or the narrowest type containing its bounds.
+fn import_integer(object: &Map<String, Value>, format: Option<&str>) -> TypeSignature {
+  match format {
+    Some("int8") => return TypeSignature::I8,
+    Some("int16") => return TypeSignature::I16,
+    Some("int32") => return TypeSignature::I32,
+    Some("int64") => return TypeSignature::I64,
+    Some("uint8") => return TypeSignature::U8,
+    Some("uint16") => return TypeSignature::U16,
+    Some("uint32") => return TypeSignature::U32,
+    Some("uint64") => return TypeSignature::U64,
+    _ => {}
+  }
+  let bound = |key: &str| {
+    object
+      .get(key)
+      .and_then(|v| v.as_i64().map(i128::from).or_else(|| v.as_u64().map(i128::from)))
+  };
+  let (min, max) = match (bound("minimum"), bound("maximum")) {
+    (Some(min), Some(max)) => (min, max),
+    _ => return TypeSignature::I64,
+  };
+  let candidates = [
+    (TypeSignature::U8, u8::MIN.into(), u8::MAX.into()),
+    (TypeSignature::I8, i8::MIN.into(), i8::MAX.into()),
+    (TypeSignature::U16, u16::MIN.into(), u16::MAX.into()),
+    (TypeSignature::I16, i16::MIN.into(), i16::MAX.into()),
+    (TypeSignature::U32, u32::MIN.into(), u32::MAX.into()),
+    (TypeSignature::I32, i32::MIN.into(), i32::MAX.into()),
+    (TypeSignature::U64, u64::MIN.into(), u64::MAX.into()),
+  ];
+  candidates
+    .into_iter()
+    .find(|(_, lo, hi): &(TypeSignature, i128, i128)| *lo <= min && max <= *hi)
+    .map_or(TypeSignature::I64, |(sig, _, _)| sig)
+}
+
+/// Wrap `sig` in a [TypeSignature::Constrained] for the validation keywords `sig` does not already express.
+fn import_constraints(
+  sig: TypeSignature,
+  object: &Map<String, Value>,
+  path: &str,
+) -> Result<TypeSignature, ImportError> {
+  let number = |key: &str| object.get(key).and_then(Value::as_f64);
+  let length = |key: &str| object.get(key).and_then(Value::as_u64);
+  let mut constraints = Constraints::new();
+  match &sig {
+    TypeSignature::String
+    | TypeSignature::Datetime
+    | TypeSignature::Uuid
+    | TypeSignature::Duration
+    | TypeSignature::Decimal
+    | TypeSignature::Url
+    | TypeSignature::Bytes => {
+      constraints.min_length = length("minLength");
+      constraints.max_length = length("maxLength");
+      constraints.pattern = match object.get("pattern").and_then(Value::as_str) {
+        Some(pattern) => Some(Pattern::new(pattern).map_err(|e| ImportError::new(path, e.to_string()))?),
+        None => None,
+      };
+    }
+    TypeSignature::List { .. } => {
+      constraints.min_length = length("minItems");
+      constraints.max_length = length("maxItems");
+    }
+    TypeSignature::Map { .. } | TypeSignature::Struct => {
+      constraints.min_length = length("minProperties");
+      constraints.max_length = length("maxProperties");
+    }
+    TypeSignature::F32 | TypeSignature::F64 => {
+      constraints.minimum = number("minimum");
+      constraints.maximum = number("maximum");
+    }
+    _ => {
+      // Integer bounds that only restate the range of the type picked for them are not constraints.
+      if let Some((min, max)) = integer_range(&sig) {
+        constraints.minimum = number("minimum").filter(|n| *n > min as f64);
+        constraints.maximum = number("maximum").filter(|n| *n < max as f64);
+      }
+    }
+  }
+  if constraints.is_empty() {
+    Ok(sig)
+  } else {
+    Ok(TypeSignature::Constrained {
+      base: Box::new(sig),
+      constraints,
+    })
+  }
+}
+
+fn optional(sig: TypeSignature) -> TypeSignature {
+  match sig {
+    TypeSignature::Optional { .. } => sig,
+    _ => TypeSignature::Optional { option: Box::new(sig) },
+  }
+}
+
+fn pascal_case(name: &str) -> String {
+  name
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+    })
+    .collect()
+}
@@ -78,9 +78,91 @@
 /// Signatures of Vino types.
 mod signatures;
 
-pub use signatures::{ComponentSignature, HostedType, InternalType, ProviderSignature, StructSignature, TypeSignature};
+pub use signatures::{
+  ComponentSignature, Constraints, HostedType, InternalType, Metadata, NetworkSignature, ParseError,
+  ProviderDependency, ProviderSignature, SchematicSignature, StructSignature, TypeSignature,
+};
 
 /// Map-related structures holding signatures and other types.
 pub mod maps;
 
-pub use maps::{ComponentMap, MapWrapper, ProviderMap, StructMap, TypeMap};
+pub use maps::{ComponentMap, MapWrapper, ProviderMap, SchematicMap, StructMap, TypeMap};
+
+/// Merging of [ProviderMap]s and [ComponentMap]s, and lookup of their components by entity.
+mod catalog;
+
+pub use catalog::MergeConflict;
+
+/// Conversion between signatures and [JSON Schema](https://json-schema.org/draft/2020-12/schema) documents.
+/// [TypeSignature::Ref]s correspond to `$ref`s into the document's `$defs`, which hold the provider's [StructMap].
+pub mod jsonschema;
+
+/// Validation of values against signatures.
+mod validate;
+
+pub use validate::{ValidationError, TYPES_PREFIX};
+
+/// Compatibility checks between versions of a signature.
+mod compat;
+
+pub use compat::{is_assignable, Change, ChangeKind, CompatibilityReport};
+
+/// Resolution of [TypeSignature::Ref]s against a provider's types.
+mod resolve;
+
+pub use resolve::{ResolvedType, SignatureError};
+
+/// The canonical representation of [TypeSignature::Datetime] values.
+mod datetime;
+
+pub use datetime::{Datetime, DatetimeError, TIMESTAMP_EXT};
+
+/// The canonical representation of [TypeSignature::Uuid] values.
+mod uuid;
+
+pub use uuid::{Uuid, UuidError};
+
+/// The canonical representation of [TypeSignature::Duration] values.
+mod duration;
+
+pub use duration::{Duration, DurationError};
+
+/// The canonical representation of [TypeSignature::Decimal] values.
+mod decimal;
+
+pub use decimal::{Decimal, DecimalError};
+
+/// The canonical representation of [TypeSignature::Url] values.
+mod url;
+
+pub use url::{Url, UrlError};
+
+/// The compiled regular expressions of [Constraints] patterns.
+mod pattern;
+
+pub use pattern::{Pattern, PatternError};
+
+/// Conversion of loosely typed values into the types a signature declares.
+mod coerce;
+
+pub use coerce::CoercionError;
+
+/// The number of values a component port carries.
+mod cardinality;
+
+#[cfg(feature = "transport")]
+pub use cardinality::OutputTracker;
+pub use cardinality::{Cardinality, CardinalityError};
+
+/// Generation of example values for signatures.
+mod sample;
+
+pub use sample::{SampleError, Sampler};
+
+/// Stable hashes of signatures.
+mod fingerprint;
+
+pub use fingerprint::Fingerprint;
+
+/// A compact interface definition language for [ProviderSignature]s, with a parser and a printer.
+pub mod idl;
@@ -1,40 +1,158 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use indexmap::IndexMap;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::signatures::{ComponentSignature, ParseError, ProviderSignature, StructSignature, TypeSignature};
+use crate::cardinality::Cardinality;
+use crate::signatures::{
+  ComponentSignature, Metadata, ParseError, ProviderSignature, SchematicSignature, StructSignature, TypeSignature,
+};
+use crate::validate::TYPES_PREFIX;
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
-#[serde(transparent)]
+#[derive(Debug, Clone, Default, PartialEq)]
 #[must_use]
-/// A HashMap of type names to their signature.
-pub struct TypeMap(HashMap<String, TypeSignature>);
+/// A map of type names to their signature, in the order entries were declared in.
+///
+/// Each entry may also carry [Metadata], which is serialized alongside the entry's type,
+/// e.g. `{"type": "u32", "description": "Page size", "default": 20}`. Maps are equal when they have the same
+/// entries and metadata, in any order.
+pub struct TypeMap {
+  types: IndexMap<String, TypeSignature>,
+  metadata: HashMap<String, Metadata>,
+}
 
 impl TypeMap {
   /// Constructor for [TypeMap]
   pub fn new() -> Self {
-    Self(HashMap::new())
+    Self::default()
+  }
+
+  /// Insert a [TypeSignature] without [Metadata], keeping the position of an entry it replaces and dropping
+  /// the replaced entry's [Metadata].
+  pub fn insert<K: AsRef<str>>(&mut self, field: K, value: TypeSignature) {
+    let field = field.as_ref();
+    self.metadata.remove(field);
+    self.types.insert(field.to_owned(), value);
+  }
+
+  /// Remove an entry and its [Metadata], keeping the order of the others.
+  pub fn remove<K: AsRef<str>>(&mut self, field: K) -> Option<TypeSignature> {
+    let field = field.as_ref();
+    self.metadata.remove(field);
+    self.types.shift_remove(field)
+  }
+
+  /// Get the [Metadata] declared for an entry.
+  #[must_use]
+  pub fn metadata<K: AsRef<str>>(&self, field: K) -> Option<&Metadata> {
+    self
+      .metadata
+      .get(field.as_ref())
+      .filter(|_| self.types.contains_key(field.as_ref()))
+  }
+
+  /// Get the [Cardinality] declared for an entry, [Cardinality::Single] unless set.
+  pub fn cardinality<K: AsRef<str>>(&self, field: K) -> Cardinality {
+    self.metadata(field).map(|m| m.cardinality).unwrap_or_default()
+  }
+
+  /// Set the [Metadata] for an entry.
+  pub fn set_metadata<K: AsRef<str>>(&mut self, field: K, metadata: Metadata) {
+    if metadata.is_empty() {
+      self.metadata.remove(field.as_ref());
+    } else {
+      self.metadata.insert(field.as_ref().to_owned(), metadata);
+    }
+  }
+
+  /// Add the [Metadata] for an entry.
+  pub fn with_metadata<K: AsRef<str>>(mut self, field: K, metadata: Metadata) -> Self {
+    self.set_metadata(field, metadata);
+    self
   }
 }
 
 impl MapWrapper<TypeSignature> for TypeMap {
-  fn get_inner_owned(self) -> HashMap<String, TypeSignature> {
-    self.0
+  fn get_inner_owned(self) -> IndexMap<String, TypeSignature> {
+    self.types
   }
 
-  fn get_inner(&self) -> &HashMap<String, TypeSignature> {
-    &self.0
+  fn get_inner(&self) -> &IndexMap<String, TypeSignature> {
+    &self.types
   }
 
-  fn get_inner_mut(&mut self) -> &mut HashMap<String, TypeSignature> {
-    &mut self.0
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, TypeSignature> {
+    &mut self.types
+  }
+
+  /// See [TypeMap::insert].
+  fn insert<K: AsRef<str>>(&mut self, field: K, value: TypeSignature) {
+    TypeMap::insert(self, field, value);
+  }
+}
+
+#[derive(Serialize)]
+struct EntryRef<'a> {
+  #[serde(flatten)]
+  sig: &'a TypeSignature,
+  #[serde(flatten)]
+  metadata: Option<&'a Metadata>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+  #[serde(flatten)]
+  sig: TypeSignature,
+  #[serde(flatten)]
+  metadata: Metadata,
+}
+
+impl Serialize for TypeMap {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(self.types.iter().map(|(name, sig)| {
+      (
+        name,
+        EntryRef {
+          sig,
+          metadata: self.metadata(name),
+        },
+      )
+    }))
+  }
+}
+
+impl<'de> Deserialize<'de> for TypeMap {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let entries = IndexMap::<String, Entry>::deserialize(deserializer)?;
+    let mut map = TypeMap::new();
+    for (name, entry) in entries {
+      if let Some(default) = &entry.metadata.default {
+        entry
+          .sig
+          .check_default(default)
+          .map_err(|e| D::Error::custom(format!("invalid default for '{}': {}", name, e)))?;
+      }
+      map.insert(&name, entry.sig);
+      map.set_metadata(&name, entry.metadata);
+    }
+    Ok(map)
+  }
+}
+
+impl From<IndexMap<String, TypeSignature>> for TypeMap {
+  fn from(types: IndexMap<String, TypeSignature>) -> Self {
+    Self {
+      types,
+      metadata: HashMap::new(),
+    }
   }
 }
 
 impl From<HashMap<String, TypeSignature>> for TypeMap {
-  fn from(map: HashMap<String, TypeSignature>) -> Self {
-    Self(map)
+  fn from(types: HashMap<String, TypeSignature>) -> Self {
+    types.into_iter().collect()
   }
 }
 
@@ -52,43 +170,58 @@ impl TryFrom<Vec<(&str, &str)>> for TypeMap {
 
 impl FromIterator<(String, TypeSignature)> for TypeMap {
   fn from_iter<T: IntoIterator<Item = (String, TypeSignature)>>(iter: T) -> Self {
-    let mut map: HashMap<String, TypeSignature> = HashMap::new();
+    let mut map = TypeMap::new();
     for (k, v) in iter {
       map.insert(k, v);
     }
-    Self(map)
+    map
   }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 #[must_use]
-/// A HashMap of struct names to their signature.
-pub struct StructMap(pub HashMap<String, StructSignature>);
+/// A map of struct names to their signature, in the order they were added.
+pub struct StructMap(pub IndexMap<String, StructSignature>);
+
+impl From<IndexMap<String, StructSignature>> for StructMap {
+  fn from(map: IndexMap<String, StructSignature>) -> Self {
+    Self(map)
+  }
+}
 
 impl From<HashMap<String, StructSignature>> for StructMap {
   fn from(map: HashMap<String, StructSignature>) -> Self {
-    Self(map)
+    Self(map.into_iter().collect())
   }
 }
 
 impl StructMap {
   /// Constructor for [StructMap]
   pub fn new() -> Self {
-    Self(HashMap::new())
+    Self(IndexMap::new())
+  }
+
+  /// Look up the [StructSignature] a [TypeSignature::Ref] points to, by name or by `#/types/` pointer.
+  #[must_use]
+  pub fn resolve(&self, reference: &str) -> Option<&StructSignature> {
+    self
+      .0
+      .get(reference)
+      .or_else(|| reference.strip_prefix(TYPES_PREFIX).and_then(|name| self.0.get(name)))
   }
 }
 
 impl MapWrapper<StructSignature> for StructMap {
-  fn get_inner_owned(self) -> HashMap<String, StructSignature> {
+  fn get_inner_owned(self) -> IndexMap<String, StructSignature> {
     self.0
   }
 
-  fn get_inner(&self) -> &HashMap<String, StructSignature> {
+  fn get_inner(&self) -> &IndexMap<String, StructSignature> {
     &self.0
   }
 
-  fn get_inner_mut(&mut self) -> &mut HashMap<String, StructSignature> {
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, StructSignature> {
     &mut self.0
   }
 }
@@ -96,25 +229,31 @@ impl MapWrapper<StructSignature> for StructMap {
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 #[must_use]
-/// A HashMap of provider names to their signature.
-pub struct ProviderMap(pub HashMap<String, ProviderSignature>);
+/// A map of provider names to their signature, in the order they were added.
+pub struct ProviderMap(pub IndexMap<String, ProviderSignature>);
+
+impl From<IndexMap<String, ProviderSignature>> for ProviderMap {
+  fn from(map: IndexMap<String, ProviderSignature>) -> Self {
+    Self(map)
+  }
+}
 
 impl From<HashMap<String, ProviderSignature>> for ProviderMap {
   fn from(map: HashMap<String, ProviderSignature>) -> Self {
-    Self(map)
+    Self(map.into_iter().collect())
   }
 }
 
 impl MapWrapper<ProviderSignature> for ProviderMap {
-  fn get_inner_owned(self) -> HashMap<String, ProviderSignature> {
+  fn get_inner_owned(self) -> IndexMap<String, ProviderSignature> {
     self.0
   }
 
-  fn get_inner(&self) -> &HashMap<String, ProviderSignature> {
+  fn get_inner(&self) -> &IndexMap<String, ProviderSignature> {
     &self.0
   }
 
-  fn get_inner_mut(&mut self) -> &mut HashMap<String, ProviderSignature> {
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, ProviderSignature> {
     &mut self.0
   }
 }
@@ -122,26 +261,32 @@ impl MapWrapper<ProviderSignature> for ProviderMap {
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(transparent)]
 #[must_use]
-/// A HashMap of component names to their signature.
-pub struct ComponentMap(pub HashMap<String, ComponentSignature>);
+/// A map of component names to their signature, in the order they were added.
+pub struct ComponentMap(pub IndexMap<String, ComponentSignature>);
 
 impl MapWrapper<ComponentSignature> for ComponentMap {
-  fn get_inner_owned(self) -> HashMap<String, ComponentSignature> {
+  fn get_inner_owned(self) -> IndexMap<String, ComponentSignature> {
     self.0
   }
 
-  fn get_inner(&self) -> &HashMap<String, ComponentSignature> {
+  fn get_inner(&self) -> &IndexMap<String, ComponentSignature> {
     &self.0
   }
 
-  fn get_inner_mut(&mut self) -> &mut HashMap<String, ComponentSignature> {
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, ComponentSignature> {
     &mut self.0
   }
 }
 
+impl From<IndexMap<String, ComponentSignature>> for ComponentMap {
+  fn from(map: IndexMap<String, ComponentSignature>) -> Self {
+    Self(map)
+  }
+}
+
 impl From<HashMap<String, ComponentSignature>> for ComponentMap {
   fn from(map: HashMap<String, ComponentSignature>) -> Self {
-    Self(map)
+    Self(map.into_iter().collect())
   }
 }
 
@@ -151,41 +296,79 @@ impl From<HashMap<&str, ComponentSignature>> for ComponentMap {
   }
 }
 
-/// Utility functions for HashMap wrappers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(transparent)]
+#[must_use]
+/// A map of schematic names to their signature, in the order they were added.
+pub struct SchematicMap(pub IndexMap<String, SchematicSignature>);
+
+impl MapWrapper<SchematicSignature> for SchematicMap {
+  fn get_inner_owned(self) -> IndexMap<String, SchematicSignature> {
+    self.0
+  }
+
+  fn get_inner(&self) -> &IndexMap<String, SchematicSignature> {
+    &self.0
+  }
+
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, SchematicSignature> {
+    &mut self.0
+  }
+}
+
+impl From<IndexMap<String, SchematicSignature>> for SchematicMap {
+  fn from(map: IndexMap<String, SchematicSignature>) -> Self {
+    Self(map)
+  }
+}
+
+impl From<HashMap<String, SchematicSignature>> for SchematicMap {
+  fn from(map: HashMap<String, SchematicSignature>) -> Self {
+    Self(map.into_iter().collect())
+  }
+}
+
+/// Utility functions for map wrappers.
 pub trait MapWrapper<T>
 where
   Self: Sized,
 {
-  /// Get the inner HashMap.
-  fn get_inner_owned(self) -> HashMap<String, T>;
+  /// Get the inner map.
+  fn get_inner_owned(self) -> IndexMap<String, T>;
+
+  /// Get a reference to the inner map.
+  fn get_inner(&self) -> &IndexMap<String, T>;
 
-  /// Get a reference to the inner HashMap.
-  fn get_inner(&self) -> &HashMap<String, T>;
+  /// Get a mutable reference to the inner map.
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, T>;
 
-  /// Get a mutable reference to the inner HashMap.
-  fn get_inner_mut(&mut self) -> &mut HashMap<String, T>;
+  #[must_use]
+  /// Return the entries in the order they were added.
+  fn ordered(&self) -> Vec<(&String, &T)> {
+    self.get_inner().iter().collect()
+  }
 
   #[must_use]
-  /// Return a list of names in the inner HashMap.
+  /// Return a list of names in the inner map, in the order of [MapWrapper::ordered].
   fn names(&self) -> Vec<String> {
-    self.get_inner().keys().cloned().collect()
+    self.ordered().into_iter().map(|(name, _)| name.clone()).collect()
   }
 
   #[must_use]
-  /// Return true if the inner HashMap is empty.
+  /// Return true if the inner map is empty.
   fn is_empty(&self) -> bool {
     self.get_inner().is_empty()
   }
 
-  /// Return the inner HashMap.
+  /// Return the inner map.
   #[must_use]
-  fn into_inner(self) -> HashMap<String, T> {
+  fn into_inner(self) -> IndexMap<String, T> {
     self.get_inner_owned()
   }
 
-  /// Return a reference to the inner HashMap.
+  /// Return a reference to the inner map.
   #[must_use]
-  fn inner(&self) -> &HashMap<String, T> {
+  fn inner(&self) -> &IndexMap<String, T> {
     self.get_inner()
   }
 
@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// A compiled regular expression, the value of a [crate::Constraints] `pattern`.
+///
+/// The expression is compiled once, when it is parsed or deserialized, so an invalid pattern is rejected
+/// there rather than when a value is checked against it. Every format encodes a [Pattern] as its source.
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Pattern(Regex);
+
+impl Pattern {
+  /// Compile a regular expression.
+  pub fn new<T: AsRef<str>>(pattern: T) -> Result<Self, PatternError> {
+    pattern.as_ref().parse()
+  }
+
+  /// The source the pattern was compiled from.
+  #[must_use]
+  pub fn as_str(&self) -> &str {
+    self.0.as_str()
+  }
+
+  /// Returns true if the pattern matches somewhere in `text`.
+  #[must_use]
+  pub fn is_match(&self, text: &str) -> bool {
+    self.0.is_match(text)
+  }
+}
+
+impl PartialEq for Pattern {
+  fn eq(&self, other: &Self) -> bool {
+    self.as_str() == other.as_str()
+  }
+}
+
+/// A string that is not a valid regular expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+  message: String,
+}
+
+impl Error for PatternError {}
+impl Display for PatternError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid pattern: {}", self.message)
+  }
+}
+
+impl Display for Pattern {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl FromStr for Pattern {
+  type Err = PatternError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Regex::new(s)
+      .map(Self)
+      .map_err(|e| PatternError { message: e.to_string() })
+  }
+}
+
+impl Serialize for Pattern {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_str(PatternVisitor)
+  }
+}
+
+struct PatternVisitor;
+
+impl<'de> Visitor<'de> for PatternVisitor {
+  type Value = Pattern;
+
+  fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    formatter.write_str("a regular expression")
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    v.parse().map_err(E::custom)
+  }
+}
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+
+use indexmap::IndexMap;
+
+use crate::maps::{MapWrapper, TypeMap};
+use crate::signatures::{Constraints, ProviderSignature, StructSignature, TypeSignature};
+use crate::validate::ref_name;
+
+/// A problem with the references in a [ProviderSignature].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignatureError {
+  /// A [TypeSignature::Ref] that does not point to any of the provider's types.
+  DanglingRef {
+    /// Where the reference occurs, e.g. `components.add.inputs.left` or `types.Blog.fields.author`.
+    path: String,
+    /// The unresolved reference.
+    reference: String,
+  },
+  /// A set of types that reference each other in a loop that no list, map or optional can end, so no finite
+  /// value exists. From [ProviderSignature::inline], any recursive type.
+  RecursiveType {
+    /// The names of the types in the cycle, starting with the first in name order.
+    cycle: Vec<String>,
+  },
+}
+
+impl Error for SignatureError {}
+impl Display for SignatureError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      SignatureError::DanglingRef { path, reference } => write!(f, "{}: unresolved reference '{}'", path, reference),
+      SignatureError::RecursiveType { cycle } => {
+        write!(f, "recursive type: {} -> {}", cycle.join(" -> "), cycle[0])
+      }
+    }
+  }
+}
+
+/// A [TypeSignature] with every [TypeSignature::Ref] replaced by the struct it points to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedType {
+  /// A type that contains no references.
+  Type(TypeSignature),
+  /// A referenced struct with its fields resolved.
+  Struct {
+    /// The struct's name.
+    name: String,
+    /// The struct's resolved fields.
+    fields: BTreeMap<String, ResolvedType>,
+  },
+  /// A list of resolved elements.
+  List {
+    /// The type of the list's elements.
+    element: Box<ResolvedType>,
+  },
+  /// An optional resolved value.
+  Optional {
+    /// The actual type that is optional.
+    option: Box<ResolvedType>,
+  },
+  /// A tuple of resolved elements.
+  Tuple {
+    /// The types of the values, in order.
+    elements: Vec<ResolvedType>,
+  },
+  /// A fixed-length array of resolved elements.
+  Array {
+    /// The type of the array's elements.
+    element: Box<ResolvedType>,
+    /// The number of elements.
+    length: u64,
+  },
+  /// A resolved value that must also satisfy [Constraints].
+  Constrained {
+    /// The type being constrained.
+    base: Box<ResolvedType>,
+    /// The limits on the value.
+    constraints: Constraints,
+  },
+  /// A map with resolved keys and values.
+  Map {
+    /// The type of the map's keys.
+    key: Box<ResolvedType>,
+    /// The type of the map's values.
+    value: Box<ResolvedType>,
+  },
+  /// A tagged union with its variants resolved.
+  Union {
+    /// The name of the field holding the variant's type name.
+    tag: String,
+    /// The resolved variant structs.
+    variants: Vec<ResolvedType>,
+  },
+}
+
+impl ProviderSignature {
+  /// Look up the [StructSignature] a [TypeSignature::Ref] points to.
+  #[must_use]
+  pub fn resolve(&self, reference: &str) -> Option<&StructSignature> {
+    self.types.resolve(reference)
+  }
+
+  /// Check that every reference in the signature resolves and that no types are infinitely recursive.
+  ///
+  /// Recursion through a list, map or optional is allowed, e.g. `Node { children: ref:Node[] }`.
+  pub fn validate(&self) -> Result<(), Vec<SignatureError>> {
+    let mut errors = Vec::new();
+    for (name, component) in sorted(self.components.inner()) {
+      self.dangling_refs(&format!("components.{}.config", name), &component.config, &mut errors);
+      self.dangling_refs(&format!("components.{}.inputs", name), &component.inputs, &mut errors);
+      self.dangling_refs(&format!("components.{}.outputs", name), &component.outputs, &mut errors);
+    }
+    self.dangling_refs("config", &self.config, &mut errors);
+    for (name, def) in sorted(self.types.inner()) {
+      self.dangling_refs(&format!("types.{}.fields", name), &def.fields, &mut errors);
+    }
+    errors.extend(self.recursive_types());
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Every cycle of types that reference each other without a list, map or optional to end it, each reported once.
+  #[must_use]
+  pub fn recursive_types(&self) -> Vec<SignatureError> {
+    let mut cycles = Vec::new();
+    let mut done = Vec::new();
+    for (name, _) in sorted(self.types.inner()) {
+      self.find_cycles(name, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+  }
+
+  /// Replace every reference in the passed [TypeSignature] with the struct it points to.
+  ///
+  /// A [ResolvedType] is finite, so any recursive type is an error here even if [ProviderSignature::validate]
+  /// accepts it.
+  pub fn inline(&self, sig: &TypeSignature) -> Result<ResolvedType, SignatureError> {
+    self.inline_at(sig, "$", &mut Vec::new())
+  }
+
+  fn inline_at(
+    &self,
+    sig: &TypeSignature,
+    path: &str,
+    stack: &mut Vec<String>,
+  ) -> Result<ResolvedType, SignatureError> {
+    let resolved = match sig {
+      TypeSignature::Ref { reference } => {
+        let def = self.resolve(reference).ok_or_else(|| SignatureError::DanglingRef {
+          path: path.to_owned(),
+          reference: reference.clone(),
+        })?;
+        let key = self.key(reference);
+        if let Some(start) = stack.iter().position(|n| n == key) {
+          return Err(SignatureError::RecursiveType {
+            cycle: normalize(&stack[start..]),
+          });
+        }
+        stack.push(key.to_owned());
+        let mut fields = BTreeMap::new();
+        for (field, field_sig) in sorted(def.fields.inner()) {
+          let field_path = format!("{}.{}", path, field);
+          fields.insert(field.clone(), self.inline_at(field_sig, &field_path, stack)?);
+        }
+        stack.pop();
+        ResolvedType::Struct {
+          name: def.name.clone(),
+          fields,
+        }
+      }
+      TypeSignature::List { element } => ResolvedType::List {
+        element: Box::new(self.inline_at(element, &format!("{}[]", path), stack)?),
+      },
+      TypeSignature::Optional { option } => ResolvedType::Optional {
+        option: Box::new(self.inline_at(option, path, stack)?),
+      },
+      TypeSignature::Tuple { elements } => ResolvedType::Tuple {
+        elements: elements
+          .iter()
+          .enumerate()
+          .map(|(i, element)| self.inline_at(element, &format!("{}[{}]", path, i), stack))
+          .collect::<Result<_, _>>()?,
+      },
+      TypeSignature::Array { element, length } => ResolvedType::Array {
+        element: Box::new(self.inline_at(element, &format!("{}[]", path), stack)?),
+        length: *length,
+      },
+      TypeSignature::Constrained { base, constraints } => ResolvedType::Constrained {
+        base: Box::new(self.inline_at(base, path, stack)?),
+        constraints: constraints.clone(),
+      },
+      TypeSignature::Map { key, value } => ResolvedType::Map {
+        key: Box::new(self.inline_at(key, path, stack)?),
+        value: Box::new(self.inline_at(value, &format!("{}[]", path), stack)?),
+      },
+      TypeSignature::Union { tag, variants } => ResolvedType::Union {
+        tag: tag.clone(),
+        variants: variants
+          .iter()
+          .map(|variant| {
+            let variant = TypeSignature::Ref {
+              reference: variant.clone(),
+            };
+            self.inline_at(&variant, path, stack)
+          })
+          .collect::<Result<_, _>>()?,
+      },
+      other => ResolvedType::Type(other.clone()),
+    };
+    Ok(resolved)
+  }
+
+  fn dangling_refs(&self, path: &str, map: &TypeMap, errors: &mut Vec<SignatureError>) {
+    for (name, sig) in sorted(map.inner()) {
+      let mut refs = Vec::new();
+      references(sig, &mut refs);
+      for reference in refs {
+        if self.resolve(reference).is_none() {
+          errors.push(SignatureError::DanglingRef {
+            path: format!("{}.{}", path, name),
+            reference: reference.to_owned(),
+          });
+        }
+      }
+    }
+  }
+
+  /// The key in the provider's types that a reference points to.
+  fn key<'a>(&self, reference: &'a str) -> &'a str {
+    if self.types.contains_key(reference) {
+      reference
+    } else {
+      ref_name(reference)
+    }
+  }
+
+  /// Depth-first search from `name`, recording a cycle whenever a type on the current stack is reached again.
+  fn find_cycles(&self, name: &str, stack: &mut Vec<String>, done: &mut Vec<String>, cycles: &mut Vec<SignatureError>) {
+    if done.iter().any(|n| n == name) {
+      return;
+    }
+    if let Some(start) = stack.iter().position(|n| n == name) {
+      let cycle = SignatureError::RecursiveType {
+        cycle: normalize(&stack[start..]),
+      };
+      if !cycles.contains(&cycle) {
+        cycles.push(cycle);
+      }
+      return;
+    }
+    let def = match self.types.get(name) {
+      Some(def) => def,
+      None => return,
+    };
+    stack.push(name.to_owned());
+    for (_, sig) in sorted(def.fields.inner()) {
+      let mut refs = Vec::new();
+      required_references(sig, &mut refs);
+      for reference in refs {
+        self.find_cycles(self.key(reference), stack, done, cycles);
+      }
+    }
+    stack.pop();
+    done.push(name.to_owned());
+  }
+}
+
+/// Every reference contained in a [TypeSignature], however deeply nested.
+pub(crate) fn references<'a>(sig: &'a TypeSignature, refs: &mut Vec<&'a str>) {
+  match sig {
+    TypeSignature::Ref { reference } => refs.push(reference),
+    TypeSignature::Union { variants, .. } => refs.extend(variants.iter().map(String::as_str)),
+    TypeSignature::List { element } | TypeSignature::Array { element, .. } => references(element, refs),
+    TypeSignature::Optional { option } => references(option, refs),
+    TypeSignature::Constrained { base, .. } => references(base, refs),
+    TypeSignature::Tuple { elements } => elements.iter().for_each(|element| references(element, refs)),
+    TypeSignature::Map { key, value } => {
+      references(key, refs);
+      references(value, refs);
+    }
+    _ => {}
+  }
+}
+
+/// The references a value of the [TypeSignature] must contain. Lists and maps can be empty and optionals can be
+/// absent, so references under them are skipped, as are unions that have another variant to choose.
+fn required_references<'a>(sig: &'a TypeSignature, refs: &mut Vec<&'a str>) {
+  match sig {
+    TypeSignature::Ref { reference } => refs.push(reference),
+    TypeSignature::Union { variants, .. } if variants.len() == 1 => refs.extend(variants.iter().map(String::as_str)),
+    TypeSignature::Array { element, length } if *length > 0 => required_references(element, refs),
+    TypeSignature::Constrained { base, .. } => required_references(base, refs),
+    TypeSignature::Tuple { elements } => elements.iter().for_each(|element| required_references(element, refs)),
+    _ => {}
+  }
+}
+
+/// Rotate a cycle so it starts with its first name in order, so the same cycle always reads the same.
+fn normalize(cycle: &[String]) -> Vec<String> {
+  let start = cycle
+    .iter()
+    .enumerate()
+    .min_by(|a, b| a.1.cmp(b.1))
+    .map_or(0, |(i, _)| i);
+  cycle[start..].iter().chain(cycle[..start].iter()).cloned().collect()
+}
+
+fn sorted<T>(map: &IndexMap<String, T>) -> Vec<(&String, &T)> {
+  let mut entries: Vec<_> = map.iter().collect();
+  entries.sort_by(|a, b| a.0.cmp(b.0));
+  entries
+}
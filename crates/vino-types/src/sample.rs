@@ -0,0 +1,441 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use serde_json::{Map, Number, Value};
+
+use crate::compat::integer_range;
+use crate::datetime::Datetime;
+use crate::duration::Duration;
+use crate::maps::{MapWrapper, StructMap, TypeMap};
+use crate::signatures::{ComponentSignature, Constraints, StructSignature, TypeSignature};
+use crate::uuid::Uuid;
+use crate::validate::ref_name;
+
+/// How many references deep values are generated before lists, maps and optional values are left empty.
+const MAX_DEPTH: usize = 8;
+
+/// The most items, bytes or characters a sample is required to have. Longer fixed lengths are an error
+/// rather than an allocation of whatever size the signature asks for.
+const MAX_LENGTH: u64 = 4096;
+
+/// Words random strings are made of.
+const WORDS: &[&str] = &[
+  "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliet", "kilo", "lima",
+];
+
+/// A signature no valid value could be generated for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleError {
+  /// The location of the value, e.g. `$.users[0].role` or the port name.
+  pub path: String,
+  /// Why no value could be generated.
+  pub message: String,
+}
+
+impl SampleError {
+  fn new<T: AsRef<str>>(path: &str, message: T) -> Self {
+    Self {
+      path: path.to_owned(),
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for SampleError {}
+impl Display for SampleError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.path, self.message)
+  }
+}
+
+/// Generates example values that are valid for a [TypeSignature].
+///
+/// A [Sampler::minimal] sampler produces the smallest valid value: zeroes, empty strings and
+/// collections, the first enum value, and no optional fields. A [Sampler::random] sampler produces
+/// varied values that are the same for the same seed.
+#[derive(Debug, Clone)]
+#[must_use]
+// A copy would silently repeat the values of the sampler it was copied from.
+#[allow(missing_copy_implementations)]
+pub struct Sampler {
+  state: Option<u64>,
+}
+
+impl Sampler {
+  /// Create a [Sampler] that generates the smallest valid values.
+  pub fn minimal() -> Self {
+    Self { state: None }
+  }
+
+  /// Create a [Sampler] that generates random values, deterministically for a given seed.
+  pub fn random(seed: u64) -> Self {
+    Self { state: Some(seed) }
+  }
+
+  /// Generate a value for `sig`, resolving references in the passed [StructMap].
+  pub fn sample(&mut self, sig: &TypeSignature, types: &StructMap) -> Result<Value, SampleError> {
+    self.value(sig, types, "$", 0)
+  }
+
+  /// Returns true with the given odds in a random sampler and never in a minimal one.
+  fn chance(&mut self, percent: u64) -> bool {
+    self.below(100).is_some_and(|n| n < percent)
+  }
+
+  /// A number in `0..n` from a random sampler.
+  fn below(&mut self, n: u64) -> Option<u64> {
+    let state = self.state.as_mut()?;
+    // SplitMix64, so that samples are stable across platforms and releases.
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    Some((z ^ (z >> 31)) % n)
+  }
+
+  /// Pick an index into a collection of `len` items.
+  fn pick(&mut self, len: usize) -> usize {
+    self.below(len as u64).map_or(0, |n| n as usize)
+  }
+
+  /// Pick how many items a list or map gets.
+  fn count(&mut self, depth: usize) -> usize {
+    if depth >= MAX_DEPTH {
+      0
+    } else {
+      self.pick(4)
+    }
+  }
+
+  fn value(&mut self, sig: &TypeSignature, types: &StructMap, path: &str, depth: usize) -> Result<Value, SampleError> {
+    let value = match sig {
+      TypeSignature::Raw | TypeSignature::Value => Value::Null,
+      TypeSignature::Internal(t) => self.value(&t.value_type(), types, path, depth)?,
+      TypeSignature::Struct | TypeSignature::Link { .. } => Value::Object(Map::new()),
+      TypeSignature::I8 | TypeSignature::I16 | TypeSignature::I32 | TypeSignature::I64 => {
+        Value::from(self.below(201).map_or(0, |n| n as i64 - 100))
+      }
+      TypeSignature::U8 | TypeSignature::U16 | TypeSignature::U32 | TypeSignature::U64 => {
+        Value::from(self.below(101).unwrap_or(0))
+      }
+      TypeSignature::F32 | TypeSignature::F64 => Value::from(self.below(10_000).map_or(0.0, |n| n as f64 / 100.0)),
+      TypeSignature::Bool => Value::Bool(self.chance(50)),
+      TypeSignature::String => Value::String(
+        self
+          .below(1000)
+          .map_or_else(String::new, |n| format!("{}-{}", WORDS[n as usize % WORDS.len()], n)),
+      ),
+      TypeSignature::Datetime => {
+        // Whole seconds between 2000 and 2030.
+        let seconds = self.below(946_684_800).map_or(0, |n| 946_684_800 + n as i64);
+        Value::String(Datetime::from_unix(seconds, 0).to_string())
+      }
+      TypeSignature::Bytes => {
+        let len = self.count(depth);
+        self.bytes(len)
+      }
+      TypeSignature::Uuid => {
+        let uuid = match self.state {
+          Some(_) => Uuid::from_random_bytes([(); 16].map(|_| self.below(256).unwrap_or(0) as u8)),
+          None => Uuid::NIL,
+        };
+        Value::String(uuid.to_string())
+      }
+      TypeSignature::Duration => Value::String(Duration::new(self.below(86_400).unwrap_or(0), 0).to_string()),
+      TypeSignature::Decimal => Value::String(
+        self
+          .below(100_000)
+          .map_or_else(|| "0".to_owned(), |n| format!("{}.{:02}", n / 100, n % 100)),
+      ),
+      TypeSignature::Url => Value::String(self.below(1000).map_or_else(
+        || "https://example.com/".to_owned(),
+        |n| format!("https://example.com/{}-{}", WORDS[n as usize % WORDS.len()], n),
+      )),
+      TypeSignature::Enum { values } if values.is_empty() => {
+        return Err(SampleError::new(path, "enum has no values"));
+      }
+      TypeSignature::Enum { values } => Value::String(values[self.pick(values.len())].clone()),
+      TypeSignature::Optional { option } => {
+        if depth < MAX_DEPTH && self.chance(50) {
+          self.value(option, types, path, depth)?
+        } else {
+          Value::Null
+        }
+      }
+      TypeSignature::List { element } => {
+        let len = self.count(depth);
+        self.list(element, len, types, path, depth)?
+      }
+      TypeSignature::Array { element, length } => {
+        self.list(element, sample_length(*length, path)?, types, path, depth)?
+      }
+      TypeSignature::Tuple { elements } => Value::Array(
+        elements
+          .iter()
+          .enumerate()
+          .map(|(i, element)| self.value(element, types, &format!("{}[{}]", path, i), depth))
+          .collect::<Result<_, _>>()?,
+      ),
+      TypeSignature::Map { key, value } => {
+        let len = self.count(depth);
+        self.map(key, value, len, types, path, depth)?
+      }
+      TypeSignature::Constrained { base, constraints } => self.constrained(base, constraints, types, path, depth)?,
+      TypeSignature::Ref { reference } => {
+        let def = resolve(reference, types, path)?;
+        self.structure(def, types, path, depth + 1)?
+      }
+      TypeSignature::Union { variants, .. } if variants.is_empty() => {
+        return Err(SampleError::new(path, "union has no variants"));
+      }
+      TypeSignature::Union { tag, variants } => {
+        let variant = &variants[self.pick(variants.len())];
+        let mut value = self.structure(resolve(variant, types, path)?, types, path, depth + 1)?;
+        if let Value::Object(map) = &mut value {
+          map.insert(tag.clone(), Value::String(ref_name(variant).to_owned()));
+        }
+        value
+      }
+    };
+    Ok(value)
+  }
+
+  fn bytes(&mut self, len: usize) -> Value {
+    Value::from((0..len).map(|_| self.below(256).unwrap_or(0) as u8).collect::<Vec<_>>())
+  }
+
+  fn list(
+    &mut self,
+    element: &TypeSignature,
+    len: usize,
+    types: &StructMap,
+    path: &str,
+    depth: usize,
+  ) -> Result<Value, SampleError> {
+    Ok(Value::Array(
+      (0..len)
+        .map(|i| self.value(element, types, &format!("{}[{}]", path, i), depth))
+        .collect::<Result<_, _>>()?,
+    ))
+  }
+
+  /// A map of up to `len` entries; fewer when generated keys repeat.
+  fn map(
+    &mut self,
+    key: &TypeSignature,
+    value: &TypeSignature,
+    len: usize,
+    types: &StructMap,
+    path: &str,
+    depth: usize,
+  ) -> Result<Value, SampleError> {
+    let mut map = Map::new();
+    for _ in 0..len {
+      let key = match self.value(key, types, path, depth)? {
+        Value::String(s) => s,
+        other => other.to_string(),
+      };
+      let entry = self.value(value, types, &format!("{}[{}]", path, key), depth)?;
+      map.insert(key, entry);
+    }
+    Ok(Value::Object(map))
+  }
+
+  /// A value of the base type brought within the constraints: collections get a length in bounds,
+  /// numbers are clamped and strings are padded or cut to length and checked against the pattern.
+  fn constrained(
+    &mut self,
+    base: &TypeSignature,
+    constraints: &Constraints,
+    types: &StructMap,
+    path: &str,
+    depth: usize,
+  ) -> Result<Value, SampleError> {
+    let (min, max) = length_bounds(constraints, path)?;
+    let value = match base {
+      TypeSignature::List { element } => {
+        let len = self.count(depth).clamp(min, max);
+        self.list(element, len, types, path, depth)?
+      }
+      TypeSignature::Map { key, value } => {
+        let len = self.count(depth).clamp(min, max);
+        self.map(key, value, len, types, path, depth)?
+      }
+      TypeSignature::Bytes => {
+        let len = self.count(depth).clamp(min, max);
+        self.bytes(len)
+      }
+      _ => self.value(base, types, path, depth)?,
+    };
+    let value = match (scalar(base), value) {
+      (_, Value::Number(n)) => bound_number(scalar(base), &n, constraints, path)?,
+      (TypeSignature::String, Value::String(s)) => Value::String(self.bound_string(s, constraints, path)?),
+      (_, value) => value,
+    };
+    // Whatever could not be brought within the constraints, like the length of a tuple, is an error.
+    constraints
+      .check_json(&value)
+      .map_err(|message| SampleError::new(path, message))?;
+    Ok(value)
+  }
+
+  fn bound_string(&mut self, sampled: String, constraints: &Constraints, path: &str) -> Result<String, SampleError> {
+    let (min, max) = length_bounds(constraints, path)?;
+    let fit = |s: String| -> String {
+      let len = s.chars().count();
+      if len < min {
+        s + &"x".repeat(min - len)
+      } else {
+        s.chars().take(max).collect()
+      }
+    };
+    let pattern = match &constraints.pattern {
+      Some(pattern) => pattern,
+      None => return Ok(fit(sampled)),
+    };
+    let offset = self.pick(WORDS.len());
+    std::iter::once(sampled)
+      .chain((0..WORDS.len()).map(|i| WORDS[(i + offset) % WORDS.len()].to_owned()))
+      .map(fit)
+      .find(|s| pattern.is_match(s))
+      .ok_or_else(|| SampleError::new(path, format!("no sample string matches the pattern '{}'", pattern)))
+  }
+
+  fn structure(
+    &mut self,
+    def: &StructSignature,
+    types: &StructMap,
+    path: &str,
+    depth: usize,
+  ) -> Result<Value, SampleError> {
+    if depth > MAX_DEPTH {
+      return Err(SampleError::new(
+        path,
+        format!("'{}' nests too deeply to build a value for", def.name),
+      ));
+    }
+    let mut map = Map::new();
+    for (field, value) in self.entries(&def.fields, types, Some(path), depth)? {
+      map.insert(field.to_owned(), value);
+    }
+    Ok(Value::Object(map))
+  }
+
+  /// Values for the entries of a [TypeMap]: struct fields under `path`, or ports when there is no path.
+  ///
+  /// A minimal sampler uses declared defaults and leaves optional entries out. A random sampler fills
+  /// in some of both. Ports with a default may be left out, struct fields with one may not.
+  fn entries<'a>(
+    &mut self,
+    map: &'a TypeMap,
+    types: &StructMap,
+    path: Option<&str>,
+    depth: usize,
+  ) -> Result<Vec<(&'a str, Value)>, SampleError> {
+    let mut entries = Vec::new();
+    for (name, sig) in map.ordered() {
+      let entry_path = path.map_or_else(|| name.clone(), |path| format!("{}.{}", path, name));
+      let default = map.metadata(name).and_then(|m| m.default.as_ref());
+      let value = match (sig, default) {
+        (_, Some(default)) if self.state.is_none() => Some(default.clone()),
+        // A chosen optional entry gets a value; leaving it out already covers null.
+        (TypeSignature::Optional { option }, _) if depth < MAX_DEPTH && self.chance(50) => {
+          Some(self.value(option, types, &entry_path, depth)?)
+        }
+        (TypeSignature::Optional { .. }, _) => None,
+        (_, Some(_)) if self.chance(50) => Some(self.value(sig, types, &entry_path, depth)?),
+        (_, Some(default)) => path.map(|_| default.clone()),
+        (_, None) => Some(self.value(sig, types, &entry_path, depth)?),
+      };
+      if let Some(value) = value {
+        entries.push((name.as_str(), value));
+      }
+    }
+    Ok(entries)
+  }
+}
+
+/// A length a sample must have, as long as it is at most [MAX_LENGTH].
+fn sample_length(length: u64, path: &str) -> Result<usize, SampleError> {
+  if length > MAX_LENGTH {
+    return Err(SampleError::new(
+      path,
+      format!(
+        "a length of {} is more than the {} a sample can have",
+        length, MAX_LENGTH
+      ),
+    ));
+  }
+  Ok(length as usize)
+}
+
+/// The constraints' length bounds, with a missing or larger than addressable maximum as [usize::MAX].
+fn length_bounds(constraints: &Constraints, path: &str) -> Result<(usize, usize), SampleError> {
+  let min = sample_length(constraints.min_length.unwrap_or(0), path)?;
+  let max = constraints
+    .max_length
+    .map_or(usize::MAX, |n| usize::try_from(n).unwrap_or(usize::MAX));
+  Ok((min, max.max(min)))
+}
+
+/// The type whose values a constraint applies to, behind any optional or constrained wrappers.
+fn scalar(sig: &TypeSignature) -> &TypeSignature {
+  match sig {
+    TypeSignature::Optional { option } => scalar(option),
+    TypeSignature::Constrained { base, .. } => scalar(base),
+    _ => sig,
+  }
+}
+
+/// Clamp a number into the constraints' bounds, keeping integers whole and within their type's range.
+fn bound_number(sig: &TypeSignature, n: &Number, constraints: &Constraints, path: &str) -> Result<Value, SampleError> {
+  let n = n.as_f64().unwrap_or_default();
+  let mut min = constraints.minimum.unwrap_or(f64::NEG_INFINITY);
+  let mut max = constraints.maximum.unwrap_or(f64::INFINITY);
+  let range = integer_range(sig);
+  if let Some((lo, hi)) = range {
+    min = min.ceil().max(lo as f64);
+    max = max.floor().min(hi as f64);
+  }
+  if min > max {
+    return Err(SampleError::new(path, format!("no {} is within the bounds", sig)));
+  }
+  let n = n.clamp(min, max);
+  Ok(match range {
+    Some(_) if n < 0.0 => Value::from(n as i64),
+    Some(_) => Value::from(n as u64),
+    None => Value::from(n),
+  })
+}
+
+fn resolve<'a>(reference: &str, types: &'a StructMap, path: &str) -> Result<&'a StructSignature, SampleError> {
+  types
+    .resolve(reference)
+    .ok_or_else(|| SampleError::new(path, format!("unresolved reference '{}'", reference)))
+}
+
+impl TypeSignature {
+  /// Generate the smallest valid value for this signature. See [Sampler].
+  pub fn sample(&self, types: &StructMap) -> Result<Value, SampleError> {
+    Sampler::minimal().sample(self, types)
+  }
+}
+
+impl ComponentSignature {
+  /// Generate a [vino_transport::TransportMap] that is a valid invocation of the component.
+  ///
+  /// Every required input gets a value. A minimal [Sampler] gives defaulted inputs their default and
+  /// leaves optional ones out, a random one generates values for some of both.
+  #[cfg(feature = "transport")]
+  pub fn sample_inputs(
+    &self,
+    sampler: &mut Sampler,
+    types: &StructMap,
+  ) -> Result<vino_transport::TransportMap, SampleError> {
+    let mut payload = vino_transport::TransportMap::new();
+    for (port, value) in sampler.entries(&self.inputs, types, None, 0)? {
+      payload.insert(port, vino_transport::MessageTransport::success(&value));
+    }
+    Ok(payload)
+  }
+}
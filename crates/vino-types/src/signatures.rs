@@ -1,9 +1,16 @@
 use std::error::Error;
+use std::fmt::Display;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::maps::{ComponentMap, MapWrapper, StructMap, TypeMap};
+use crate::cardinality::Cardinality;
+use crate::maps::{ComponentMap, MapWrapper, SchematicMap, StructMap, TypeMap};
+use crate::pattern::Pattern;
+
+/// The type expression parser.
+pub(crate) mod parse;
+
 /// The signature of a Vino component, including its input and output types.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[must_use]
@@ -14,6 +21,15 @@ pub struct ComponentSignature {
   pub inputs: TypeMap,
   /// The component's outputs.
   pub outputs: TypeMap,
+  /// The configuration keys the component accepts.
+  #[serde(default, skip_serializing_if = "TypeMap::is_empty")]
+  pub config: TypeMap,
+  /// A description of the component.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// Whether the component is deprecated.
+  #[serde(default, skip_serializing_if = "is_false")]
+  pub deprecated: bool,
 }
 
 impl ComponentSignature {
@@ -24,6 +40,62 @@ impl ComponentSignature {
       ..Default::default()
     }
   }
+
+  /// The component's `config` schema as a [StructSignature], the type of its [InternalType::Config] inputs.
+  pub fn config_struct(&self) -> StructSignature {
+    StructSignature {
+      name: format!("{}.config", self.name),
+      fields: self.config.clone(),
+      ..Default::default()
+    }
+  }
+
+  /// Insert the default value of every input that is missing from the [vino_transport::TransportMap].
+  #[cfg(feature = "transport")]
+  pub fn apply_defaults(&self, payload: &mut vino_transport::TransportMap) {
+    for port in self.inputs.inner().keys() {
+      if payload.contains(port) {
+        continue;
+      }
+      if let Some(default) = self.inputs.metadata(port).and_then(|m| m.default.as_ref()) {
+        payload.insert(port, vino_transport::MessageTransport::success(default));
+      }
+    }
+  }
+
+  /// Fill every input of an [InternalType] with the value the [vino_transport::Invocation] provides for
+  /// it, replacing anything the caller sent on that port. [InternalType::Seed] and
+  /// [InternalType::Timestamp] inputs are removed when the invocation has no inherent data, so validation
+  /// reports them missing. [InternalType::ComponentInput] inputs are left as they are.
+  #[cfg(feature = "invocation")]
+  pub fn apply_internal(&self, invocation: &mut vino_transport::Invocation) {
+    use vino_transport::MessageTransport;
+
+    let config = invocation.payload.get_config().clone().unwrap_or_default();
+    for (port, sig) in self.inputs.inner() {
+      let value = match sig {
+        TypeSignature::Internal(InternalType::Seed) => invocation.seed().map(|seed| MessageTransport::success(&seed)),
+        TypeSignature::Internal(InternalType::Timestamp) => invocation.timestamp().map(|millis| {
+          let millis = i64::try_from(millis).unwrap_or(i64::MAX);
+          MessageTransport::success(&crate::Datetime::from_unix_millis(millis))
+        }),
+        TypeSignature::Internal(InternalType::Origin) => Some(MessageTransport::success(&invocation.origin_url())),
+        TypeSignature::Internal(InternalType::TxId) => {
+          Some(MessageTransport::success(&crate::Uuid::from(invocation.tx_id)))
+        }
+        TypeSignature::Internal(InternalType::Config) => Some(MessageTransport::success(&config)),
+        _ => continue,
+      };
+      match value {
+        Some(value) => {
+          invocation.payload.insert(port, value);
+        }
+        None => {
+          invocation.payload.consume_raw(port).ok();
+        }
+      }
+    }
+  }
 }
 
 /// Signature for Providers.
@@ -37,6 +109,9 @@ pub struct ProviderSignature {
   pub types: StructMap,
   /// A list of [ComponentSignature]s the provider hosts.
   pub components: ComponentMap,
+  /// The configuration keys the provider accepts.
+  #[serde(default, skip_serializing_if = "TypeMap::is_empty")]
+  pub config: TypeMap,
 }
 
 impl ProviderSignature {
@@ -63,14 +138,275 @@ pub struct StructSignature {
   pub name: String,
   /// The fields in this struct.
   pub fields: TypeMap,
+  /// A description of the struct.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// Whether the struct is deprecated.
+  #[serde(default, skip_serializing_if = "is_false")]
+  pub deprecated: bool,
+}
+
+/// Documentation and a default value for a component port or struct field.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[must_use]
+pub struct Metadata {
+  /// A description of the port or field.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// The value used when the port or field is not provided.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub default: Option<serde_json::Value>,
+  /// Whether the port or field is deprecated.
+  #[serde(default, skip_serializing_if = "is_false")]
+  pub deprecated: bool,
+  /// How many values the port carries. Ignored for struct fields.
+  #[serde(default, skip_serializing_if = "Cardinality::is_single")]
+  pub cardinality: Cardinality,
+}
+
+impl Metadata {
+  /// Create empty [Metadata].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the description.
+  pub fn with_description<T: AsRef<str>>(mut self, description: T) -> Self {
+    self.description = Some(description.as_ref().to_owned());
+    self
+  }
+
+  /// Set the default value.
+  pub fn with_default(mut self, default: serde_json::Value) -> Self {
+    self.default = Some(default);
+    self
+  }
+
+  /// Mark as deprecated.
+  pub fn with_deprecated(mut self, deprecated: bool) -> Self {
+    self.deprecated = deprecated;
+    self
+  }
+
+  /// Set the [Cardinality].
+  pub fn with_cardinality(mut self, cardinality: Cardinality) -> Self {
+    self.cardinality = cardinality;
+    self
+  }
+
+  /// Returns true if no metadata is set.
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.description.is_none() && self.default.is_none() && !self.deprecated && self.cardinality.is_single()
+  }
+}
+
+/// Limits on the values a [TypeSignature::Constrained] type accepts.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[must_use]
+pub struct Constraints {
+  /// The smallest number allowed. Numbers are compared as `f64`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub minimum: Option<f64>,
+  /// The largest number allowed. Numbers are compared as `f64`.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub maximum: Option<f64>,
+  /// The fewest characters in a string, bytes in a byte array, or entries in a list or map.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub min_length: Option<u64>,
+  /// The most characters in a string, bytes in a byte array, or entries in a list or map.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub max_length: Option<u64>,
+  /// A regular expression strings must match somewhere. Anchor it with `^` and `$` to match whole strings.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub pattern: Option<Pattern>,
+}
+
+impl Constraints {
+  /// Create empty [Constraints].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the smallest number allowed.
+  pub fn with_minimum(mut self, minimum: f64) -> Self {
+    self.minimum = Some(minimum);
+    self
+  }
+
+  /// Set the largest number allowed.
+  pub fn with_maximum(mut self, maximum: f64) -> Self {
+    self.maximum = Some(maximum);
+    self
+  }
+
+  /// Set the smallest length allowed.
+  pub fn with_min_length(mut self, min_length: u64) -> Self {
+    self.min_length = Some(min_length);
+    self
+  }
+
+  /// Set the largest length allowed.
+  pub fn with_max_length(mut self, max_length: u64) -> Self {
+    self.max_length = Some(max_length);
+    self
+  }
+
+  /// Set the pattern strings must match.
+  pub fn with_pattern(mut self, pattern: Pattern) -> Self {
+    self.pattern = Some(pattern);
+    self
+  }
+
+  /// Returns true if no constraint is set.
+  #[must_use]
+  pub fn is_empty(&self) -> bool {
+    self.minimum.is_none()
+      && self.maximum.is_none()
+      && self.min_length.is_none()
+      && self.max_length.is_none()
+      && self.pattern.is_none()
+  }
+}
+
+/// Renders constraints the way they appear between the parentheses of a type expression, e.g. `min: 0, max: 1`.
+impl Display for Constraints {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut parts = Vec::new();
+    if let Some(minimum) = self.minimum {
+      parts.push(format!("min: {}", minimum));
+    }
+    if let Some(maximum) = self.maximum {
+      parts.push(format!("max: {}", maximum));
+    }
+    if let Some(min_length) = self.min_length {
+      parts.push(format!("min_len: {}", min_length));
+    }
+    if let Some(max_length) = self.max_length {
+      parts.push(format!("max_len: {}", max_length));
+    }
+    if let Some(pattern) = &self.pattern {
+      parts.push(format!("pattern: {}", serde_json::Value::from(pattern.as_str())));
+    }
+    f.write_str(&parts.join(", "))
+  }
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_false(value: &bool) -> bool {
+  !*value
+}
+
+/// A provider that a schematic or network depends on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[must_use]
+pub struct ProviderDependency {
+  /// The namespace the provider is available under.
+  pub namespace: String,
+  /// The version of the provider the flow was built against.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub version: Option<String>,
+}
+
+impl ProviderDependency {
+  /// Create a new [ProviderDependency] on the passed namespace.
+  pub fn new<T: AsRef<str>>(namespace: T) -> Self {
+    Self {
+      namespace: namespace.as_ref().to_owned(),
+      version: None,
+    }
+  }
+
+  /// Set the version of the provider.
+  pub fn with_version<T: AsRef<str>>(mut self, version: T) -> Self {
+    self.version = Some(version.as_ref().to_owned());
+    self
+  }
+}
+
+/// Signature of a schematic, a flow composed of components from other providers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[must_use]
+pub struct SchematicSignature {
+  /// Name of the schematic.
+  pub name: Option<String>,
+  /// The schematic's external inputs.
+  pub inputs: TypeMap,
+  /// The schematic's external outputs.
+  pub outputs: TypeMap,
+  /// A map of type signatures referenced by the inputs and outputs.
+  #[serde(default, skip_serializing_if = "StructMap::is_empty")]
+  pub types: StructMap,
+  /// The providers the schematic's components come from.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub providers: Vec<ProviderDependency>,
+  /// A description of the schematic.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+}
+
+impl SchematicSignature {
+  /// Create a new [SchematicSignature] with the passed name.
+  pub fn new<T: AsRef<str>>(name: T) -> Self {
+    Self {
+      name: Some(name.as_ref().to_owned()),
+      ..Default::default()
+    }
+  }
+
+  /// The [ComponentSignature] the schematic presents to callers, which invoke it like any other component.
+  pub fn as_component(&self) -> ComponentSignature {
+    ComponentSignature {
+      name: self.name.clone().unwrap_or_default(),
+      inputs: self.inputs.clone(),
+      outputs: self.outputs.clone(),
+      config: TypeMap::new(),
+      description: self.description.clone(),
+      deprecated: false,
+    }
+  }
+}
+
+/// Signature of a network, a set of schematics and the providers they share.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[must_use]
+pub struct NetworkSignature {
+  /// Name of the network.
+  pub name: Option<String>,
+  /// The schematics the network exposes.
+  pub schematics: SchematicMap,
+  /// The providers the network's schematics depend on.
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub providers: Vec<ProviderDependency>,
+}
+
+impl NetworkSignature {
+  /// Create a new [NetworkSignature] with the passed name.
+  pub fn new<T: AsRef<str>>(name: T) -> Self {
+    Self {
+      name: Some(name.as_ref().to_owned()),
+      ..Default::default()
+    }
+  }
+
+  #[must_use]
+  /// Get the [SchematicSignature] for the requested schematic.
+  pub fn get_schematic<T: AsRef<str>>(&self, name: T) -> Option<&SchematicSignature> {
+    self.schematics.get(name.as_ref())
+  }
 }
 
 /// An enum representing the types of components that can be hosted.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[must_use]
+#[allow(clippy::large_enum_variant)]
 pub enum HostedType {
   /// A provider.
   Provider(ProviderSignature),
+  /// A schematic.
+  Schematic(SchematicSignature),
+  /// A network.
+  Network(NetworkSignature),
 }
 
 impl HostedType {
@@ -79,6 +415,8 @@ impl HostedType {
   pub fn get_name(&self) -> &Option<String> {
     match self {
       HostedType::Provider(s) => &s.name,
+      HostedType::Schematic(s) => &s.name,
+      HostedType::Network(s) => &s.name,
     }
   }
 }
@@ -117,6 +455,14 @@ pub enum TypeSignature {
   Datetime,
   /// Raw bytes.
   Bytes,
+  /// A universally unique identifier.
+  Uuid,
+  /// A span of time.
+  Duration,
+  /// An arbitrary precision decimal number.
+  Decimal,
+  /// An absolute URL.
+  Url,
   /// Raw value to be processed later.
   Raw,
   /// Any valid value.
@@ -153,51 +499,200 @@ pub enum TypeSignature {
   },
   /// A JSON-like key/value map.
   Struct,
+  /// A fixed number of values, each of its own type.
+  Tuple {
+    /// The types of the values, in order.
+    elements: Vec<TypeSignature>,
+  },
+  /// A list with an exact number of elements.
+  Array {
+    /// The type of the array's elements.
+    element: Box<TypeSignature>,
+    /// The number of elements.
+    length: u64,
+  },
+  /// A value of another type that must also satisfy [Constraints].
+  Constrained {
+    /// The type being constrained.
+    base: Box<TypeSignature>,
+    /// The limits on the value.
+    constraints: Constraints,
+  },
+  /// A closed set of string values.
+  Enum {
+    /// The allowed values.
+    values: Vec<String>,
+  },
+  /// A struct chosen from a set of variants by a tag field holding the variant's type name.
+  Union {
+    /// The name of the field holding the variant's type name.
+    tag: String,
+    /// References to the variants' struct types.
+    variants: Vec<String>,
+  },
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 /// Error returned when attempting to convert an invalid string into a [TypeSignature].
-pub struct ParseError(String);
+pub struct ParseError {
+  input: String,
+  position: usize,
+  message: String,
+}
+
+impl ParseError {
+  pub(crate) fn new<T: AsRef<str>>(input: &str, position: usize, message: T) -> Self {
+    Self {
+      input: input.to_owned(),
+      position,
+      message: message.as_ref().to_owned(),
+    }
+  }
+
+  /// The string that failed to parse.
+  #[must_use]
+  pub fn input(&self) -> &str {
+    &self.input
+  }
+
+  /// The byte offset into the input where parsing failed.
+  #[must_use]
+  pub fn position(&self) -> usize {
+    self.position
+  }
+
+  /// What went wrong, without the input and position.
+  pub(crate) fn message(&self) -> &str {
+    &self.message
+  }
+}
+
 impl Error for ParseError {}
-impl std::fmt::Display for ParseError {
+impl Display for ParseError {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "Could not parse {} into a TypeSignature.", self.0)
+    write!(
+      f,
+      "Could not parse {} into a TypeSignature: {} at position {}.",
+      self.input, self.message, self.position
+    )
   }
 }
 
+/// Parses type expressions such as `string[]`, `{string: i64}`, `u32?`, `ref:User`, `link:provider_id`,
+/// `enum(fast|accurate)`, `union:kind(Circle|Square)`, `(f32, string)`, `f32[3]`, and
+/// `string(min_len: 1, pattern: "^[a-z]+$")`.
 impl FromStr for TypeSignature {
   type Err = ParseError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let t = match s {
-      "i8" => Self::I8,
-      "i16" => Self::I16,
-      "i32" => Self::I32,
-      "i64" => Self::I64,
-      "u8" => Self::U8,
-      "u16" => Self::U16,
-      "u32" => Self::U32,
-      "u64" => Self::U64,
-      "f32" => Self::F32,
-      "f64" => Self::F64,
-      "bool" => Self::Bool,
-      "bytes" => Self::Bytes,
-      "raw" => Self::Raw,
-      "value" => Self::Value,
-      "string" => Self::String,
-      "datetime" => Self::Datetime,
-      _ => return Err(ParseError(s.to_owned())),
-    };
-    Ok(t)
+    parse::Parser::new(s).parse()
+  }
+}
+
+/// Renders the type expression that [TypeSignature::from_str] parses back into an equal [TypeSignature].
+impl Display for TypeSignature {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TypeSignature::I8 => f.write_str("i8"),
+      TypeSignature::I16 => f.write_str("i16"),
+      TypeSignature::I32 => f.write_str("i32"),
+      TypeSignature::I64 => f.write_str("i64"),
+      TypeSignature::U8 => f.write_str("u8"),
+      TypeSignature::U16 => f.write_str("u16"),
+      TypeSignature::U32 => f.write_str("u32"),
+      TypeSignature::U64 => f.write_str("u64"),
+      TypeSignature::F32 => f.write_str("f32"),
+      TypeSignature::F64 => f.write_str("f64"),
+      TypeSignature::Bool => f.write_str("bool"),
+      TypeSignature::String => f.write_str("string"),
+      TypeSignature::Datetime => f.write_str("datetime"),
+      TypeSignature::Bytes => f.write_str("bytes"),
+      TypeSignature::Uuid => f.write_str("uuid"),
+      TypeSignature::Duration => f.write_str("duration"),
+      TypeSignature::Decimal => f.write_str("decimal"),
+      TypeSignature::Url => f.write_str("url"),
+      TypeSignature::Raw => f.write_str("raw"),
+      TypeSignature::Value => f.write_str("value"),
+      TypeSignature::Struct => f.write_str("struct"),
+      TypeSignature::Internal(t) => write!(f, "internal:{}", t),
+      TypeSignature::Ref { reference } => write!(f, "ref:{}", reference),
+      TypeSignature::List { element } => write!(f, "{}[]", element),
+      TypeSignature::Optional { option } => write!(f, "{}?", option),
+      TypeSignature::Map { key, value } => write!(f, "{{{}: {}}}", key, value),
+      TypeSignature::Tuple { elements } => {
+        let elements: Vec<String> = elements.iter().map(ToString::to_string).collect();
+        write!(f, "({})", elements.join(", "))
+      }
+      TypeSignature::Array { element, length } => write!(f, "{}[{}]", element, length),
+      TypeSignature::Constrained { base, constraints } => write!(f, "{}({})", base, constraints),
+      TypeSignature::Link {
+        provider: Some(provider),
+      } => write!(f, "link:{}", provider),
+      TypeSignature::Link { provider: None } => f.write_str("link"),
+      TypeSignature::Enum { values } => {
+        let values: Vec<String> = values.iter().map(|v| quote_value(v)).collect();
+        write!(f, "enum({})", values.join("|"))
+      }
+      TypeSignature::Union { tag, variants } => write!(f, "union:{}({})", tag, variants.join("|")),
+    }
   }
 }
 
-/// Internal types for use within the Vino runtime
+/// Enum values are written bare when they are simple words and quoted otherwise, with quotes and backslashes
+/// escaped.
+fn quote_value(value: &str) -> String {
+  let bare = !value.is_empty()
+    && value
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+  if bare {
+    value.to_owned()
+  } else {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+  }
+}
+
+/// Internal types for use within the Vino runtime.
+///
+/// Inputs of these types are provided by the runtime rather than the caller, so a component can declare
+/// the context it needs as ports, e.g. `seed: internal:seed`.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Copy)]
 #[serde(tag = "id")]
 pub enum InternalType {
   /// Represents a complete set of component inputs
   #[serde(rename = "__input__")]
   ComponentInput,
+  /// The seed of the invocation's inherent data.
+  #[serde(rename = "__seed__")]
+  Seed,
+  /// The timestamp of the invocation's inherent data, as a [TypeSignature::Datetime].
+  #[serde(rename = "__timestamp__")]
+  Timestamp,
+  /// The URL of the entity that originated the invocation.
+  #[serde(rename = "__origin__")]
+  Origin,
+  /// The id of the transaction the invocation belongs to.
+  #[serde(rename = "__tx_id__")]
+  TxId,
+  /// The component's configuration, typed by its `config` schema. See [ComponentSignature::config_struct].
+  #[serde(rename = "__config__")]
+  Config,
+}
+
+impl InternalType {
+  /// The type of the values the runtime provides for this internal type.
+  pub fn value_type(&self) -> TypeSignature {
+    match self {
+      InternalType::ComponentInput => TypeSignature::Value,
+      InternalType::Seed => TypeSignature::U64,
+      InternalType::Timestamp => TypeSignature::Datetime,
+      InternalType::Origin => TypeSignature::String,
+      InternalType::TxId => TypeSignature::Uuid,
+      InternalType::Config => TypeSignature::Map {
+        key: Box::new(TypeSignature::String),
+        value: Box::new(TypeSignature::Value),
+      },
+    }
+  }
 }
 
 impl FromStr for InternalType {
@@ -206,8 +701,26 @@ impl FromStr for InternalType {
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     let t = match s {
       "component_input" => Self::ComponentInput,
-      _ => return Err(ParseError(s.to_owned())),
+      "seed" => Self::Seed,
+      "timestamp" => Self::Timestamp,
+      "origin" => Self::Origin,
+      "tx_id" => Self::TxId,
+      "config" => Self::Config,
+      _ => return Err(ParseError::new(s, 0, "unknown internal type")),
     };
     Ok(t)
   }
 }
+
+impl Display for InternalType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      InternalType::ComponentInput => f.write_str("component_input"),
+      InternalType::Seed => f.write_str("seed"),
+      InternalType::Timestamp => f.write_str("timestamp"),
+      InternalType::Origin => f.write_str("origin"),
+      InternalType::TxId => f.write_str("tx_id"),
+      InternalType::Config => f.write_str("config"),
+    }
+  }
+}
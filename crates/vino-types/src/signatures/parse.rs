@@ -0,0 +1,369 @@
+use std::str::FromStr;
+
+use super::{Constraints, InternalType, ParseError, TypeSignature};
+use crate::pattern::{Pattern, PatternError};
+
+/// Characters that end a `ref:` or `link:` argument.
+const DELIMITERS: &[char] = &['[', ']', '?', '{', '}', ',', ':', '(', ')', '|'];
+
+/// A recursive descent parser for type expressions like `{string: u32?}[]`.
+pub(crate) struct Parser<'a> {
+  src: &'a str,
+  pos: usize,
+  bare_refs: bool,
+}
+
+impl<'a> Parser<'a> {
+  pub(super) fn new(src: &'a str) -> Self {
+    Self::at(src, 0)
+  }
+
+  /// Create a parser for a type expression that starts at byte offset `pos` of a larger input.
+  pub(crate) fn at(src: &'a str, pos: usize) -> Self {
+    Self {
+      src,
+      pos,
+      bare_refs: false,
+    }
+  }
+
+  /// Read names that are not built-in types as references, so `User[]` means `ref:User[]`.
+  pub(crate) fn with_bare_refs(mut self) -> Self {
+    self.bare_refs = true;
+    self
+  }
+
+  /// The byte offset parsing stopped at.
+  pub(crate) fn position(&self) -> usize {
+    self.pos
+  }
+
+  /// Parse the entire input as a single type expression.
+  pub(super) fn parse(mut self) -> Result<TypeSignature, ParseError> {
+    let ty = self.parse_type()?;
+    self.skip_whitespace();
+    self
+      .peek()
+      .map_or(Ok(ty), |c| Err(self.error(format!("unexpected '{}' after type", c))))
+  }
+
+  /// Parse one type expression, leaving the parser after it and any whitespace that follows.
+  pub(crate) fn parse_type(&mut self) -> Result<TypeSignature, ParseError> {
+    self.skip_whitespace();
+    let mut ty = match self.peek() {
+      Some('{') => self.parse_map()?,
+      Some('(') => self.parse_tuple()?,
+      Some(_) => self.parse_named()?,
+      None => return Err(self.error("expected a type")),
+    };
+    loop {
+      self.skip_whitespace();
+      if self.eat("[]") {
+        ty = TypeSignature::List { element: Box::new(ty) };
+      } else if self.peek() == Some('[') {
+        let start = self.pos;
+        self.pos += 1;
+        let length = self
+          .take_while(|c| c.is_ascii_digit())
+          .parse()
+          .map_err(|_| ParseError::new(self.src, start, "expected '[]' or an array length"))?;
+        self.expect("]")?;
+        ty = TypeSignature::Array {
+          element: Box::new(ty),
+          length,
+        };
+      } else if self.eat("?") {
+        ty = TypeSignature::Optional { option: Box::new(ty) };
+      } else if self.peek() == Some('(') {
+        ty = TypeSignature::Constrained {
+          base: Box::new(ty),
+          constraints: self.parse_constraints()?,
+        };
+      } else {
+        break;
+      }
+    }
+    Ok(ty)
+  }
+
+  fn parse_map(&mut self) -> Result<TypeSignature, ParseError> {
+    self.expect("{")?;
+    let key = self.parse_type()?;
+    self.skip_whitespace();
+    self.expect(":")?;
+    let value = self.parse_type()?;
+    self.skip_whitespace();
+    self.expect("}")?;
+    Ok(TypeSignature::Map {
+      key: Box::new(key),
+      value: Box::new(value),
+    })
+  }
+
+  /// Parse a parenthesized, comma-separated list of types like `(f32, string)`.
+  fn parse_tuple(&mut self) -> Result<TypeSignature, ParseError> {
+    self.expect("(")?;
+    let mut elements = Vec::new();
+    loop {
+      self.skip_whitespace();
+      if self.eat(")") {
+        break;
+      }
+      elements.push(self.parse_type()?);
+      self.skip_whitespace();
+      if !self.eat(",") {
+        self.expect(")")?;
+        break;
+      }
+    }
+    Ok(TypeSignature::Tuple { elements })
+  }
+
+  /// Parse a parenthesized list of constraints like `(min: 0, max: 1)`.
+  fn parse_constraints(&mut self) -> Result<Constraints, ParseError> {
+    self.expect("(")?;
+    let mut constraints = Constraints::new();
+    loop {
+      self.skip_whitespace();
+      let start = self.pos;
+      let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+      let duplicate = match name {
+        "min" => constraints.minimum.replace(self.parse_bound()?).is_some(),
+        "max" => constraints.maximum.replace(self.parse_bound()?).is_some(),
+        "min_len" => constraints
+          .min_length
+          .replace(self.parse_value("length", |c| c.is_ascii_digit())?)
+          .is_some(),
+        "max_len" => constraints
+          .max_length
+          .replace(self.parse_value("length", |c| c.is_ascii_digit())?)
+          .is_some(),
+        "pattern" => constraints.pattern.replace(self.parse_pattern()?).is_some(),
+        "" => return Err(self.error("expected a constraint")),
+        _ => {
+          return Err(ParseError::new(
+            self.src,
+            start,
+            format!("unknown constraint '{}'", name),
+          ))
+        }
+      };
+      if duplicate {
+        return Err(ParseError::new(
+          self.src,
+          start,
+          format!("duplicate constraint '{}'", name),
+        ));
+      }
+      self.skip_whitespace();
+      if !self.eat(",") {
+        break;
+      }
+    }
+    self.skip_whitespace();
+    self.expect(")")?;
+    Ok(constraints)
+  }
+
+  /// Parse the `: value` of a `min` or `max` constraint.
+  fn parse_bound(&mut self) -> Result<f64, ParseError> {
+    self.parse_value("number", |c| {
+      c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')
+    })
+  }
+
+  /// Parse the `: "regex"` of a `pattern` constraint.
+  fn parse_pattern(&mut self) -> Result<Pattern, ParseError> {
+    self.skip_whitespace();
+    self.expect(":")?;
+    self.skip_whitespace();
+    let start = self.pos;
+    let mut strings = serde_json::Deserializer::from_str(&self.src[start..]).into_iter::<String>();
+    match strings.next() {
+      Some(Ok(pattern)) => {
+        let pattern = pattern.parse().map_err(|e: PatternError| self.error(e.to_string()))?;
+        self.pos += strings.byte_offset();
+        Ok(pattern)
+      }
+      _ => Err(self.error("expected a quoted pattern")),
+    }
+  }
+
+  /// Parse the `: value` of a constraint, made of the characters `predicate` accepts.
+  fn parse_value<T: FromStr>(&mut self, what: &str, predicate: impl Fn(char) -> bool) -> Result<T, ParseError> {
+    self.skip_whitespace();
+    self.expect(":")?;
+    self.skip_whitespace();
+    let start = self.pos;
+    let text = self.take_while(predicate);
+    text
+      .parse()
+      .map_err(|_| ParseError::new(self.src, start, format!("expected a {}", what)))
+  }
+
+  fn parse_named(&mut self) -> Result<TypeSignature, ParseError> {
+    let start = self.pos;
+    let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+    if name.is_empty() {
+      return Err(self.error(format!("unexpected '{}'", self.peek().unwrap_or_default())));
+    }
+    let t = match name {
+      "i8" => TypeSignature::I8,
+      "i16" => TypeSignature::I16,
+      "i32" => TypeSignature::I32,
+      "i64" => TypeSignature::I64,
+      "u8" => TypeSignature::U8,
+      "u16" => TypeSignature::U16,
+      "u32" => TypeSignature::U32,
+      "u64" => TypeSignature::U64,
+      "f32" => TypeSignature::F32,
+      "f64" => TypeSignature::F64,
+      "bool" => TypeSignature::Bool,
+      "bytes" => TypeSignature::Bytes,
+      "raw" => TypeSignature::Raw,
+      "value" => TypeSignature::Value,
+      "string" => TypeSignature::String,
+      "datetime" => TypeSignature::Datetime,
+      "uuid" => TypeSignature::Uuid,
+      "duration" => TypeSignature::Duration,
+      "decimal" => TypeSignature::Decimal,
+      "url" => TypeSignature::Url,
+      "struct" => TypeSignature::Struct,
+      "ref" => TypeSignature::Ref {
+        reference: self
+          .parse_argument()?
+          .ok_or_else(|| self.error("expected a reference after 'ref:'"))?,
+      },
+      "link" => TypeSignature::Link {
+        provider: self.parse_argument()?,
+      },
+      "enum" => TypeSignature::Enum {
+        values: self.parse_alternatives("value")?,
+      },
+      "union" => TypeSignature::Union {
+        tag: self
+          .parse_argument()?
+          .ok_or_else(|| self.error("expected a tag field after 'union:'"))?,
+        variants: self.parse_alternatives("variant")?,
+      },
+      "internal" => {
+        let arg_start = self.pos;
+        let arg = self
+          .parse_argument()?
+          .ok_or_else(|| self.error("expected an internal type after 'internal:'"))?;
+        TypeSignature::Internal(
+          InternalType::from_str(&arg)
+            .map_err(|_| ParseError::new(self.src, arg_start + 1, format!("unknown internal type '{}'", arg)))?,
+        )
+      }
+      _ if self.bare_refs => TypeSignature::Ref {
+        reference: name.to_owned(),
+      },
+      _ => return Err(ParseError::new(self.src, start, format!("unknown type '{}'", name))),
+    };
+    Ok(t)
+  }
+
+  /// Parse the `:argument` that may follow `ref`, `link`, or `internal`.
+  ///
+  /// A colon followed by whitespace is not an argument so that `{link: string}` still reads as a map.
+  fn parse_argument(&mut self) -> Result<Option<String>, ParseError> {
+    let rest = &self.src[self.pos..];
+    match rest.strip_prefix(':') {
+      Some(after) if after.starts_with(|c: char| !c.is_whitespace() && !DELIMITERS.contains(&c)) => {
+        self.pos += 1;
+        let arg = self.take_while(|c| !c.is_whitespace() && !DELIMITERS.contains(&c));
+        Ok(Some(arg.to_owned()))
+      }
+      Some("") => Err(ParseError::new(
+        self.src,
+        self.pos + 1,
+        "expected an argument after ':'",
+      )),
+      _ => Ok(None),
+    }
+  }
+
+  /// Parse a parenthesized, `|`-separated list like `(fast|"very accurate")`.
+  fn parse_alternatives(&mut self, what: &str) -> Result<Vec<String>, ParseError> {
+    self.expect("(")?;
+    let mut items = Vec::new();
+    loop {
+      self.skip_whitespace();
+      let item = if self.eat("\"") {
+        self.parse_quoted()?
+      } else {
+        let item = self.take_while(|c| !c.is_whitespace() && c != '|' && c != ')');
+        if item.is_empty() {
+          return Err(self.error(format!("expected a {}", what)));
+        }
+        item.to_owned()
+      };
+      items.push(item);
+      self.skip_whitespace();
+      if !self.eat("|") {
+        break;
+      }
+    }
+    self.expect(")")?;
+    Ok(items)
+  }
+
+  /// The rest of a quoted string after its opening quote, where `\"` and `\\` escape a quote and a backslash.
+  fn parse_quoted(&mut self) -> Result<String, ParseError> {
+    let mut item = String::new();
+    let src = self.src;
+    let mut chars = src[self.pos..].chars();
+    while let Some(c) = chars.next() {
+      self.pos += c.len_utf8();
+      match c {
+        '"' => return Ok(item),
+        '\\' => match chars.next() {
+          Some(escaped @ ('"' | '\\')) => {
+            self.pos += 1;
+            item.push(escaped);
+          }
+          _ => return Err(self.error("expected '\"' or '\\' after '\\'")),
+        },
+        c => item.push(c),
+      }
+    }
+    Err(self.error("expected '\"'"))
+  }
+
+  fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+    let rest = &self.src[self.pos..];
+    let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+    self.pos += len;
+    &rest[..len]
+  }
+
+  fn skip_whitespace(&mut self) {
+    self.take_while(char::is_whitespace);
+  }
+
+  fn peek(&self) -> Option<char> {
+    self.src[self.pos..].chars().next()
+  }
+
+  fn eat(&mut self, token: &str) -> bool {
+    if self.src[self.pos..].starts_with(token) {
+      self.pos += token.len();
+      true
+    } else {
+      false
+    }
+  }
+
+  fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+    if self.eat(token) {
+      Ok(())
+    } else {
+      Err(self.error(format!("expected '{}'", token)))
+    }
+  }
+
+  fn error<T: AsRef<str>>(&self, message: T) -> ParseError {
+    ParseError::new(self.src, self.pos, message)
+  }
+}
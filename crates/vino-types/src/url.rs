@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// An absolute URL, the canonical value of a [crate::TypeSignature::Url].
+///
+/// Every format encodes a [Url] as a string in the normalized form the WHATWG URL standard gives it,
+/// with the scheme and host in lowercase, e.g. `https://vino.dev/docs?page=2`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[must_use]
+pub struct Url(url::Url);
+
+impl Url {
+  /// The URL as a string.
+  #[must_use]
+  pub fn as_str(&self) -> &str {
+    self.0.as_str()
+  }
+
+  /// The scheme, in lowercase and without the `:`.
+  #[must_use]
+  pub fn scheme(&self) -> &str {
+    self.0.scheme()
+  }
+
+  /// The host of URLs with an authority, like `vino.dev` in `https://user@vino.dev:8080/`.
+  /// IPv6 addresses keep their brackets.
+  #[must_use]
+  pub fn host(&self) -> Option<&str> {
+    self.0.host_str().filter(|host| !host.is_empty())
+  }
+}
+
+/// A string that is not an absolute URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlError {
+  message: String,
+}
+
+impl UrlError {
+  fn new<T: AsRef<str>>(message: T) -> Self {
+    Self {
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for UrlError {}
+impl Display for UrlError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid URL: {}", self.message)
+  }
+}
+
+impl Display for Url {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl AsRef<str> for Url {
+  fn as_ref(&self) -> &str {
+    self.as_str()
+  }
+}
+
+impl From<url::Url> for Url {
+  fn from(url: url::Url) -> Self {
+    Self(url)
+  }
+}
+
+impl From<Url> for url::Url {
+  fn from(url: Url) -> Self {
+    url.0
+  }
+}
+
+/// Parses absolute URLs as the WHATWG URL standard describes them. Characters that must be percent-encoded
+/// are encoded, and `http`, `https`, `ws`, `wss` and `ftp` URLs must name a host.
+impl FromStr for Url {
+  type Err = UrlError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    url::Url::parse(s)
+      .map(Self)
+      .map_err(|e| UrlError::new(format!("'{}' {}", s, e)))
+  }
+}
+
+impl Serialize for Url {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+impl<'de> Deserialize<'de> for Url {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(UrlVisitor)
+  }
+}
+
+struct UrlVisitor;
+
+impl<'de> Visitor<'de> for UrlVisitor {
+  type Value = Url;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("a URL string")
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    v.parse().map_err(E::custom)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(self)
+  }
+}
@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// A universally unique identifier, the canonical value of a [crate::TypeSignature::Uuid].
+///
+/// Every format encodes a [Uuid] as a lowercase hyphenated string, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+/// 16 bytes of binary data are accepted too when deserializing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[must_use]
+pub struct Uuid(uuid::Uuid);
+
+impl Uuid {
+  /// The all-zero UUID.
+  pub const NIL: Uuid = Uuid(uuid::Uuid::nil());
+
+  /// Create a [Uuid] from its 16 bytes, most significant first.
+  pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+    Self(uuid::Uuid::from_bytes(bytes))
+  }
+
+  /// Create a version 4 [Uuid] from random bytes, overwriting the version and variant bits.
+  pub fn from_random_bytes(bytes: [u8; 16]) -> Self {
+    Self(
+      uuid::Builder::from_bytes(bytes)
+        .set_variant(uuid::Variant::RFC4122)
+        .set_version(uuid::Version::Random)
+        .build(),
+    )
+  }
+
+  /// The 16 bytes of the UUID, most significant first.
+  #[must_use]
+  pub fn as_bytes(&self) -> &[u8; 16] {
+    self.0.as_bytes()
+  }
+
+  /// The version number in the UUID's version field, e.g. 4 for random UUIDs.
+  #[must_use]
+  pub fn version(&self) -> u8 {
+    self.0.as_bytes()[6] >> 4
+  }
+}
+
+/// Bytes that are not exactly 16 long, or a string that is not a UUID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UuidError {
+  message: String,
+}
+
+impl UuidError {
+  fn new<T: AsRef<str>>(message: T) -> Self {
+    Self {
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for UuidError {}
+impl Display for UuidError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "Invalid UUID: {}", self.message)
+  }
+}
+
+impl TryFrom<&[u8]> for Uuid {
+  type Error = UuidError;
+
+  fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+    <[u8; 16]>::try_from(bytes)
+      .map(Self::from_bytes)
+      .map_err(|_| UuidError::new(format!("{} bytes instead of 16", bytes.len())))
+  }
+}
+
+impl From<Uuid> for [u8; 16] {
+  fn from(uuid: Uuid) -> Self {
+    *uuid.0.as_bytes()
+  }
+}
+
+impl From<uuid::Uuid> for Uuid {
+  fn from(uuid: uuid::Uuid) -> Self {
+    Self(uuid)
+  }
+}
+
+impl From<Uuid> for uuid::Uuid {
+  fn from(uuid: Uuid) -> Self {
+    uuid.0
+  }
+}
+
+/// Formats as lowercase hex digits in groups of 8, 4, 4, 4 and 12, separated by hyphens.
+impl Display for Uuid {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    self.0.to_hyphenated_ref().fmt(f)
+  }
+}
+
+/// Parses hyphenated UUIDs in either case, optionally in braces or after `urn:uuid:`, and the 32 hex digits
+/// without hyphens.
+impl FromStr for Uuid {
+  type Err = UuidError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let inner = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')).unwrap_or(s);
+    uuid::Uuid::parse_str(inner)
+      .map(Self)
+      .map_err(|_| UuidError::new(format!("'{}' is not a UUID", s)))
+  }
+}
+
+impl Serialize for Uuid {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for Uuid {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(UuidVisitor)
+  }
+}
+
+struct UuidVisitor;
+
+impl<'de> Visitor<'de> for UuidVisitor {
+  type Value = Uuid;
+
+  fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("a UUID string or 16 bytes")
+  }
+
+  fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+    v.parse().map_err(E::custom)
+  }
+
+  fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+    Uuid::try_from(v).map_err(E::custom)
+  }
+
+  fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+    deserializer.deserialize_any(self)
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+    let mut bytes = Vec::with_capacity(16);
+    while let Some(byte) = seq.next_element::<u8>()? {
+      bytes.push(byte);
+    }
+    Uuid::try_from(bytes.as_slice()).map_err(de::Error::custom)
+  }
+}
@@ -0,0 +1,499 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+
+use serde::Deserialize;
+use serde_value::Value;
+
+use crate::datetime::Datetime;
+use crate::decimal::Decimal;
+use crate::duration::Duration;
+use crate::maps::{MapWrapper, StructMap};
+use crate::signatures::{ComponentSignature, Constraints, StructSignature, TypeSignature};
+use crate::url::Url;
+use crate::uuid::Uuid;
+
+/// The prefix of references that point into a provider's types by JSON pointer, e.g. `#/types/User`.
+pub const TYPES_PREFIX: &str = "#/types/";
+
+/// The type name a reference points to, which is also the tag value of a [TypeSignature::Union] variant.
+pub(crate) fn ref_name(reference: &str) -> &str {
+  reference.strip_prefix(TYPES_PREFIX).unwrap_or(reference)
+}
+
+/// The type under any [TypeSignature::Optional] and [TypeSignature::Constrained] wrappers.
+pub(crate) fn unwrap_optional(sig: &TypeSignature) -> &TypeSignature {
+  match sig {
+    TypeSignature::Optional { option } => unwrap_optional(option),
+    TypeSignature::Constrained { base, .. } => unwrap_optional(base),
+    _ => sig,
+  }
+}
+
+/// A single mismatch between a value and the type it was validated against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+  /// The location of the mismatch, e.g. `input.users[2].name`.
+  pub path: String,
+  /// A description of the mismatch.
+  pub message: String,
+}
+
+impl ValidationError {
+  fn new<T: AsRef<str>>(path: &str, message: T) -> Self {
+    Self {
+      path: path.to_owned(),
+      message: message.as_ref().to_owned(),
+    }
+  }
+}
+
+impl Error for ValidationError {}
+impl Display for ValidationError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}: {}", self.path, self.message)
+  }
+}
+
+impl TypeSignature {
+  /// Check a value against this signature, resolving [TypeSignature::Ref]s in the passed [StructMap].
+  ///
+  /// MessagePack payloads can be checked by deserializing them into a [serde_value::Value] first.
+  /// Returns every mismatch found rather than stopping at the first.
+  pub fn validate(&self, value: &Value, types: &StructMap) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    Validator {
+      types,
+      trust_refs: false,
+    }
+    .check(self, value, "$", &mut errors);
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  /// Check a JSON value against this signature. See [TypeSignature::validate].
+  pub fn validate_json(&self, value: &serde_json::Value, types: &StructMap) -> Result<(), Vec<ValidationError>> {
+    let value = serde_value::to_value(value).map_err(|e| vec![ValidationError::new("$", e.to_string())])?;
+    self.validate(&value, types)
+  }
+
+  /// Check a port or field's default before the types it references are known. References are taken on
+  /// trust and the first mismatch is returned.
+  pub(crate) fn check_default(&self, value: &serde_json::Value) -> Result<(), String> {
+    let value = serde_value::to_value(value).map_err(|e| e.to_string())?;
+    let types = StructMap::new();
+    let mut errors = Vec::new();
+    Validator {
+      types: &types,
+      trust_refs: true,
+    }
+    .check(self, &value, "$", &mut errors);
+    errors.into_iter().next().map_or(Ok(()), |e| Err(e.to_string()))
+  }
+}
+
+impl ComponentSignature {
+  /// Check the ports in a [vino_transport::TransportMap] against this component's inputs.
+  ///
+  /// Flags inputs that are missing (unless optional or defaulted), ports the component does not declare,
+  /// and values that do not match their port's type.
+  #[cfg(feature = "transport")]
+  pub fn validate_inputs(
+    &self,
+    payload: &vino_transport::TransportMap,
+    types: &StructMap,
+  ) -> Result<(), Vec<ValidationError>> {
+    let validator = Validator {
+      types,
+      trust_refs: false,
+    };
+    let mut errors = Vec::new();
+    for (port, sig) in self.inputs.ordered() {
+      match payload.get(port) {
+        Some(msg) => match msg.clone().deserialize::<Value>() {
+          Ok(value) if matches!(sig, TypeSignature::Internal(crate::InternalType::Config)) => {
+            validator.check_struct(&self.config_struct(), &value, port, None, &mut errors);
+          }
+          Ok(value) => validator.check(sig, &value, port, &mut errors),
+          Err(e) => errors.push(ValidationError::new(port, format!("could not read port value: {}", e))),
+        },
+        None if matches!(sig, TypeSignature::Optional { .. }) => {}
+        None if self.inputs.metadata(port).is_some_and(|m| m.default.is_some()) => {}
+        None => errors.push(ValidationError::new(port, "missing input")),
+      }
+    }
+    for (port, _) in payload.iter() {
+      if !self.inputs.contains_key(port) {
+        errors.push(ValidationError::new(port, "unexpected input"));
+      }
+    }
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+}
+
+struct Validator<'a> {
+  types: &'a StructMap,
+  /// Accept any value for references that do not resolve.
+  trust_refs: bool,
+}
+
+impl<'a> Validator<'a> {
+  fn check(&self, sig: &TypeSignature, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let value = unwrap_newtype(value);
+    let valid = match sig {
+      TypeSignature::Raw | TypeSignature::Value => true,
+      TypeSignature::Internal(t) => return self.check(&t.value_type(), value, path, errors),
+      TypeSignature::I8 => integer(value).is_some_and(|n| i8::try_from(n).is_ok()),
+      TypeSignature::I16 => integer(value).is_some_and(|n| i16::try_from(n).is_ok()),
+      TypeSignature::I32 => integer(value).is_some_and(|n| i32::try_from(n).is_ok()),
+      TypeSignature::I64 => integer(value).is_some_and(|n| i64::try_from(n).is_ok()),
+      TypeSignature::U8 => integer(value).is_some_and(|n| u8::try_from(n).is_ok()),
+      TypeSignature::U16 => integer(value).is_some_and(|n| u16::try_from(n).is_ok()),
+      TypeSignature::U32 => integer(value).is_some_and(|n| u32::try_from(n).is_ok()),
+      TypeSignature::U64 => integer(value).is_some_and(|n| u64::try_from(n).is_ok()),
+      TypeSignature::F32 | TypeSignature::F64 => {
+        matches!(value, Value::F32(_) | Value::F64(_)) || integer(value).is_some()
+      }
+      TypeSignature::Bool => matches!(value, Value::Bool(_)),
+      TypeSignature::String => matches!(value, Value::String(_) | Value::Char(_)),
+      TypeSignature::Datetime => Datetime::deserialize(value.clone()).is_ok(),
+      TypeSignature::Uuid => Uuid::deserialize(value.clone()).is_ok(),
+      TypeSignature::Duration => Duration::deserialize(value.clone()).is_ok(),
+      TypeSignature::Decimal => Decimal::deserialize(value.clone()).is_ok(),
+      TypeSignature::Url => Url::deserialize(value.clone()).is_ok(),
+      TypeSignature::Bytes => match value {
+        Value::Bytes(_) => true,
+        Value::Seq(items) => items
+          .iter()
+          .all(|v| integer(unwrap_newtype(v)).is_some_and(|n| u8::try_from(n).is_ok())),
+        _ => false,
+      },
+      TypeSignature::Struct | TypeSignature::Link { .. } => matches!(value, Value::Map(_)),
+      TypeSignature::Enum { values } => matches!(value, Value::String(s) if values.contains(s)),
+      TypeSignature::Union { tag, variants } => {
+        self.check_union(tag, variants, value, path, errors);
+        return;
+      }
+      TypeSignature::Optional { option } => {
+        match value {
+          Value::Unit | Value::Option(None) => {}
+          Value::Option(Some(inner)) => self.check(option, inner, path, errors),
+          _ => self.check(option, value, path, errors),
+        }
+        return;
+      }
+      TypeSignature::List { element } => {
+        if let Value::Seq(items) = value {
+          for (i, item) in items.iter().enumerate() {
+            self.check(element, item, &format!("{}[{}]", path, i), errors);
+          }
+          return;
+        }
+        false
+      }
+      TypeSignature::Tuple { elements } => {
+        if let Value::Seq(items) = value {
+          self.check_items(elements.iter(), elements.len(), items, path, errors);
+          return;
+        }
+        false
+      }
+      TypeSignature::Array { element, length } => {
+        if let Value::Seq(items) = value {
+          let length = usize::try_from(*length).unwrap_or(usize::MAX);
+          self.check_items(std::iter::repeat(element.as_ref()), length, items, path, errors);
+          return;
+        }
+        false
+      }
+      TypeSignature::Constrained { base, constraints } => {
+        let found = errors.len();
+        self.check(base, value, path, errors);
+        if errors.len() == found {
+          check_constraints(constraints, value, path, errors);
+        }
+        return;
+      }
+      TypeSignature::Map { key, value: value_sig } => {
+        if let Value::Map(map) = value {
+          self.check_map(key, value_sig, map, path, errors);
+          return;
+        }
+        false
+      }
+      TypeSignature::Ref { reference } => {
+        match self.types.resolve(reference) {
+          Some(def) => self.check_struct(def, value, path, None, errors),
+          None if self.trust_refs => {}
+          None => errors.push(ValidationError::new(
+            path,
+            format!("unresolved reference '{}'", reference),
+          )),
+        }
+        return;
+      }
+    };
+    if !valid {
+      errors.push(ValidationError::new(
+        path,
+        format!("expected {}, found {}", sig, describe(value)),
+      ));
+    }
+  }
+
+  /// Check a sequence that must hold exactly `length` items of the passed types.
+  fn check_items<'s, I: Iterator<Item = &'s TypeSignature>>(
+    &self,
+    sigs: I,
+    length: usize,
+    items: &[Value],
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+  ) {
+    if items.len() != length {
+      errors.push(ValidationError::new(
+        path,
+        format!("expected {} items, found {}", length, items.len()),
+      ));
+      return;
+    }
+    for (i, (sig, item)) in sigs.zip(items).enumerate() {
+      self.check(sig, item, &format!("{}[{}]", path, i), errors);
+    }
+  }
+
+  fn check_map(
+    &self,
+    key_sig: &TypeSignature,
+    value_sig: &TypeSignature,
+    map: &BTreeMap<Value, Value>,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+  ) {
+    for (key, value) in map {
+      let key_path = format!("{}[{}]", path, describe_key(key));
+      // Formats like JSON only have string keys, so give numeric and boolean keys a chance to parse.
+      let parsed_key = match key {
+        Value::String(s) if is_scalar_key(key_sig) => serde_json::from_str::<serde_json::Value>(s)
+          .ok()
+          .and_then(|v| serde_value::to_value(v).ok()),
+        _ => None,
+      };
+      self.check(key_sig, parsed_key.as_ref().unwrap_or(key), &key_path, errors);
+      self.check(value_sig, value, &key_path, errors);
+    }
+  }
+
+  fn check_union(&self, tag: &str, variants: &[String], value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let map = match value {
+      Value::Map(map) => map,
+      _ => {
+        errors.push(ValidationError::new(
+          path,
+          format!("expected union, found {}", describe(value)),
+        ));
+        return;
+      }
+    };
+    let tag_path = format!("{}.{}", path, tag);
+    let name = match map.get(&Value::String(tag.to_owned())).map(unwrap_newtype) {
+      Some(Value::String(name)) => name,
+      Some(other) => {
+        errors.push(ValidationError::new(
+          &tag_path,
+          format!("expected string, found {}", describe(other)),
+        ));
+        return;
+      }
+      None => {
+        errors.push(ValidationError::new(&tag_path, "missing tag"));
+        return;
+      }
+    };
+    match variants.iter().find(|v| ref_name(v) == name) {
+      Some(variant) => match self.types.resolve(variant) {
+        Some(def) => self.check_struct(def, value, path, Some(tag), errors),
+        None if self.trust_refs => {}
+        None => errors.push(ValidationError::new(
+          path,
+          format!("unresolved reference '{}'", variant),
+        )),
+      },
+      None => errors.push(ValidationError::new(&tag_path, format!("unknown variant '{}'", name))),
+    }
+  }
+
+  /// Check a map against a struct's fields. A union's `tag` field is allowed in addition to the fields.
+  fn check_struct(
+    &self,
+    def: &StructSignature,
+    value: &Value,
+    path: &str,
+    tag: Option<&str>,
+    errors: &mut Vec<ValidationError>,
+  ) {
+    let map = match value {
+      Value::Map(map) => map,
+      _ => {
+        errors.push(ValidationError::new(
+          path,
+          format!("expected struct {}, found {}", def.name, describe(value)),
+        ));
+        return;
+      }
+    };
+    for (field, sig) in def.fields.ordered() {
+      let field_path = format!("{}.{}", path, field);
+      match map.get(&Value::String(field.clone())) {
+        Some(v) => self.check(sig, v, &field_path, errors),
+        None if matches!(sig, TypeSignature::Optional { .. }) => {}
+        None if def.fields.metadata(field).is_some_and(|m| m.default.is_some()) => {}
+        None => errors.push(ValidationError::new(&field_path, "missing field")),
+      }
+    }
+    for key in map.keys() {
+      let known = matches!(key, Value::String(k) if def.fields.contains_key(k) || tag == Some(k.as_str()));
+      if !known {
+        errors.push(ValidationError::new(
+          &format!("{}.{}", path, describe_key(key)),
+          "unexpected field",
+        ));
+      }
+    }
+  }
+}
+
+impl Constraints {
+  /// Check a JSON value that already matches the constrained type, returning the first violation.
+  pub(crate) fn check_json(&self, value: &serde_json::Value) -> Result<(), String> {
+    let value = serde_value::to_value(value).map_err(|e| e.to_string())?;
+    let mut errors = Vec::new();
+    check_constraints(self, &value, "$", &mut errors);
+    errors.into_iter().next().map_or(Ok(()), |e| Err(e.message))
+  }
+}
+
+/// Check a value that already matches its base type against [Constraints].
+fn check_constraints(constraints: &Constraints, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+  let value = match value {
+    Value::Option(Some(inner)) => unwrap_newtype(inner),
+    _ => value,
+  };
+  let number = match value {
+    Value::F32(n) => Some(f64::from(*n)),
+    Value::F64(n) => Some(*n),
+    _ => integer(value).map(|n| n as f64),
+  };
+  if let Some(n) = number {
+    if let Some(minimum) = constraints.minimum.filter(|minimum| n < *minimum) {
+      errors.push(ValidationError::new(
+        path,
+        format!("{} is less than the minimum {}", n, minimum),
+      ));
+    }
+    if let Some(maximum) = constraints.maximum.filter(|maximum| n > *maximum) {
+      errors.push(ValidationError::new(
+        path,
+        format!("{} is greater than the maximum {}", n, maximum),
+      ));
+    }
+  }
+  let length = match value {
+    Value::String(s) => Some(s.chars().count()),
+    Value::Char(_) => Some(1),
+    Value::Bytes(bytes) => Some(bytes.len()),
+    Value::Seq(items) => Some(items.len()),
+    Value::Map(map) => Some(map.len()),
+    _ => None,
+  };
+  if let Some(length) = length.map(|n| n as u64) {
+    if let Some(min_length) = constraints.min_length.filter(|min| length < *min) {
+      errors.push(ValidationError::new(
+        path,
+        format!("length {} is less than the minimum length {}", length, min_length),
+      ));
+    }
+    if let Some(max_length) = constraints.max_length.filter(|max| length > *max) {
+      errors.push(ValidationError::new(
+        path,
+        format!("length {} is greater than the maximum length {}", length, max_length),
+      ));
+    }
+  }
+  if let (Some(pattern), Value::String(s)) = (&constraints.pattern, value) {
+    if !pattern.is_match(s) {
+      errors.push(ValidationError::new(
+        path,
+        format!("'{}' does not match the pattern '{}'", s, pattern),
+      ));
+    }
+  }
+}
+
+/// Returns true if keys of this type are numbers or booleans, which formats like JSON write as strings.
+fn is_scalar_key(sig: &TypeSignature) -> bool {
+  matches!(
+    unwrap_optional(sig),
+    TypeSignature::U8
+      | TypeSignature::U16
+      | TypeSignature::U32
+      | TypeSignature::U64
+      | TypeSignature::I8
+      | TypeSignature::I16
+      | TypeSignature::I32
+      | TypeSignature::I64
+      | TypeSignature::F32
+      | TypeSignature::F64
+      | TypeSignature::Bool
+  )
+}
+
+fn unwrap_newtype(value: &Value) -> &Value {
+  match value {
+    Value::Newtype(inner) => unwrap_newtype(inner),
+    _ => value,
+  }
+}
+
+fn integer(value: &Value) -> Option<i128> {
+  match value {
+    Value::U8(n) => Some((*n).into()),
+    Value::U16(n) => Some((*n).into()),
+    Value::U32(n) => Some((*n).into()),
+    Value::U64(n) => Some((*n).into()),
+    Value::I8(n) => Some((*n).into()),
+    Value::I16(n) => Some((*n).into()),
+    Value::I32(n) => Some((*n).into()),
+    Value::I64(n) => Some((*n).into()),
+    _ => None,
+  }
+}
+
+fn describe(value: &Value) -> &'static str {
+  match value {
+    Value::Bool(_) => "bool",
+    Value::U8(_) | Value::U16(_) | Value::U32(_) | Value::U64(_) => "unsigned integer",
+    Value::I8(_) | Value::I16(_) | Value::I32(_) | Value::I64(_) => "integer",
+    Value::F32(_) | Value::F64(_) => "float",
+    Value::Char(_) | Value::String(_) => "string",
+    Value::Unit | Value::Option(None) => "null",
+    Value::Option(Some(_)) | Value::Newtype(_) => "wrapped value",
+    Value::Seq(_) => "list",
+    Value::Map(_) => "map",
+    Value::Bytes(_) => "bytes",
+  }
+}
+
+fn describe_key(key: &Value) -> String {
+  match unwrap_newtype(key) {
+    Value::String(s) => s.clone(),
+    other => integer(other).map_or_else(|| describe(other).to_owned(), |n| n.to_string()),
+  }
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_types::{Cardinality, CardinalityError, ComponentSignature, MapWrapper};
+
+fn component() -> Result<ComponentSignature> {
+  Ok(serde_json::from_str(
+    r#"{
+      "name": "search",
+      "inputs": { "query": { "type": "string" } },
+      "outputs": {
+        "total": { "type": "u32" },
+        "best": { "type": "string", "cardinality": "optional" },
+        "results": { "type": "string", "cardinality": "stream" }
+      }
+    }"#,
+  )?)
+}
+
+#[test_log::test]
+fn test_serde() -> Result<()> {
+  let component = component()?;
+  assert_eq!(component.outputs.cardinality("total"), Cardinality::Single);
+  assert_eq!(component.outputs.cardinality("best"), Cardinality::Optional);
+  assert_eq!(component.outputs.cardinality("results"), Cardinality::Stream);
+
+  let json = serde_json::to_value(&component)?;
+  assert_eq!(json["outputs"]["total"], serde_json::json!({ "type": "u32" }));
+  assert_eq!(json["outputs"]["results"]["cardinality"], "stream");
+  assert_eq!(component.outputs.len(), 3);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_check() -> Result<()> {
+  assert_eq!(Cardinality::Single.check("out", 1), Ok(()));
+  assert_eq!(
+    Cardinality::Single.check("out", 0),
+    Err(CardinalityError::Missing("out".to_owned()))
+  );
+  assert_eq!(
+    Cardinality::Optional.check("out", 2),
+    Err(CardinalityError::Extra("out".to_owned()))
+  );
+  assert_eq!(Cardinality::Optional.check("out", 0), Ok(()));
+  assert_eq!(Cardinality::Stream.check("out", 0), Ok(()));
+  assert_eq!(Cardinality::Stream.check("out", 100), Ok(()));
+
+  assert!(Cardinality::Stream.includes(Cardinality::Optional));
+  assert!(Cardinality::Optional.includes(Cardinality::Single));
+  assert!(!Cardinality::Single.includes(Cardinality::Optional));
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_output_tracker() -> Result<()> {
+  use vino_transport::{MessageTransport, TransportWrapper};
+
+  let component = component()?;
+  let value = MessageTransport::success(&"value");
+
+  let mut tracker = component.track_outputs();
+  tracker.record(&TransportWrapper::new("total", MessageTransport::success(&3)))?;
+  tracker.record(&TransportWrapper::done("total"))?;
+  tracker.record(&TransportWrapper::new("results", value.clone()))?;
+  tracker.record(&TransportWrapper::new("results", value.clone()))?;
+  tracker.record(&TransportWrapper::new_system_close())?;
+  assert_eq!(tracker.finish(), Ok(()));
+
+  let mut tracker = component.track_outputs();
+  tracker.record(&TransportWrapper::new("best", value.clone()))?;
+  assert_eq!(
+    tracker.record(&TransportWrapper::new("best", value.clone())),
+    Err(CardinalityError::Extra("best".to_owned()))
+  );
+  assert_eq!(
+    tracker.record(&TransportWrapper::new("other", value)),
+    Err(CardinalityError::Undeclared("other".to_owned()))
+  );
+  assert_eq!(
+    tracker.finish(),
+    Err(vec![
+      CardinalityError::Missing("total".to_owned()),
+      CardinalityError::Extra("best".to_owned())
+    ])
+  );
+
+  let mut tracker = component.track_outputs();
+  tracker.record(&TransportWrapper::component_error(MessageTransport::error("failed")))?;
+  assert_eq!(tracker.finish(), Ok(()));
+  Ok(())
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_types::{
+  ComponentMap, ComponentSignature, MapWrapper, MergeConflict, ProviderMap, ProviderSignature, TypeMap,
+};
+
+fn provider(name: &str, components: &[&str]) -> ProviderSignature {
+  let mut provider = ProviderSignature::new(name);
+  for component in components {
+    provider
+      .components
+      .insert(component, ComponentSignature::new(component));
+  }
+  provider
+}
+
+fn providers(list: Vec<(&str, ProviderSignature)>) -> ProviderMap {
+  let mut map = ProviderMap::default();
+  for (namespace, provider) in list {
+    map.insert(namespace, provider);
+  }
+  map
+}
+
+#[test_log::test]
+fn test_merge() -> Result<()> {
+  let merged = ProviderMap::merge_all(vec![
+    providers(vec![("math", provider("math", &["add"]))]),
+    providers(vec![
+      ("text", provider("text", &["upper"])),
+      ("math", provider("math", &["add"])),
+    ]),
+  ])?;
+  assert_eq!(merged.names(), vec!["math", "text"]);
+
+  let mut map = merged.clone();
+  let err = map
+    .merge(providers(vec![
+      ("net", provider("net", &["fetch"])),
+      ("text", provider("text", &["lower"])),
+      ("math", provider("math", &["sub"])),
+    ]))
+    .unwrap_err();
+  assert_eq!(
+    err,
+    MergeConflict::Providers(vec!["math".to_owned(), "text".to_owned()])
+  );
+  assert_eq!(
+    err.to_string(),
+    "providers declared with different signatures: math, text"
+  );
+  assert_eq!(map, merged, "nothing is added when merging conflicts");
+
+  let mut components = provider("math", &["add", "sub"]).components;
+  let mut changed = ComponentSignature::new("sub");
+  changed.inputs = TypeMap::try_from(vec![("left", "i64")])?;
+  let mut other = ComponentMap::default();
+  other.insert("sub", changed);
+  assert_eq!(
+    components.merge(other),
+    Err(MergeConflict::Components(vec!["sub".to_owned()]))
+  );
+  components.merge(provider("math", &["add", "mul"]).components)?;
+  assert_eq!(components.names(), vec!["add", "sub", "mul"]);
+  Ok(())
+}
+
+#[cfg(feature = "entity")]
+#[test_log::test]
+fn test_resolve() -> Result<()> {
+  use vino_entity::Entity;
+
+  let map = providers(vec![
+    ("math", provider("math", &["add", "sub"])),
+    ("text", provider("text", &["upper"])),
+  ]);
+  assert_eq!(
+    map.resolve(&Entity::component("math", "sub")).map(|c| c.name.as_str()),
+    Some("sub")
+  );
+  assert_eq!(map.resolve(&Entity::component("text", "add")), None);
+  assert_eq!(map.resolve(&Entity::component("none", "add")), None);
+  assert_eq!(map.resolve(&Entity::provider("math")), None);
+
+  let components = &map.get("text").unwrap().components;
+  assert_eq!(
+    components
+      .resolve(&Entity::local_component("upper"))
+      .map(|c| c.name.as_str()),
+    Some("upper")
+  );
+
+  let entities: Vec<_> = map
+    .entities()
+    .map(|(entity, component)| (entity.url(), component.name.clone()))
+    .collect();
+  assert_eq!(
+    entities,
+    vec![
+      ("ofp://math.prov/add".to_owned(), "add".to_owned()),
+      ("ofp://math.prov/sub".to_owned(), "sub".to_owned()),
+      ("ofp://text.prov/upper".to_owned(), "upper".to_owned()),
+    ]
+  );
+  Ok(())
+}
+
+#[cfg(feature = "entity")]
+#[test_log::test]
+fn test_filter() -> Result<()> {
+  use vino_entity::EntityPattern;
+
+  let map = providers(vec![
+    ("math", provider("math", &["add", "sub"])),
+    ("text", provider("text", &["upper", "add_suffix"])),
+  ]);
+  let filtered = map.filter(&EntityPattern::new("ofp://*.prov/add*"));
+  assert_eq!(filtered.names(), vec!["math", "text"]);
+  assert_eq!(filtered.get("math").unwrap().components.names(), vec!["add"]);
+  assert_eq!(filtered.get("text").unwrap().components.names(), vec!["add_suffix"]);
+
+  let filtered = map.filter(&EntityPattern::new("ofp://math.prov/*"));
+  assert_eq!(filtered.names(), vec!["math"]);
+  assert_eq!(filtered.get("math"), map.get("math"));
+
+  assert!(map.filter(&EntityPattern::new("ofp://net.prov/*")).is_empty());
+  assert_eq!(map.filter(&EntityPattern::any()), map);
+  Ok(())
+}
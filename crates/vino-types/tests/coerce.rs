@@ -0,0 +1,311 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::{MapWrapper, StructMap, StructSignature, TypeMap, TypeSignature};
+
+fn types() -> Result<StructMap> {
+  let mut types = StructMap::new();
+  types.insert(
+    "User",
+    StructSignature {
+      name: "User".to_owned(),
+      fields: TypeMap::try_from(vec![("name", "string"), ("age", "u8"), ("nickname", "string?")])?,
+      ..Default::default()
+    },
+  );
+  Ok(types)
+}
+
+fn coerce(sig: &str, value: serde_json::Value) -> Result<serde_json::Value> {
+  let sig: TypeSignature = sig.parse()?;
+  Ok(sig.coerce(&value, &types()?)?)
+}
+
+fn coerce_str(sig: &str, value: &str) -> Result<serde_json::Value> {
+  let sig: TypeSignature = sig.parse()?;
+  Ok(sig.coerce_str(value, &types()?)?)
+}
+
+#[test_log::test]
+fn test_coerce() -> Result<()> {
+  let cases = vec![
+    ("i32", json!("42"), json!(42)),
+    ("i32", json!(" -7 "), json!(-7)),
+    ("u8", json!(3.0), json!(3)),
+    ("f64", json!(2), json!(2.0)),
+    ("f32", json!("1.5"), json!(1.5)),
+    ("bool", json!("true"), json!(true)),
+    ("string", json!(42), json!("42")),
+    (
+      "datetime",
+      json!("2021-11-04T12:30:00.5+01:00"),
+      json!("2021-11-04T11:30:00.500Z"),
+    ),
+    ("bytes", json!("aGk="), json!([104, 105])),
+    ("bytes", json!([1, "2"]), json!([1, 2])),
+    ("enum(a|b)", json!("b"), json!("b")),
+    ("u16?", json!(null), json!(null)),
+    ("u16?", json!("8"), json!(8)),
+    ("u8[]", json!(["1", 2]), json!([1, 2])),
+    ("{u8: f64}", json!({"01": "1"}), json!({"1": 1.0})),
+    (
+      "ref:User",
+      json!({"name": 7, "age": "30"}),
+      json!({"name": "7", "age": 30}),
+    ),
+    ("value", json!({"any": [1]}), json!({"any": [1]})),
+  ];
+  for (sig, value, expected) in cases {
+    assert_eq!(coerce(sig, value.clone())?, expected, "coercing {} into {}", value, sig);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_coerce_struct_defaults() -> Result<()> {
+  let mut types = types()?;
+  let user = types.get_inner_mut().get_mut("User").unwrap();
+  user.fields = user
+    .fields
+    .clone()
+    .with_metadata("age", vino_types::Metadata::new().with_default(json!("21")));
+  let sig: TypeSignature = "ref:User".parse()?;
+  assert_eq!(
+    sig.coerce(&json!({"name": "ann"}), &types)?,
+    json!({"name": "ann", "age": 21})
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_coerce_str() -> Result<()> {
+  assert_eq!(coerce_str("i32", "42")?, json!(42));
+  assert_eq!(coerce_str("string", "42")?, json!("42"));
+  assert_eq!(coerce_str("string", "hello world")?, json!("hello world"));
+  assert_eq!(coerce_str("string", r#""quoted""#)?, json!("quoted"));
+  assert_eq!(coerce_str("string?", "null")?, json!(null));
+  assert_eq!(
+    coerce_str("datetime", "2021-11-04T12:30:00Z")?,
+    json!("2021-11-04T12:30:00Z")
+  );
+  assert_eq!(coerce_str("u8[]", "[1, 2]")?, json!([1, 2]));
+  assert_eq!(coerce_str("bool", "false")?, json!(false));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_lossy_coercions() -> Result<()> {
+  let cases = vec![
+    ("u8", json!(256), "$", "256 overflows u8"),
+    ("i8", json!("-129"), "$", "-129 overflows i8"),
+    ("u32", json!(-1), "$", "-1 overflows u32"),
+    ("i32", json!(1.5), "$", "1.5 is not an integer"),
+    ("i32", json!("abc"), "$", "'abc' is not an integer"),
+    ("f32", json!(1e300), "$", "1e+300 overflows f32"),
+    (
+      "f64",
+      json!(9_007_199_254_740_993_u64),
+      "$",
+      "9007199254740993 cannot be represented exactly as f64",
+    ),
+    ("bool", json!("yes"), "$", "expected bool, found string"),
+    (
+      "datetime",
+      json!("2021-02-30T00:00:00Z"),
+      "$",
+      "'2021-02-30T00:00:00Z' is not an RFC3339 datetime",
+    ),
+    (
+      "datetime",
+      json!(1_636_000_000),
+      "$",
+      "expected datetime, found integer",
+    ),
+    (
+      "bytes",
+      json!("not base64!"),
+      "$",
+      "invalid base64: Invalid byte 32, offset 3.",
+    ),
+    ("enum(a|b)", json!("c"), "$", "'c' is not one of a, b"),
+    ("u8[]", json!([1, 300]), "$[1]", "300 overflows u8"),
+    ("ref:User", json!({"name": "Jane"}), "$.age", "missing field"),
+    (
+      "ref:User",
+      json!({"name": "Jane", "age": 1, "x": 1}),
+      "$.x",
+      "unexpected field",
+    ),
+  ];
+  for (sig, value, path, message) in cases {
+    let sig: TypeSignature = sig.parse()?;
+    let err = sig.coerce(&value, &types()?).unwrap_err();
+    assert_eq!((err.path.as_str(), err.message.as_str()), (path, message), "{}", sig);
+  }
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_coerce_kv() -> Result<()> {
+  use vino_types::ComponentSignature;
+
+  let mut component = ComponentSignature::new("create");
+  component.inputs = TypeMap::try_from(vec![("user", "ref:User"), ("count", "u32"), ("label", "string")])?;
+
+  let args: Vec<String> = vec![
+    r#"user={"name": "Jane", "age": "30"}"#.to_owned(),
+    "count=42".to_owned(),
+    "label=hi there".to_owned(),
+  ];
+  let mut payload = component.coerce_kv(&args, &types()?).unwrap();
+  assert_eq!(payload.consume::<u32>("count")?, 42);
+  assert_eq!(payload.consume::<String>("label")?, "hi there");
+  assert_eq!(
+    payload.consume::<serde_json::Value>("user")?,
+    json!({"name": "Jane", "age": 30})
+  );
+
+  component.inputs = TypeMap::try_from(vec![("image", "bytes"), ("chunks", "bytes[]?")])?;
+  let args: Vec<String> = vec!["image=aGk=".to_owned(), r#"chunks=["aGk=", [1, 2]]"#.to_owned()];
+  let mut payload = component.coerce_kv(&args, &types()?).unwrap();
+  assert_eq!(
+    payload.consume::<serde_value::Value>("image")?,
+    serde_value::Value::Bytes(b"hi".to_vec())
+  );
+  assert_eq!(
+    payload.consume::<serde_value::Value>("chunks")?,
+    serde_value::Value::Seq(vec![
+      serde_value::Value::Bytes(b"hi".to_vec()),
+      serde_value::Value::Bytes(vec![1, 2])
+    ])
+  );
+
+  let args: Vec<String> = vec!["count=-1".to_owned(), "extra=1".to_owned(), "label".to_owned()];
+  let paths: Vec<_> = component
+    .coerce_kv(&args, &types()?)
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.path)
+    .collect();
+  assert_eq!(paths, vec!["count", "extra", "label"]);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_coerce_config() -> Result<()> {
+  use std::collections::HashMap;
+
+  use serde_value::Value;
+  use vino_types::ComponentSignature;
+
+  let component: ComponentSignature = serde_json::from_str(
+    r#"{
+      "name": "fetch",
+      "inputs": {},
+      "outputs": {},
+      "config": {
+        "url": { "type": "string" },
+        "timeout": { "type": "u32", "default": 30 },
+        "since": { "type": "datetime" },
+        "headers": { "type": "map", "key": { "type": "string" }, "value": { "type": "string" } },
+        "proxy": { "type": "optional", "option": { "type": "string" } }
+      }
+    }"#,
+  )?;
+  assert_eq!(
+    serde_json::to_value(&component)?["config"]["timeout"]["default"],
+    json!(30)
+  );
+
+  let string = |s: &str| Value::String(s.to_owned());
+  let config = HashMap::from([
+    ("url".to_owned(), string("https://vino.dev")),
+    ("since".to_owned(), string("2021-11-04T12:30:00+01:00")),
+    ("headers".to_owned(), string(r#"{"accept": "text/html"}"#)),
+  ]);
+  let coerced = component.coerce_config(&config, &types()?).unwrap();
+  assert_eq!(
+    coerced,
+    HashMap::from([
+      ("url".to_owned(), string("https://vino.dev")),
+      ("timeout".to_owned(), Value::U64(30)),
+      ("since".to_owned(), string("2021-11-04T11:30:00Z")),
+      (
+        "headers".to_owned(),
+        Value::Map([(string("accept"), string("text/html"))].into_iter().collect())
+      ),
+    ])
+  );
+
+  let typed = HashMap::from([
+    ("url".to_owned(), string("https://vino.dev")),
+    ("timeout".to_owned(), Value::U8(5)),
+    ("since".to_owned(), string("2021-11-04T11:30:00Z")),
+    (
+      "headers".to_owned(),
+      Value::Map([(string("accept"), string("text/html"))].into_iter().collect()),
+    ),
+  ]);
+  let coerced = component.coerce_config(&typed, &types()?).unwrap();
+  assert_eq!(coerced["timeout"], Value::U64(5));
+  assert_eq!(coerced["headers"], typed["headers"]);
+
+  let config = HashMap::from([
+    ("timeout".to_owned(), string("soon")),
+    ("retries".to_owned(), Value::U8(3)),
+    ("url".to_owned(), Value::Seq(vec![])),
+  ]);
+  let paths: Vec<_> = component
+    .coerce_config(&config, &types()?)
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.path)
+    .collect();
+  assert_eq!(paths, vec!["retries", "url", "timeout", "since", "headers"]);
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_configure() -> Result<()> {
+  use std::collections::HashMap;
+
+  use serde_value::Value;
+  use vino_types::ComponentSignature;
+
+  #[derive(serde::Deserialize)]
+  struct Config {
+    level: u8,
+    label: String,
+  }
+
+  let mut component = ComponentSignature::new("log");
+  component.config = TypeMap::try_from(vec![("level", "u8"), ("label", "string")])?;
+
+  let mut payload = vino_transport::TransportMap::new();
+  payload.with_config(HashMap::from([
+    ("level".to_owned(), Value::String("3.0".to_owned())),
+    ("label".to_owned(), Value::String("\"quoted\"".to_owned())),
+  ]));
+  let types = types()?;
+  let config: Config = payload.get_config_typed(&component.config_schema(&types))?;
+  assert_eq!((config.level, config.label.as_str()), (3, "quoted"));
+
+  component.configure(&mut payload, &types).unwrap();
+  let config: Config = payload.get_config_typed(&component.config_schema(&types))?;
+  assert_eq!((config.level, config.label.as_str()), (3, "quoted"));
+
+  payload.with_config(HashMap::from([
+    ("level".to_owned(), Value::U64(7)),
+    ("label".to_owned(), Value::String("typed".to_owned())),
+  ]));
+  let config: Config = payload.get_config_typed(&component.config_schema(&types))?;
+  assert_eq!((config.level, config.label.as_str()), (7, "typed"));
+
+  payload.with_config(HashMap::from([("level".to_owned(), Value::String("300".to_owned()))]));
+  assert!(component.configure(&mut payload, &types).is_err());
+  let result = payload.get_config_typed::<Config>(&component.config_schema(&types));
+  assert!(matches!(result, Err(vino_transport::Error::InvalidConfig(e)) if e.starts_with("level:")));
+  Ok(())
+}
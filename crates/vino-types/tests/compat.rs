@@ -0,0 +1,311 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_types::{
+  is_assignable, Cardinality, ChangeKind, ComponentSignature, MapWrapper, Metadata, ProviderSignature, StructSignature,
+  TypeMap, TypeSignature,
+};
+
+fn component(name: &str, inputs: Vec<(&str, &str)>, outputs: Vec<(&str, &str)>) -> Result<ComponentSignature> {
+  Ok(ComponentSignature {
+    name: name.to_owned(),
+    inputs: TypeMap::try_from(inputs)?,
+    outputs: TypeMap::try_from(outputs)?,
+    ..Default::default()
+  })
+}
+
+fn provider(components: Vec<ComponentSignature>, types: Vec<StructSignature>) -> ProviderSignature {
+  let mut provider = ProviderSignature::new("test");
+  for component in components {
+    provider.components.insert(component.name.clone(), component);
+  }
+  for def in types {
+    provider.types.insert(def.name.clone(), def);
+  }
+  provider
+}
+
+fn blog(fields: Vec<(&str, &str)>) -> Result<StructSignature> {
+  Ok(StructSignature {
+    name: "Blog".to_owned(),
+    fields: TypeMap::try_from(fields)?,
+    ..Default::default()
+  })
+}
+
+#[test_log::test]
+fn test_assignable() -> Result<()> {
+  let cases = vec![
+    ("u8", "u32", true),
+    ("u32", "u8", false),
+    ("u32", "i64", true),
+    ("i8", "u64", false),
+    ("f32", "f64", true),
+    ("string", "string?", true),
+    ("string?", "string", false),
+    ("u8[]", "u16[]", true),
+    ("ref:#/types/Blog", "ref:Blog", true),
+    ("link:kv", "link", true),
+    ("link", "link:kv", false),
+    ("bool", "value", true),
+    ("enum(a|b)", "enum(a|b|c)", true),
+    ("enum(a|b|c)", "enum(a|b)", false),
+    ("enum(a)", "string", true),
+    ("union:kind(A)", "union:kind(#/types/A|B)", true),
+    ("union:kind(A|B)", "union:type(A|B)", false),
+  ];
+  for (from, to, expected) in cases {
+    let (from_sig, to_sig): (TypeSignature, TypeSignature) = (from.parse()?, to.parse()?);
+    assert_eq!(is_assignable(&from_sig, &to_sig), expected, "{} -> {}", from, to);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_identical() -> Result<()> {
+  let sig = provider(
+    vec![component("add", vec![("left", "u32")], vec![("output", "u32")])?],
+    vec![],
+  );
+  let report = sig.check_compatibility(&sig.clone());
+  assert!(report.changes.is_empty());
+  assert!(report.is_compatible());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_components() -> Result<()> {
+  let old = provider(
+    vec![
+      component(
+        "add",
+        vec![("left", "u16"), ("right", "u16")],
+        vec![("output", "u32"), ("log", "string")],
+      )?,
+      component("remove", vec![], vec![])?,
+    ],
+    vec![],
+  );
+  let new = provider(
+    vec![
+      component(
+        "add",
+        vec![("left", "u32"), ("right", "u8"), ("carry", "bool?"), ("mode", "string")],
+        vec![("output", "u64"), ("overflow", "bool")],
+      )?,
+      component("subtract", vec![], vec![])?,
+    ],
+    vec![],
+  );
+  let report = old.check_compatibility(&new);
+  let changes: Vec<_> = report.changes.iter().map(|c| (c.path.as_str(), c.breaking)).collect();
+  assert_eq!(
+    changes,
+    vec![
+      ("components.add.inputs.carry", false),
+      ("components.add.inputs.left", false),
+      ("components.add.inputs.mode", true),
+      ("components.add.inputs.right", true),
+      ("components.add.outputs.log", true),
+      ("components.add.outputs.output", true),
+      ("components.add.outputs.overflow", false),
+      ("components.remove", true),
+      ("components.subtract", false),
+    ]
+  );
+  assert!(!report.is_compatible());
+  assert_eq!(report.breaking().count(), 5);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_narrowed_output() -> Result<()> {
+  let old = provider(vec![component("get", vec![], vec![("output", "u64?")])?], vec![]);
+  let new = provider(vec![component("get", vec![], vec![("output", "u8")])?], vec![]);
+  let report = old.check_compatibility(&new);
+  assert!(report.is_compatible());
+  assert_eq!(
+    report.changes[0].kind,
+    ChangeKind::OutputChanged {
+      from: "u64?".parse()?,
+      to: "u8".parse()?
+    }
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_struct_fields() -> Result<()> {
+  let old = provider(
+    vec![],
+    vec![blog(vec![("title", "string"), ("id", "u32"), ("body", "string")])?],
+  );
+  let new = provider(
+    vec![],
+    vec![blog(vec![
+      ("headline", "string"),
+      ("id", "u64"),
+      ("body", "string"),
+      ("draft", "bool?"),
+    ])?],
+  );
+  let report = old.check_compatibility(&new);
+  let changes: Vec<_> = report
+    .changes
+    .iter()
+    .map(|c| (c.path.as_str(), c.kind.clone(), c.breaking))
+    .collect();
+  assert_eq!(
+    changes,
+    vec![
+      (
+        "types.Blog.fields.draft",
+        ChangeKind::FieldAdded("bool?".parse()?),
+        false
+      ),
+      (
+        "types.Blog.fields.id",
+        ChangeKind::FieldChanged {
+          from: TypeSignature::U32,
+          to: TypeSignature::U64
+        },
+        true
+      ),
+      (
+        "types.Blog.fields.title",
+        ChangeKind::FieldRenamed {
+          from: "title".to_owned(),
+          to: "headline".to_owned()
+        },
+        true
+      ),
+    ]
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_report_display() -> Result<()> {
+  let old = provider(vec![component("get", vec![], vec![])?], vec![]);
+  let new = provider(vec![], vec![blog(vec![])?]);
+  assert_eq!(
+    old.check_compatibility(&new).to_string(),
+    "components.get: component removed [breaking]\ntypes.Blog: type added [non-breaking]\n"
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_defaulted_input() -> Result<()> {
+  let old = provider(vec![component("list", vec![], vec![])?], vec![]);
+  let mut list = component("list", vec![("page_size", "u32")], vec![])?;
+  let new = provider(vec![list.clone()], vec![]);
+  assert!(!old.check_compatibility(&new).is_compatible());
+
+  list
+    .inputs
+    .set_metadata("page_size", Metadata::new().with_default(serde_json::json!(20)));
+  let new = provider(vec![list], vec![]);
+  assert!(old.check_compatibility(&new).is_compatible());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_cardinality() -> Result<()> {
+  let with = |input: Cardinality, output: Cardinality| -> Result<ProviderSignature> {
+    let mut get = component("get", vec![("id", "string")], vec![("output", "string")])?;
+    get.inputs.set_metadata("id", Metadata::new().with_cardinality(input));
+    get
+      .outputs
+      .set_metadata("output", Metadata::new().with_cardinality(output));
+    Ok(provider(vec![get], vec![]))
+  };
+  let old = with(Cardinality::Single, Cardinality::Optional)?;
+
+  let report = old.check_compatibility(&with(Cardinality::Stream, Cardinality::Single)?);
+  assert!(report.is_compatible());
+  assert_eq!(
+    report.changes[0].kind,
+    ChangeKind::CardinalityChanged {
+      from: Cardinality::Single,
+      to: Cardinality::Stream
+    }
+  );
+
+  let report = old.check_compatibility(&with(Cardinality::Single, Cardinality::Stream)?);
+  let breaking: Vec<_> = report.breaking().map(|c| c.to_string()).collect();
+  assert_eq!(
+    breaking,
+    vec!["components.get.outputs.output: cardinality changed from optional to stream [breaking]"]
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_removed_default() -> Result<()> {
+  let with_default = |default: bool| -> Result<ProviderSignature> {
+    let mut list = component("list", vec![("page_size", "u32"), ("cursor", "string?")], vec![])?;
+    let mut page = blog(vec![("title", "string")])?;
+    if default {
+      let metadata = Metadata::new().with_default(serde_json::json!(20));
+      list.inputs.set_metadata("page_size", metadata.clone());
+      list.inputs.set_metadata("cursor", metadata.clone());
+      page.fields.set_metadata("title", metadata);
+    }
+    Ok(provider(vec![list], vec![page]))
+  };
+  let report = with_default(true)?.check_compatibility(&with_default(false)?);
+  let changes: Vec<_> = report.changes.iter().map(|c| c.to_string()).collect();
+  assert_eq!(
+    changes,
+    vec![
+      "components.list.inputs.cursor: default removed (20) [non-breaking]",
+      "components.list.inputs.page_size: default removed (20) [breaking]",
+      "types.Blog.fields.title: default removed (20) [breaking]",
+    ]
+  );
+  assert!(with_default(false)?
+    .check_compatibility(&with_default(true)?)
+    .is_compatible());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_config() -> Result<()> {
+  let with_config = |config: Vec<(&str, &str)>| -> Result<ProviderSignature> {
+    let mut fetch = component("fetch", vec![], vec![])?;
+    fetch.config = TypeMap::try_from(config.clone())?;
+    let mut provider = provider(vec![fetch], vec![]);
+    provider.config = TypeMap::try_from(config)?;
+    Ok(provider)
+  };
+  let old = with_config(vec![("url", "string"), ("timeout", "u16")])?;
+
+  let report = old.check_compatibility(&with_config(vec![
+    ("url", "string"),
+    ("timeout", "u32"),
+    ("proxy", "string?"),
+  ])?);
+  assert!(report.is_compatible(), "{}", report);
+  assert_eq!(
+    report.changes[0].kind,
+    ChangeKind::ConfigAdded(TypeSignature::Optional {
+      option: Box::new(TypeSignature::String)
+    })
+  );
+
+  let report = old.check_compatibility(&with_config(vec![("url", "u8"), ("retries", "u8")])?);
+  let breaking: Vec<_> = report.breaking().map(|c| c.to_string()).collect();
+  assert_eq!(
+    breaking,
+    vec![
+      "components.fetch.config.retries: config added (u8) [breaking]",
+      "components.fetch.config.timeout: config removed (u16) [breaking]",
+      "components.fetch.config.url: config changed from string to u8 [breaking]",
+      "config.retries: config added (u8) [breaking]",
+      "config.timeout: config removed (u16) [breaking]",
+      "config.url: config changed from string to u8 [breaking]",
+    ]
+  );
+  Ok(())
+}
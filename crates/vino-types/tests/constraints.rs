@@ -0,0 +1,315 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::jsonschema::{import_schema, type_schema};
+use vino_types::{is_assignable, Constraints, MapWrapper, Pattern, Sampler, StructMap, TypeSignature};
+
+fn sig(src: &str) -> Result<TypeSignature> {
+  Ok(src.parse()?)
+}
+
+fn messages(src: &str, value: serde_json::Value) -> Result<Vec<String>> {
+  Ok(match sig(src)?.validate_json(&value, &StructMap::new()) {
+    Ok(()) => vec![],
+    Err(errors) => errors.into_iter().map(|e| e.to_string()).collect(),
+  })
+}
+
+#[test_log::test]
+fn test_parse() -> Result<()> {
+  let cases = vec![
+    (
+      "(f32, string)",
+      TypeSignature::Tuple {
+        elements: vec![TypeSignature::F32, TypeSignature::String],
+      },
+    ),
+    ("()", TypeSignature::Tuple { elements: vec![] }),
+    (
+      "f32[3]",
+      TypeSignature::Array {
+        element: Box::new(TypeSignature::F32),
+        length: 3,
+      },
+    ),
+    (
+      "u8(min: 1, max: 10)",
+      TypeSignature::Constrained {
+        base: Box::new(TypeSignature::U8),
+        constraints: Constraints::new().with_minimum(1.0).with_maximum(10.0),
+      },
+    ),
+    (
+      r#"string(min_len: 1, max_len: 8, pattern: "^[a-z]+\\d?$")[]"#,
+      TypeSignature::List {
+        element: Box::new(TypeSignature::Constrained {
+          base: Box::new(TypeSignature::String),
+          constraints: Constraints::new()
+            .with_min_length(1)
+            .with_max_length(8)
+            .with_pattern(Pattern::new(r"^[a-z]+\d?$")?),
+        }),
+      },
+    ),
+    (
+      "(f64(min: -0.5), u8[2])?",
+      TypeSignature::Optional {
+        option: Box::new(TypeSignature::Tuple {
+          elements: vec![
+            TypeSignature::Constrained {
+              base: Box::new(TypeSignature::F64),
+              constraints: Constraints::new().with_minimum(-0.5),
+            },
+            TypeSignature::Array {
+              element: Box::new(TypeSignature::U8),
+              length: 2,
+            },
+          ],
+        }),
+      },
+    ),
+  ];
+  for (src, expected) in cases {
+    let actual = sig(src)?;
+    assert_eq!(actual, expected, "parsing '{}'", src);
+    assert_eq!(sig(&actual.to_string())?, expected, "round trip of '{}'", src);
+  }
+  assert_eq!(sig("(f32,string)")?.to_string(), "(f32, string)");
+
+  let errors = vec![
+    ("u8(min: x)", 8),
+    ("u8(size: 1)", 3),
+    ("u8(min: 1, min: 2)", 11),
+    ("u8()", 3),
+    ("f32[x]", 3),
+    ("(f32, string", 12),
+    ("string(pattern: abc)", 16),
+    (r#"string(pattern: "[a")"#, 16),
+  ];
+  for (src, position) in errors {
+    let err = src.parse::<TypeSignature>().expect_err(src);
+    assert_eq!(err.position(), position, "error position for '{}': {}", src, err);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_validate() -> Result<()> {
+  assert_eq!(messages("(f32, string)", json!([1.5, "a"]))?, Vec::<String>::new());
+  assert_eq!(
+    messages("(f32, string)", json!(["a", "b"]))?,
+    vec!["$[0]: expected f32, found string"]
+  );
+  assert_eq!(
+    messages("(f32, string)", json!([1.5]))?,
+    vec!["$: expected 2 items, found 1"]
+  );
+  assert_eq!(messages("f32[3]", json!([1, 2, 3]))?, Vec::<String>::new());
+  assert_eq!(messages("f32[3]", json!([1, 2]))?, vec!["$: expected 3 items, found 2"]);
+  assert_eq!(messages("u8(min: 1, max: 10)", json!(10))?, Vec::<String>::new());
+  assert_eq!(
+    messages("u8(min: 1, max: 10)", json!(11))?,
+    vec!["$: 11 is greater than the maximum 10"]
+  );
+  assert_eq!(
+    messages("f64(min: 0.5)", json!(0.25))?,
+    vec!["$: 0.25 is less than the minimum 0.5"]
+  );
+  // The base type is checked first, and constraints only once it matches.
+  assert_eq!(
+    messages("u8(min: 1)", json!(-1))?,
+    vec!["$: expected u8, found integer"]
+  );
+  assert_eq!(
+    messages("string(min_len: 2, max_len: 3)", json!("héé"))?,
+    Vec::<String>::new()
+  );
+  assert_eq!(
+    messages("string(min_len: 2)[]", json!(["ab", "c"]))?,
+    vec!["$[1]: length 1 is less than the minimum length 2"]
+  );
+  assert_eq!(
+    messages("u8[](max_len: 1)", json!([1, 2]))?,
+    vec!["$: length 2 is greater than the maximum length 1"]
+  );
+  assert_eq!(
+    messages(r#"string(pattern: "^[a-z]+$")"#, json!("abc1"))?,
+    vec!["$: 'abc1' does not match the pattern '^[a-z]+$'"]
+  );
+  assert_eq!(messages("u8(min: 1)?", json!(null))?, Vec::<String>::new());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_coerce() -> Result<()> {
+  let types = StructMap::new();
+  assert_eq!(
+    sig("(u8, bool)")?.coerce(&json!(["1", "true"]), &types)?,
+    json!([1, true])
+  );
+  assert_eq!(sig("f32[2]")?.coerce_str("[1, 2]", &types)?, json!([1.0, 2.0]));
+  assert_eq!(
+    sig("f32[2]")?.coerce_str("[1]", &types).unwrap_err().to_string(),
+    "$: expected 2 items, found 1"
+  );
+  assert_eq!(sig("u8(max: 5)")?.coerce_str("5", &types)?, json!(5));
+  assert_eq!(
+    sig("u8(max: 5)")?.coerce_str("6", &types).unwrap_err().to_string(),
+    "$: 6 is greater than the maximum 5"
+  );
+  assert_eq!(sig("string(min_len: 2)")?.coerce_str("abc", &types)?, json!("abc"));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_sample() -> Result<()> {
+  let types = StructMap::new();
+  let cases = vec![
+    "(f32, string, bool)",
+    "f32[3]",
+    "u8(min: 10, max: 20)",
+    "i64(max: -1000)",
+    "f64(min: 0.25, max: 0.5)",
+    "string(min_len: 5, max_len: 6)",
+    "u8[](min_len: 2)",
+    "{string: u8}(max_len: 1)",
+    r#"string(pattern: "^[a-z]+$")"#,
+  ];
+  for src in cases {
+    let sig = sig(src)?;
+    let minimal = sig.sample(&types)?;
+    assert_eq!(sig.validate_json(&minimal, &types), Ok(()), "minimal sample of {}", src);
+    let mut sampler = Sampler::random(7);
+    for _ in 0..20 {
+      let value = sampler.sample(&sig, &types)?;
+      assert_eq!(sig.validate_json(&value, &types), Ok(()), "random sample of {}", src);
+    }
+  }
+  assert_eq!(
+    sig("u8(min: 300)")?.sample(&types).unwrap_err().to_string(),
+    "$: no u8 is within the bounds"
+  );
+  assert_eq!(
+    sig(r#"string(pattern: "^\\d+$")"#)?
+      .sample(&types)
+      .unwrap_err()
+      .to_string(),
+    r"$: no sample string matches the pattern '^\d+$'"
+  );
+  assert_eq!(
+    sig("u8[5000]")?.sample(&types).unwrap_err().to_string(),
+    "$: a length of 5000 is more than the 4096 a sample can have"
+  );
+  assert!(sig("string(min_len: 18446744073709551615)")?.sample(&types).is_err());
+  assert_eq!(sig("string(max_len: 18446744073709551615)")?.sample(&types)?, json!(""));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_invalid_pattern() -> Result<()> {
+  assert!(Pattern::new("[a").is_err());
+  let err = serde_json::from_value::<Constraints>(json!({ "pattern": "[a" })).unwrap_err();
+  assert!(err.to_string().starts_with("Invalid pattern:"), "{}", err);
+  let constraints: Constraints = serde_json::from_value(json!({ "pattern": "^a" }))?;
+  assert_eq!(constraints, Constraints::new().with_pattern(Pattern::new("^a")?));
+  assert_eq!(serde_json::to_value(&constraints)?, json!({ "pattern": "^a" }));
+
+  let schema = json!({
+    "type": "object",
+    "properties": { "name": { "type": "string", "pattern": "(" } }
+  });
+  let err = import_schema(&schema).unwrap_err();
+  assert!(err.to_string().contains("Invalid pattern:"), "{}", err);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_schema() -> Result<()> {
+  let cases = vec![
+    (
+      "(f32, string)",
+      json!({
+        "type": "array",
+        "prefixItems": [{"type": "number"}, {"type": "string"}],
+        "items": false,
+        "minItems": 2,
+        "maxItems": 2,
+      }),
+    ),
+    (
+      "f32[3]",
+      json!({"type": "array", "items": {"type": "number"}, "minItems": 3, "maxItems": 3}),
+    ),
+    (
+      "u8(min: 1, max: 300)",
+      json!({"type": "integer", "minimum": 1, "maximum": 255}),
+    ),
+    ("f64(max: 0.5)", json!({"type": "number", "maximum": 0.5})),
+    (
+      r#"string(min_len: 1, pattern: "^a")"#,
+      json!({"type": "string", "minLength": 1, "pattern": "^a"}),
+    ),
+    (
+      "u8[](max_len: 4)",
+      json!({"type": "array", "items": {"type": "integer", "minimum": 0, "maximum": 255}, "maxItems": 4}),
+    ),
+    (
+      "{string: bool}(min_len: 1)",
+      json!({"type": "object", "additionalProperties": {"type": "boolean"}, "minProperties": 1}),
+    ),
+  ];
+  for (src, expected) in cases {
+    assert_eq!(type_schema(&sig(src)?), expected, "schema for {}", src);
+  }
+
+  let (_, fields) = import_schema(&json!({
+    "type": "object",
+    "properties": {
+      "point": type_schema(&sig("(f32, string)")?),
+      "vector": type_schema(&sig("f32[3]")?),
+      "percent": type_schema(&sig("u8(max: 100)")?),
+      "ratio": type_schema(&sig("f64(min: 0, max: 1)")?),
+      "name": type_schema(&sig(r#"string(min_len: 1, pattern: "^a")"#)?),
+      "byte": type_schema(&sig("u8")?),
+    },
+    "required": ["point", "vector", "percent", "ratio", "name", "byte"],
+  }))?;
+  let expected = vec![
+    ("point", "(f64, string)"),
+    ("vector", "f64[3]"),
+    ("percent", "u8(max: 100)"),
+    ("ratio", "f64(min: 0, max: 1)"),
+    ("name", r#"string(min_len: 1, pattern: "^a")"#),
+    ("byte", "u8"),
+  ];
+  for (field, expected) in expected {
+    assert_eq!(
+      fields.get(field).map(ToString::to_string).as_deref(),
+      Some(expected),
+      "import of {}",
+      field
+    );
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_assignable() -> Result<()> {
+  let cases = vec![
+    ("u8(min: 1, max: 5)", "u8", true),
+    ("u8", "u8(min: 1)", false),
+    ("u8(min: 2, max: 4)", "u8(min: 1, max: 5)", true),
+    ("u8(min: 0, max: 6)", "u8(min: 1, max: 5)", false),
+    (r#"string(pattern: "^a")"#, r#"string(pattern: "^b")"#, false),
+    ("f32[3]", "f32[]", true),
+    ("f32[3]", "f64[3]", true),
+    ("f32[3]", "f32[4]", false),
+    ("(u8, u16)", "(u16, u32)", true),
+    ("(u8, u16)", "u32[]", true),
+    ("(u8, u16)", "(u8, u16, u32)", false),
+  ];
+  for (from, to, expected) in cases {
+    assert_eq!(is_assignable(&sig(from)?, &sig(to)?), expected, "{} -> {}", from, to);
+  }
+  Ok(())
+}
@@ -0,0 +1,182 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::{Datetime, StructMap, TypeSignature};
+
+#[test_log::test]
+fn test_rfc3339() -> Result<()> {
+  let cases = vec![
+    ("1970-01-01T00:00:00Z", 0, 0, "1970-01-01T00:00:00Z"),
+    (
+      "2021-11-04T12:30:00.5+01:00",
+      1_636_025_400,
+      500_000_000,
+      "2021-11-04T11:30:00.500Z",
+    ),
+    (
+      "2021-11-04t11:30:00.000123z",
+      1_636_025_400,
+      123_000,
+      "2021-11-04T11:30:00.000123Z",
+    ),
+    (
+      "1969-12-31T23:59:59.999999999Z",
+      -1,
+      999_999_999,
+      "1969-12-31T23:59:59.999999999Z",
+    ),
+    ("2000-02-29 23:00:00-01:30", 951_870_600, 0, "2000-03-01T00:30:00Z"),
+    ("2016-12-31T23:59:60Z", 1_483_228_800, 0, "2017-01-01T00:00:00Z"),
+  ];
+  for (input, seconds, nanos, canonical) in cases {
+    let datetime: Datetime = input.parse()?;
+    assert_eq!(
+      (datetime.unix_seconds(), datetime.subsec_nanos()),
+      (seconds, nanos),
+      "{}",
+      input
+    );
+    assert_eq!(datetime.to_string(), canonical);
+    assert_eq!(canonical.parse::<Datetime>()?, datetime);
+  }
+
+  for invalid in [
+    "2021-11-04",
+    "2021-02-29T00:00:00Z",
+    "2021-11-04T24:00:00Z",
+    "2021-11-04T12:30:00",
+    "2021-11-04T12:30:00.Z",
+    "2021-11-04T12:30:00+0100",
+  ] {
+    assert!(invalid.parse::<Datetime>().is_err(), "{}", invalid);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_system_time() -> Result<()> {
+  let time = UNIX_EPOCH + Duration::new(1_636_025_400, 42);
+  let datetime = Datetime::from(time);
+  assert_eq!(datetime, Datetime::from_unix(1_636_025_400, 42));
+  assert_eq!(SystemTime::try_from(datetime)?, time);
+
+  let before = UNIX_EPOCH - Duration::from_millis(1500);
+  let datetime = Datetime::from(before);
+  assert_eq!(datetime, Datetime::from_unix_millis(-1500));
+  assert_eq!(datetime.unix_millis(), -1500);
+  assert_eq!(SystemTime::try_from(datetime)?, before);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_json() -> Result<()> {
+  let datetime = Datetime::from_unix(1_636_025_400, 500_000_000);
+  assert_eq!(serde_json::to_value(datetime)?, json!("2021-11-04T11:30:00.500Z"));
+  assert_eq!(
+    serde_json::from_value::<Datetime>(json!("2021-11-04T12:30:00.5+01:00"))?,
+    datetime
+  );
+  assert!(serde_json::from_value::<Datetime>(json!(1_636_025_400_500_u64)).is_err());
+  assert_eq!(
+    serde_value::to_value(datetime)?,
+    serde_value::Value::String("2021-11-04T11:30:00.500Z".to_owned())
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_messagepack() -> Result<()> {
+  let cases = vec![
+    // timestamp 32
+    (
+      Datetime::from_unix(1_636_025_400, 0),
+      vec![0xd6, 0xff, 0x61, 0x83, 0xc4, 0x38],
+    ),
+    // timestamp 64
+    (
+      Datetime::from_unix(1, 1),
+      vec![0xd7, 0xff, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01],
+    ),
+    // timestamp 96
+    (
+      Datetime::from_unix(-1, 0),
+      vec![
+        0xc7, 12, 0xff, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+      ],
+    ),
+  ];
+  for (datetime, bytes) in cases {
+    assert_eq!(vino_codec::messagepack::serialize(&datetime)?, bytes, "{}", datetime);
+    assert_eq!(vino_codec::messagepack::deserialize::<Datetime>(&bytes)?, datetime);
+  }
+
+  // Strings are read too, so values coerced from JSON still deserialize.
+  let bytes = vino_codec::messagepack::serialize(&"2021-11-04T11:30:00Z")?;
+  assert_eq!(
+    vino_codec::messagepack::deserialize::<Datetime>(&bytes)?,
+    Datetime::from_unix(1_636_025_400, 0)
+  );
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_as_json() -> Result<()> {
+  let cases = vec![
+    Datetime::from_unix(1_636_025_400, 0),
+    Datetime::from_unix(1_636_025_400, 500_000_000),
+    Datetime::from_unix(-1, 999_999_999),
+    Datetime::from_unix(253_402_300_799, 123_000),
+  ];
+  for datetime in cases {
+    let mut payload = vino_transport::MessageTransport::success(&datetime);
+    payload.to_messagepack();
+    let json = payload.as_json();
+    assert_eq!(json["value"], json!(datetime.to_string()), "{}", json);
+    assert_eq!(serde_json::from_value::<Datetime>(json["value"].clone())?, datetime);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_saturating() {
+  assert_eq!(Datetime::from_unix(i64::MAX, 1_500_000_000).unix_seconds(), i64::MAX);
+  assert_eq!(Datetime::from_unix(i64::MAX, 0).unix_millis(), i64::MAX);
+  assert_eq!(Datetime::from_unix(i64::MIN, 0).unix_millis(), i64::MIN);
+}
+
+#[test_log::test]
+fn test_validate() -> Result<()> {
+  let datetime = Datetime::now();
+  let bytes = vino_codec::messagepack::serialize(&datetime)?;
+  let value: serde_value::Value = vino_codec::messagepack::deserialize(&bytes)?;
+  let types = StructMap::new();
+  assert_eq!(TypeSignature::Datetime.validate(&value, &types), Ok(()));
+  assert_eq!(TypeSignature::Datetime.validate_json(&json!(datetime), &types), Ok(()));
+  assert!(TypeSignature::Datetime
+    .validate_json(&json!("yesterday"), &types)
+    .is_err());
+  Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test_log::test]
+fn test_chrono() -> Result<()> {
+  let datetime = Datetime::from_unix(1_636_025_400, 42);
+  let converted = chrono::DateTime::<chrono::Utc>::try_from(datetime)?;
+  assert_eq!(converted.timestamp(), 1_636_025_400);
+  assert_eq!(Datetime::from(converted), datetime);
+  Ok(())
+}
+
+#[cfg(feature = "time")]
+#[test_log::test]
+fn test_time() -> Result<()> {
+  let datetime = Datetime::from_unix(-1_636_025_400, 42);
+  let converted = time::OffsetDateTime::try_from(datetime)?;
+  assert_eq!(converted.unix_timestamp(), -1_636_025_400);
+  assert_eq!(Datetime::from(converted), datetime);
+  Ok(())
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use pretty_assertions::{assert_eq, assert_ne};
+use vino_types::{Cardinality, MapWrapper, Metadata, ProviderSignature, TypeSignature};
+
+static SIGNATURE: &str = r##"{
+  "name": "blog",
+  "types": {
+    "Post": {
+      "name": "Post",
+      "description": "A blog post.",
+      "fields": {
+        "title": { "type": "string" },
+        "status": { "type": "enum", "values": ["draft", "published"], "default": "draft" }
+      }
+    },
+    "Page": { "name": "Page", "fields": { "path": { "type": "string" } } }
+  },
+  "components": {
+    "publish": {
+      "name": "publish",
+      "inputs": { "post": { "type": "ref", "ref": "#/types/Post" }, "at": { "type": "datetime" } },
+      "outputs": { "url": { "type": "string" } }
+    },
+    "render": {
+      "name": "render",
+      "inputs": { "content": { "type": "union", "tag": "kind", "variants": ["Post", "Page"] } },
+      "outputs": { "html": { "type": "string" } }
+    }
+  }
+}"##;
+
+/// The same signature, reordered and redocumented.
+static REORDERED: &str = r##"{
+  "name": "blog-v2",
+  "components": {
+    "render": {
+      "name": "render",
+      "description": "Render content to HTML.",
+      "inputs": { "content": { "type": "union", "tag": "kind", "variants": ["#/types/Page", "#/types/Post"] } },
+      "outputs": { "html": { "type": "string", "deprecated": true } }
+    },
+    "publish": {
+      "name": "publish",
+      "inputs": { "at": { "type": "datetime" }, "post": { "type": "ref", "ref": "Post" } },
+      "outputs": { "url": { "type": "string", "description": "Where the post lives." } }
+    }
+  },
+  "types": {
+    "Page": { "name": "Page", "fields": { "path": { "type": "string" } } },
+    "Post": {
+      "name": "Post",
+      "fields": {
+        "status": { "type": "enum", "values": ["published", "draft"], "default": "draft" },
+        "title": { "type": "string" }
+      }
+    }
+  }
+}"##;
+
+#[test_log::test]
+fn test_cosmetic_changes() -> Result<()> {
+  let sig: ProviderSignature = serde_json::from_str(SIGNATURE)?;
+  let reordered: ProviderSignature = serde_json::from_str(REORDERED)?;
+  assert_eq!(sig.fingerprint(), reordered.fingerprint());
+  assert_eq!(
+    sig.get_component("render").unwrap().fingerprint(),
+    reordered.get_component("render").unwrap().fingerprint()
+  );
+  assert_eq!(
+    sig.types.get("Post").unwrap().fingerprint(),
+    reordered.types.get("Post").unwrap().fingerprint()
+  );
+  assert_eq!(
+    "enum(a|b)".parse::<TypeSignature>()?.fingerprint(),
+    "enum(b|a)".parse::<TypeSignature>()?.fingerprint()
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_structural_changes() -> Result<()> {
+  let sig: ProviderSignature = serde_json::from_str(SIGNATURE)?;
+  let fingerprint = sig.fingerprint();
+
+  let changes = vec![
+    SIGNATURE.replace(r#""at": { "type": "datetime" }"#, r#""at": { "type": "string" }"#),
+    SIGNATURE.replace(r#""default": "draft""#, r#""default": "published""#),
+    SIGNATURE.replace(r#""tag": "kind""#, r#""tag": "type""#),
+    SIGNATURE.replace(r#""html": { "type": "string" }"#, r#""markup": { "type": "string" }"#),
+    SIGNATURE.replace(r#""render": {"#, r#""draw": {"#),
+    SIGNATURE.replace(r#""Page": {"#, r#""Document": {"#),
+  ];
+  for changed in changes {
+    let changed: ProviderSignature = serde_json::from_str(&changed)?;
+    assert_ne!(changed.fingerprint(), fingerprint, "{:?}", changed);
+  }
+
+  let mut changed = sig.clone();
+  changed
+    .components
+    .get_inner_mut()
+    .get_mut("render")
+    .unwrap()
+    .outputs
+    .set_metadata("html", Metadata::new().with_cardinality(Cardinality::Stream));
+  assert_ne!(changed.fingerprint(), fingerprint);
+
+  assert_ne!(
+    "u8[]".parse::<TypeSignature>()?.fingerprint(),
+    "u8?".parse::<TypeSignature>()?.fingerprint()
+  );
+  assert_ne!(
+    "{string: u8}".parse::<TypeSignature>()?.fingerprint(),
+    "{u8: string}".parse::<TypeSignature>()?.fingerprint()
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_stable() -> Result<()> {
+  // Fingerprints are persisted by hosts, so they must never change between releases.
+  assert_eq!(TypeSignature::String.fingerprint().to_string(), "61cc5d3861200386");
+  assert_eq!(
+    "u32[]".parse::<TypeSignature>()?.fingerprint().as_u64(),
+    17_647_326_441_587_057_228
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_referenced_types() -> Result<()> {
+  let sig: ProviderSignature = serde_json::from_str(SIGNATURE)?;
+  let component = sig.get_component("publish").unwrap();
+  let fingerprint = component.fingerprint_with(&sig.types);
+
+  let reordered: ProviderSignature = serde_json::from_str(REORDERED)?;
+  assert_eq!(
+    reordered
+      .get_component("publish")
+      .unwrap()
+      .fingerprint_with(&reordered.types),
+    fingerprint
+  );
+
+  let changed: ProviderSignature = serde_json::from_str(&SIGNATURE.replace(
+    r#""title": { "type": "string" }"#,
+    r#""title": { "type": "ref", "ref": "Title" }"#,
+  ))?;
+  let changed_component = changed.get_component("publish").unwrap();
+  assert_eq!(changed_component.fingerprint(), component.fingerprint());
+  assert_ne!(changed_component.fingerprint_with(&changed.types), fingerprint);
+
+  // Types reached through other types count too, and unrelated types do not.
+  let mut title = changed.clone();
+  title
+    .types
+    .insert("Title", serde_json::from_str(r#"{ "name": "Title", "fields": {} }"#)?);
+  let with_title = changed_component.fingerprint_with(&title.types);
+  assert_ne!(with_title, changed_component.fingerprint_with(&changed.types));
+  title
+    .types
+    .insert("Unused", serde_json::from_str(r#"{ "name": "Unused", "fields": {} }"#)?);
+  assert_eq!(changed_component.fingerprint_with(&title.types), with_title);
+  Ok(())
+}
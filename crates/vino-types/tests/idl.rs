@@ -0,0 +1,165 @@
+use std::fs::read_to_string;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::idl::{parse, print, IdlError};
+use vino_types::{Cardinality, MapWrapper, ProviderSignature, TypeSignature};
+
+static IDL: &str = r#"provider math
+
+// Shared types.
+/// A point on a plane.
+type Point {
+  x: f64,
+  /// Defaults to the x axis.
+  y: f64 = 0,
+}
+
+@deprecated
+type "Legacy Point" { coords: {string: f64} }
+
+/// Add two numbers.
+component add(left: i64, right: i64 = 1) -> (output: i64)
+
+component walk(from: Point, path: Point[]?) -> (position: stream Point, done: optional bool) config {
+  speed: f64 = 1.5,
+  mode: enum(fast|slow) = "fast",
+}
+"#;
+
+#[test_log::test]
+fn test_parse() -> Result<()> {
+  let provider = parse(IDL)?;
+  assert_eq!(provider.name.as_deref(), Some("math"));
+  assert_eq!(provider.types.names(), vec!["Point", "Legacy Point"]);
+
+  let point = provider.types.get("Point").unwrap();
+  assert_eq!(point.description.as_deref(), Some("A point on a plane."));
+  let y = point.fields.metadata("y").unwrap();
+  assert_eq!(y.description.as_deref(), Some("Defaults to the x axis."));
+  assert_eq!(y.default, Some(json!(0)));
+  assert!(provider.types.get("Legacy Point").unwrap().deprecated);
+
+  let add = provider.components.get("add").unwrap();
+  assert_eq!(add.description.as_deref(), Some("Add two numbers."));
+  assert_eq!(add.inputs.get("left"), Some(&TypeSignature::I64));
+  assert_eq!(add.inputs.metadata("right").unwrap().default, Some(json!(1)));
+  assert_eq!(add.outputs.get("output"), Some(&TypeSignature::I64));
+
+  let walk = provider.components.get("walk").unwrap();
+  assert_eq!(walk.inputs.get("from"), Some(&"ref:Point".parse()?));
+  assert_eq!(walk.inputs.get("path"), Some(&"ref:Point[]?".parse()?));
+  assert_eq!(walk.outputs.cardinality("position"), Cardinality::Stream);
+  assert_eq!(walk.outputs.cardinality("done"), Cardinality::Optional);
+  assert_eq!(walk.config.metadata("mode").unwrap().default, Some(json!("fast")));
+  assert_eq!(walk.config.get("speed"), Some(&TypeSignature::F64));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_errors() -> Result<()> {
+  let cases = vec![
+    ("type A { x: nope }", 1, 13, "unknown type 'nope'"),
+    (
+      "provider a\n\ncomponent b(x: u8) (y: u8)",
+      3,
+      20,
+      "expected '->', found '('",
+    ),
+    ("type A {\n  x: u8\n  y: u8\n}", 3, 3, "expected '}', found 'y'"),
+    ("type A { x: u8 }\ntype A { y: u8 }", 2, 6, "type 'A' declared twice"),
+    (
+      "component a(x: u8 = nah) -> ()",
+      1,
+      21,
+      "invalid default value: expected ident at line 1 column 2",
+    ),
+    (
+      "component a(x: u8 = 300) -> ()",
+      1,
+      21,
+      "invalid default for 'x': $: expected u8, found unsigned integer",
+    ),
+    (
+      "type A {\n  x: string = 1,\n}",
+      2,
+      15,
+      "invalid default for 'x': $: expected string, found unsigned integer",
+    ),
+    ("interface a", 1, 1, "unknown item 'interface'"),
+    ("type A {", 1, 9, "expected a name"),
+  ];
+  for (src, line, column, message) in cases {
+    assert_eq!(
+      parse(src),
+      Err(IdlError {
+        line,
+        column,
+        message: message.to_owned()
+      }),
+      "error for {}",
+      src
+    );
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_print() -> Result<()> {
+  let printed = print(&parse(IDL)?);
+  let expected = r#"provider math
+
+/// A point on a plane.
+type Point {
+  x: f64,
+  /// Defaults to the x axis.
+  y: f64 = 0,
+}
+
+@deprecated
+type "Legacy Point" {
+  coords: {string: f64},
+}
+
+/// Add two numbers.
+component add(left: i64, right: i64 = 1) -> (output: i64)
+
+component walk(from: Point, path: Point[]?) -> (position: stream Point, done: optional bool) config {
+  speed: f64 = 1.5,
+  mode: enum(fast|slow) = "fast",
+}
+"#;
+  assert_eq!(printed, expected);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_round_trip() -> Result<()> {
+  let src = read_to_string("./tests/interface.json")?;
+  let sig: ProviderSignature = serde_json::from_str(&src)?;
+
+  let printed = print(&sig);
+  let parsed = parse(&printed)?;
+  assert_eq!(print(&parsed), printed);
+  assert_eq!(parsed.fingerprint(), sig.fingerprint());
+  assert_eq!(parsed.name, sig.name);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_compound_types() -> Result<()> {
+  let src = "type Point { x: f64 }\n\ncomponent move(by: (Point, u8(max: 3)), axis: f32[3]) -> (to: Point[2])\n";
+  let provider = parse(src)?;
+  let component = provider.components.get("move").unwrap();
+  assert_eq!(component.inputs.get("by"), Some(&"(ref:Point, u8(max: 3))".parse()?));
+  assert_eq!(component.outputs.get("to"), Some(&"ref:Point[2]".parse()?));
+  assert_eq!(print(&provider), src.replace("{ x: f64 }", "{\n  x: f64,\n}"));
+
+  let err = parse("component a(x: (Missing, u8)) -> ()").unwrap_err();
+  assert_eq!(
+    (err.line, err.column, err.message.as_str()),
+    (1, 16, "unknown type 'Missing'")
+  );
+  Ok(())
+}
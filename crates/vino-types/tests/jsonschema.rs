@@ -0,0 +1,364 @@
+use std::fs::read_to_string;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::jsonschema::{
+  component_inputs_schema, component_outputs_schema, import_schema, provider_schema, type_schema, DIALECT,
+};
+use vino_types::{MapWrapper, ProviderSignature, StructMap, TypeMap, TypeSignature};
+
+#[test_log::test]
+fn test_type_schemas() -> Result<()> {
+  let cases = vec![
+    ("u8", json!({"type": "integer", "minimum": 0, "maximum": 255})),
+    ("datetime", json!({"type": "string", "format": "date-time"})),
+    ("string[]", json!({"type": "array", "items": {"type": "string"}})),
+    ("bool?", json!({"anyOf": [{"type": "boolean"}, {"type": "null"}]})),
+    (
+      "{string: f64}",
+      json!({"type": "object", "additionalProperties": {"type": "number"}}),
+    ),
+    ("ref:#/types/Blog", json!({"$ref": "#/$defs/Blog"})),
+    ("ref:Blog", json!({"$ref": "#/$defs/Blog"})),
+    (
+      "{u32: bool}",
+      json!({
+        "type": "object",
+        "propertyNames": {"type": "string", "pattern": "^(0|[1-9][0-9]*)$"},
+        "additionalProperties": {"type": "boolean"},
+      }),
+    ),
+    ("value", json!({})),
+  ];
+  for (sig, expected) in cases {
+    let sig: TypeSignature = sig.parse()?;
+    assert_eq!(type_schema(&sig), expected, "schema for {}", sig);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_provider_schema() -> Result<()> {
+  let src = read_to_string("./tests/interface.json")?;
+  let sig: ProviderSignature = serde_json::from_str(&src)?;
+
+  let schema = provider_schema(&sig);
+  assert_eq!(schema["$schema"], json!(DIALECT));
+  assert_eq!(schema["title"], json!("blog"));
+  assert_eq!(
+    schema["$defs"]["Blog"]["required"],
+    json!(["blog_id", "body", "section_id", "title", "user_id"])
+  );
+  assert_eq!(schema["$defs"]["Blog"]["additionalProperties"], json!(false));
+  assert_eq!(
+    schema["components"]["new"]["outputs"]["properties"],
+    json!({"blog_id": {"type": "string"}})
+  );
+  assert_eq!(
+    schema["components"]["new"]["inputs"]["properties"]["kv"],
+    json!({"type": "object"})
+  );
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_component_schema() -> Result<()> {
+  let src = read_to_string("./tests/interface-test.json")?;
+  let sig: ProviderSignature = serde_json::from_str(&src)?;
+  let component = sig.get_component("copy").unwrap();
+
+  let schema = component_inputs_schema(component, &StructMap::new());
+  assert_eq!(
+    schema,
+    json!({
+      "$schema": DIALECT,
+      "title": "copy inputs",
+      "type": "object",
+      "properties": {
+        "input": {"type": "string"},
+        "times": {"type": "integer", "minimum": -128, "maximum": 127}
+      },
+      "required": ["input", "times"],
+      "additionalProperties": false
+    })
+  );
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_import_schema() -> Result<()> {
+  let schema = json!({
+    "$schema": DIALECT,
+    "$defs": {
+      "User": {
+        "type": "object",
+        "properties": {
+          "name": {"type": "string"},
+          "role": {"enum": ["admin", "member"]},
+          "age": {"type": "integer", "minimum": 0, "maximum": 255},
+          "nickname": {"type": ["string", "null"]}
+        },
+        "required": ["name", "role", "age", "nickname"]
+      }
+    },
+    "type": "object",
+    "properties": {
+      "users": {"type": "array", "items": {"$ref": "#/$defs/User"}},
+      "owner": {"anyOf": [{"$ref": "#/$defs/User"}, {"type": "null"}]},
+      "created": {"type": "string", "format": "date-time"},
+      "labels": {"type": "object", "additionalProperties": {"type": "number"}},
+      "address": {
+        "type": "object",
+        "properties": {"street": {"type": "string"}},
+        "required": ["street"]
+      }
+    },
+    "required": ["users", "created", "labels", "address"]
+  });
+
+  let (types, fields) = import_schema(&schema)?;
+
+  let expected = TypeMap::try_from(vec![
+    ("users", "ref:#/types/User[]"),
+    ("owner", "ref:#/types/User?"),
+    ("created", "datetime"),
+    ("labels", "{string: f64}"),
+    ("address", "ref:#/types/Address"),
+  ])?;
+  assert_eq!(fields, expected);
+
+  let user = types.get("User").unwrap();
+  let expected = TypeMap::try_from(vec![
+    ("name", "string"),
+    ("role", "enum(admin|member)"),
+    ("age", "u8"),
+    ("nickname", "string?"),
+  ])?;
+  assert_eq!(user.fields, expected);
+  assert_eq!(
+    types.get("Address").unwrap().fields,
+    TypeMap::try_from(vec![("street", "string")])?
+  );
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_import_roundtrip() -> Result<()> {
+  let src = read_to_string("./tests/interface.json")?;
+  let sig: ProviderSignature = serde_json::from_str(&src)?;
+
+  let schema = provider_schema(&sig);
+  let (types, _) = import_schema(&schema)?;
+  assert_eq!(types.get("Blog").unwrap().fields, sig.types.get("Blog").unwrap().fields);
+
+  let component = sig.get_component("new").unwrap();
+  let (_, outputs) = import_schema(&component_outputs_schema(component, &sig.types))?;
+  assert_eq!(outputs, component.outputs);
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_import_errors() -> Result<()> {
+  let cases = vec![
+    (json!({"properties": {"a": {"allOf": []}}}), "#/properties/a"),
+    (
+      json!({"properties": {"a": {"type": ["string", "integer"]}}}),
+      "#/properties/a",
+    ),
+    (
+      json!({"properties": {"a": {"$ref": "other.json#/User"}}}),
+      "#/properties/a",
+    ),
+    (
+      json!({"properties": {"a": {"items": {"oneOf": [{"type": "string"}, {"type": "integer"}]}, "type": "array"}}}),
+      "#/properties/a/items/oneOf",
+    ),
+    (json!({"$defs": {"Name": {"type": "string"}}}), "#/$defs/Name"),
+    (
+      json!({"$defs": {"User": {"type": "object"}}, "definitions": {"User": {"type": "object"}}}),
+      "#/definitions/User",
+    ),
+  ];
+  for (schema, path) in cases {
+    let err = import_schema(&schema).unwrap_err();
+    assert_eq!(err.path, path, "{}", err);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_union_schema_round_trip() -> Result<()> {
+  let mut provider = ProviderSignature::new("shapes");
+  for (name, field) in [("Circle", "radius"), ("Square", "side")] {
+    provider.types.insert(
+      name,
+      vino_types::StructSignature {
+        name: name.to_owned(),
+        fields: TypeMap::try_from(vec![(field, "f64")])?,
+        ..Default::default()
+      },
+    );
+  }
+  let mut component = vino_types::ComponentSignature::new("area");
+  component.inputs = TypeMap::try_from(vec![
+    ("shape", "union:kind(Circle|Square)"),
+    ("precision", "enum(fast|accurate)"),
+  ])?;
+  component.outputs = TypeMap::try_from(vec![("area", "f64")])?;
+  provider.components.insert("area", component.clone());
+
+  let schema = component_inputs_schema(&component, &provider.types);
+  assert_eq!(
+    schema["properties"]["shape"],
+    json!({"oneOf": [
+      {"$ref": "#/$defs/Circle", "properties": {"kind": {"const": "Circle"}}, "required": ["kind"]},
+      {"$ref": "#/$defs/Square", "properties": {"kind": {"const": "Square"}}, "required": ["kind"]},
+    ]})
+  );
+  assert_eq!(
+    schema["properties"]["precision"],
+    json!({"type": "string", "enum": ["fast", "accurate"]})
+  );
+  assert_eq!(
+    schema["$defs"]["Circle"]["properties"]["kind"],
+    json!({"type": "string"})
+  );
+
+  let (types, inputs) = import_schema(&schema)?;
+  assert_eq!(
+    inputs,
+    TypeMap::try_from(vec![
+      ("shape", "union:kind(#/types/Circle|#/types/Square)"),
+      ("precision", "enum(fast|accurate)"),
+    ])?
+  );
+  assert_eq!(
+    types.get("Circle").map(|d| &d.fields),
+    Some(&TypeMap::try_from(vec![("radius", "f64")])?)
+  );
+
+  let schema = provider_schema(&provider);
+  assert_eq!(
+    schema["$defs"]["Square"]["properties"]["kind"],
+    json!({"type": "string"})
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_union_tag_keeps_field_order() -> Result<()> {
+  let schema = json!({
+    "$defs": {
+      "Circle": {
+        "type": "object",
+        "properties": {
+          "a": {"type": "string"},
+          "b": {"type": "string"},
+          "aa": {"type": "string"},
+          "c": {"type": "string"},
+          "d": {"type": "string"},
+        },
+        "required": ["a", "b", "aa", "c", "d"],
+      },
+    },
+    "type": "object",
+    "properties": {
+      "shape": {"oneOf": [
+        {"$ref": "#/$defs/Circle", "properties": {"aa": {"const": "Circle"}}, "required": ["aa"]},
+      ]},
+    },
+    "required": ["shape"],
+  });
+  let (types, _) = import_schema(&schema)?;
+  let circle = types.get("Circle").unwrap();
+  assert_eq!(circle.fields.names(), vec!["a", "b", "c", "d"]);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_metadata_annotations() -> Result<()> {
+  let mut component = vino_types::ComponentSignature::new("list");
+  component.inputs = TypeMap::try_from(vec![("page_size", "u32"), ("tag", "string?")])?
+    .with_metadata(
+      "page_size",
+      vino_types::Metadata::new()
+        .with_description("Posts per page")
+        .with_default(json!(20)),
+    )
+    .with_metadata("tag", vino_types::Metadata::new().with_deprecated(true));
+
+  let schema = component_inputs_schema(&component, &StructMap::new());
+  assert_eq!(schema["required"], json!([]));
+  assert_eq!(
+    schema["properties"]["page_size"]["description"],
+    json!("Posts per page")
+  );
+  assert_eq!(schema["properties"]["page_size"]["default"], json!(20));
+  assert_eq!(schema["properties"]["tag"]["deprecated"], json!(true));
+
+  let (_, inputs) = import_schema(&schema)?;
+  assert_eq!(inputs, component.inputs);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_schema_order_and_keys() -> Result<()> {
+  let mut component = vino_types::ComponentSignature::new("search");
+  component.inputs = TypeMap::try_from(vec![
+    ("query", "string"),
+    ("limit", "u32"),
+    ("boosts", "{i16: f64}"),
+    ("flags", "{bool: string}"),
+    ("by_id", "{uuid: string}"),
+    ("after", "datetime"),
+  ])?;
+  let schema = component_inputs_schema(&component, &StructMap::new());
+  let properties: Vec<_> = schema["properties"].as_object().unwrap().keys().collect();
+  assert_eq!(properties, vec!["query", "limit", "boosts", "flags", "by_id", "after"]);
+
+  let (_, inputs) = import_schema(&schema)?;
+  assert_eq!(inputs.names(), component.inputs.names());
+  assert_eq!(inputs.get("boosts"), Some(&"{i64: f64}".parse()?));
+  assert_eq!(inputs.get("flags"), Some(&"{bool: string}".parse()?));
+  assert_eq!(inputs.get("by_id"), Some(&"{uuid: string}".parse()?));
+
+  let (types, fields) = import_schema(&json!({
+    "properties": {"item": {"$ref": "#/$defs/a~1b~0c"}},
+    "required": ["item"],
+    "$defs": {"a/b~c": {"type": "object", "properties": {}}},
+  }))?;
+  let reference = TypeSignature::Ref {
+    reference: "#/types/a/b~c".to_owned(),
+  };
+  assert!(types.get("a/b~c").is_some());
+  assert_eq!(fields.get("item"), Some(&reference));
+  assert_eq!(type_schema(&reference), json!({"$ref": "#/$defs/a~1b~0c"}));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_import_hoisted_names() -> Result<()> {
+  let (types, _) = import_schema(&json!({
+    "$defs": {
+      "User": {
+        "type": "object",
+        "properties": {"address": {"type": "object", "properties": {"street": {"type": "string"}}}},
+        "required": ["address"],
+      },
+      "UserAddress": {"type": "object", "properties": {"zip": {"type": "string"}}, "required": ["zip"]},
+    },
+  }))?;
+  let address = match types.get("User").unwrap().fields.get("address") {
+    Some(TypeSignature::Ref { reference }) => reference.trim_start_matches("#/types/").to_owned(),
+    other => panic!("expected a reference, found {:?}", other),
+  };
+  assert_eq!(address, "UserAddress2");
+  assert_eq!(types.get(&address).unwrap().fields.names(), vec!["street"]);
+  assert_eq!(types.get("UserAddress").unwrap().fields.names(), vec!["zip"]);
+  Ok(())
+}
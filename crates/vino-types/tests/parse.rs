@@ -0,0 +1,180 @@
+use std::str::FromStr;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_types::{InternalType, MapWrapper, TypeMap, TypeSignature};
+
+fn list(element: TypeSignature) -> TypeSignature {
+  TypeSignature::List {
+    element: Box::new(element),
+  }
+}
+
+fn optional(option: TypeSignature) -> TypeSignature {
+  TypeSignature::Optional {
+    option: Box::new(option),
+  }
+}
+
+fn map(key: TypeSignature, value: TypeSignature) -> TypeSignature {
+  TypeSignature::Map {
+    key: Box::new(key),
+    value: Box::new(value),
+  }
+}
+
+#[test_log::test]
+fn test_parse_expressions() -> Result<()> {
+  let cases = vec![
+    ("i32", TypeSignature::I32),
+    ("struct", TypeSignature::Struct),
+    ("string[]", list(TypeSignature::String)),
+    ("u32?", optional(TypeSignature::U32)),
+    ("u32?[]", list(optional(TypeSignature::U32))),
+    ("u32[]?", optional(list(TypeSignature::U32))),
+    ("{string: i64}", map(TypeSignature::String, TypeSignature::I64)),
+    (
+      "{ string : {string: bool[]} }?",
+      optional(map(
+        TypeSignature::String,
+        map(TypeSignature::String, list(TypeSignature::Bool)),
+      )),
+    ),
+    (
+      "ref:User",
+      TypeSignature::Ref {
+        reference: "User".to_owned(),
+      },
+    ),
+    (
+      "ref:#/types/ProviderLink[]",
+      list(TypeSignature::Ref {
+        reference: "#/types/ProviderLink".to_owned(),
+      }),
+    ),
+    (
+      "link:provider_id",
+      TypeSignature::Link {
+        provider: Some("provider_id".to_owned()),
+      },
+    ),
+    ("link", TypeSignature::Link { provider: None }),
+    (
+      "{link: string}",
+      map(TypeSignature::Link { provider: None }, TypeSignature::String),
+    ),
+    (
+      "internal:component_input",
+      TypeSignature::Internal(InternalType::ComponentInput),
+    ),
+    ("internal:tx_id", TypeSignature::Internal(InternalType::TxId)),
+    (
+      "enum(fast | accurate)?",
+      optional(TypeSignature::Enum {
+        values: vec!["fast".to_owned(), "accurate".to_owned()],
+      }),
+    ),
+    (
+      r#"enum("very fast"|slow)"#,
+      TypeSignature::Enum {
+        values: vec!["very fast".to_owned(), "slow".to_owned()],
+      },
+    ),
+    (
+      "union:kind(Circle|#/types/Square)[]",
+      list(TypeSignature::Union {
+        tag: "kind".to_owned(),
+        variants: vec!["Circle".to_owned(), "#/types/Square".to_owned()],
+      }),
+    ),
+  ];
+
+  for (src, expected) in cases {
+    let actual = TypeSignature::from_str(src)?;
+    assert_eq!(actual, expected, "parsing '{}'", src);
+    assert_eq!(
+      TypeSignature::from_str(&actual.to_string())?,
+      expected,
+      "round trip of '{}'",
+      src
+    );
+  }
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_display() -> Result<()> {
+  assert_eq!(list(optional(TypeSignature::U32)).to_string(), "u32?[]");
+  assert_eq!(
+    map(TypeSignature::String, TypeSignature::I64).to_string(),
+    "{string: i64}"
+  );
+  assert_eq!(
+    TypeSignature::Link {
+      provider: Some("kv".to_owned())
+    }
+    .to_string(),
+    "link:kv"
+  );
+  assert_eq!(
+    TypeSignature::Enum {
+      values: vec!["a".to_owned(), "b c".to_owned()]
+    }
+    .to_string(),
+    r#"enum(a|"b c")"#
+  );
+  let quoted = TypeSignature::Enum {
+    values: vec![r#"say "hi""#.to_owned(), r"back\slash".to_owned()],
+  };
+  assert_eq!(quoted.to_string(), r#"enum("say \"hi\""|"back\\slash")"#);
+  assert_eq!(TypeSignature::from_str(&quoted.to_string())?, quoted);
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_parse_errors() -> Result<()> {
+  let cases = vec![
+    ("", 0),
+    ("int", 0),
+    ("string[", 6),
+    ("{string i64}", 8),
+    ("{string: i64", 12),
+    ("string string", 7),
+    ("ref", 3),
+    ("ref:", 4),
+    ("internal:nothing", 9),
+    ("enum", 4),
+    ("enum()", 5),
+    ("enum(a|", 7),
+    (r#"enum("a"#, 7),
+    (r#"enum("a\x")"#, 8),
+    ("union(A|B)", 5),
+  ];
+
+  for (src, position) in cases {
+    let err = TypeSignature::from_str(src).expect_err(src);
+    assert_eq!(err.position(), position, "error position for '{}': {}", src, err);
+  }
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_typemap_from_expressions() -> Result<()> {
+  let types = TypeMap::try_from(vec![
+    ("names", "string[]"),
+    ("counts", "{string: u64}"),
+    ("kv", "link:keyvalue"),
+  ])?;
+
+  assert_eq!(types.get("names"), Some(&list(TypeSignature::String)));
+  assert_eq!(
+    types.get("counts"),
+    Some(&map(TypeSignature::String, TypeSignature::U64))
+  );
+  assert_eq!(types.len(), 3);
+
+  Ok(())
+}
@@ -0,0 +1,368 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::jsonschema::{import_schema, type_schema};
+use vino_types::{is_assignable, Decimal, Duration, MapWrapper, Sampler, StructMap, TypeSignature, Url, Uuid};
+
+#[test_log::test]
+fn test_names() -> Result<()> {
+  let cases = vec![
+    ("uuid", TypeSignature::Uuid),
+    ("duration", TypeSignature::Duration),
+    ("decimal", TypeSignature::Decimal),
+    ("url", TypeSignature::Url),
+  ];
+  for (name, sig) in cases {
+    assert_eq!(name.parse::<TypeSignature>()?, sig);
+    assert_eq!(sig.to_string(), name);
+    assert_eq!(serde_json::to_value(&sig)?, json!({ "type": name }));
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_uuid() -> Result<()> {
+  let canonical = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+  let uuid: Uuid = canonical.parse()?;
+  assert_eq!(uuid.to_string(), canonical);
+  assert_eq!(uuid.version(), 4);
+  for input in [
+    "67E55044-10B1-426F-9247-BB680E5FE0C8",
+    "67e5504410b1426f9247bb680e5fe0c8",
+    "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+    "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+  ] {
+    assert_eq!(input.parse::<Uuid>()?, uuid, "{}", input);
+  }
+  for invalid in [
+    "67e55044-10b1-426f-9247-bb680e5fe0c",
+    "67e55044_10b1_426f_9247_bb680e5fe0c8",
+    "67e55044-10b1-426f-9247-bb680e5fe0cg",
+    "",
+  ] {
+    assert!(invalid.parse::<Uuid>().is_err(), "{}", invalid);
+  }
+  assert_eq!(Uuid::from_random_bytes([0xff; 16]).version(), 4);
+  assert_eq!(Uuid::NIL.to_string(), "00000000-0000-0000-0000-000000000000");
+  Ok(())
+}
+
+#[test_log::test]
+fn test_duration() -> Result<()> {
+  let cases = vec![
+    ("PT0S", 0, 0, "PT0S"),
+    ("PT1M0.25S", 60, 250_000_000, "PT1M0.25S"),
+    ("P1W2DT3H4M5.5S", 788_645, 500_000_000, "P9DT3H4M5.5S"),
+    ("PT36H", 129_600, 0, "P1DT12H"),
+    ("P1D", 86_400, 0, "P1D"),
+    ("PT0.000000001S", 0, 1, "PT0.000000001S"),
+  ];
+  for (input, seconds, nanos, canonical) in cases {
+    let duration: Duration = input.parse()?;
+    assert_eq!(
+      (duration.as_secs(), duration.subsec_nanos()),
+      (seconds, nanos),
+      "{}",
+      input
+    );
+    assert_eq!(duration.to_string(), canonical);
+    assert_eq!(canonical.parse::<Duration>()?, duration);
+  }
+  for invalid in ["", "P", "PT", "1H", "PT1H1H", "PT1S1M", "P1.5D", "PT1.S", "P1H", "PT1D"] {
+    assert!(invalid.parse::<Duration>().is_err(), "{}", invalid);
+  }
+  assert_eq!(
+    "P1Y".parse::<Duration>().unwrap_err().to_string(),
+    "Invalid duration: 'P1Y' has years or months, which have no fixed length"
+  );
+
+  let std = std::time::Duration::from_millis(1500);
+  assert_eq!(Duration::from(std), Duration::from_millis(1500));
+  assert_eq!(std::time::Duration::from(Duration::from_millis(1500)), std);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_decimal() -> Result<()> {
+  let cases = vec![
+    ("42", "42"),
+    ("-12.50", "-12.50"),
+    ("+.5", "0.5"),
+    ("0.00", "0.00"),
+    ("-0", "0"),
+    ("1.25e-3", "0.00125"),
+    ("1.5E3", "1500"),
+    (
+      "123456789012345678901234567890.123456789",
+      "123456789012345678901234567890.123456789",
+    ),
+  ];
+  for (input, canonical) in cases {
+    let decimal: Decimal = input.parse()?;
+    assert_eq!(decimal.to_string(), canonical, "{}", input);
+    assert_eq!(canonical.parse::<Decimal>()?, decimal);
+  }
+  for invalid in ["", "-", ".", "1.2.3", "1e", "1e1001", "0x10", "1 000", "NaN"] {
+    assert!(invalid.parse::<Decimal>().is_err(), "{}", invalid);
+  }
+
+  let parse = |s: &str| s.parse::<Decimal>().unwrap();
+  assert_eq!(parse("12.50"), parse("12.5"));
+  assert_eq!(parse("0.00"), Decimal::ZERO);
+  assert_eq!(parse("12.50").normalize().to_string(), "12.5");
+  let mut ordered = [
+    parse("1.5"),
+    parse("-2"),
+    parse("0.15"),
+    parse("10"),
+    parse("-0.5"),
+    parse("0"),
+  ];
+  ordered.sort();
+  let ordered: Vec<_> = ordered.iter().map(ToString::to_string).collect();
+  assert_eq!(ordered, vec!["-2", "-0.5", "0", "0.15", "1.5", "10"]);
+
+  assert_eq!(Decimal::from(-42_i64).to_string(), "-42");
+  assert_eq!(Decimal::try_from(0.1)?.to_string(), "0.1");
+  assert!(Decimal::try_from(f64::NAN).is_err());
+  assert_eq!(parse("-0.25").to_f64(), -0.25);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_url() -> Result<()> {
+  let url: Url = "HTTPS://user@vino.dev:8080/docs?page=2#top".parse()?;
+  assert_eq!(url.as_str(), "https://user@vino.dev:8080/docs?page=2#top");
+  assert_eq!(url.scheme(), "https");
+  assert_eq!(url.host(), Some("vino.dev"));
+  assert_eq!("http://[::1]:80/".parse::<Url>()?.host(), Some("[::1]"));
+  assert_eq!("mailto:someone@vino.dev".parse::<Url>()?.host(), None);
+  assert_eq!("file:///etc/hosts".parse::<Url>()?.host(), None);
+  assert_eq!("https://VINO.dev".parse::<Url>()?, "https://vino.dev".parse::<Url>()?);
+  assert_eq!(
+    "https://vino.dev/a b".parse::<Url>()?.as_str(),
+    "https://vino.dev/a%20b"
+  );
+
+  for invalid in [
+    "vino.dev",
+    "/docs",
+    "https:",
+    "https://",
+    "1http://vino.dev",
+    "http://vino dev/",
+  ] {
+    assert!(invalid.parse::<Url>().is_err(), "{}", invalid);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_json() -> Result<()> {
+  let uuid: Uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse()?;
+  let duration = Duration::new(90, 500_000_000);
+  let decimal: Decimal = "-12.50".parse()?;
+  let url: Url = "https://vino.dev/".parse()?;
+  assert_eq!(
+    serde_json::to_value((uuid, duration, &decimal, &url))?,
+    json!([
+      "67e55044-10b1-426f-9247-bb680e5fe0c8",
+      "PT1M30.5S",
+      "-12.50",
+      "https://vino.dev/"
+    ])
+  );
+  assert_eq!(serde_json::from_value::<Duration>(json!("PT1M30.5S"))?, duration);
+  assert_eq!(serde_json::from_value::<Decimal>(json!(-12))?, "-12".parse()?);
+  assert!(serde_json::from_value::<Decimal>(json!(-12.5)).is_err());
+  assert!(serde_json::from_value::<Url>(json!("vino.dev")).is_err());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_messagepack() -> Result<()> {
+  let uuid: Uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse()?;
+  let bytes = vino_codec::messagepack::serialize(&uuid)?;
+  assert_eq!(bytes, vino_codec::messagepack::serialize(&uuid.to_string())?);
+  assert_eq!(vino_codec::messagepack::deserialize::<Uuid>(&bytes)?, uuid);
+  // Binary UUIDs are still read.
+  let mut bytes = vec![0xc4, 16];
+  bytes.extend(uuid.as_bytes());
+  assert_eq!(vino_codec::messagepack::deserialize::<Uuid>(&bytes)?, uuid);
+
+  let duration = Duration::new(90, 5);
+  let bytes = vino_codec::messagepack::serialize(&duration)?;
+  assert_eq!(bytes, vino_codec::messagepack::serialize(&"PT1M30.000000005S")?);
+  assert_eq!(vino_codec::messagepack::deserialize::<Duration>(&bytes)?, duration);
+  // Arrays of seconds and nanoseconds are still read.
+  assert_eq!(
+    vino_codec::messagepack::deserialize::<Duration>(&[0x92, 90, 5])?,
+    duration
+  );
+
+  let decimal: Decimal = "0.10".parse()?;
+  let bytes = vino_codec::messagepack::serialize(&decimal)?;
+  assert_eq!(bytes, vino_codec::messagepack::serialize(&"0.10")?);
+  assert_eq!(
+    vino_codec::messagepack::deserialize::<Decimal>(&bytes)?.to_string(),
+    "0.10"
+  );
+
+  // Strings are read too, so values coerced from JSON still deserialize.
+  let bytes = vino_codec::messagepack::serialize(&"PT1M30S")?;
+  assert_eq!(
+    vino_codec::messagepack::deserialize::<Duration>(&bytes)?,
+    Duration::new(90, 0)
+  );
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_as_json() -> Result<()> {
+  let uuid: Uuid = "67e55044-10b1-426f-9247-bb680e5fe0c8".parse()?;
+  let duration = Duration::new(1, 500_000_000);
+  let url: Url = "https://vino.dev/docs".parse()?;
+  let mut payload = vino_transport::MessageTransport::success(&(uuid, duration, &url));
+  payload.to_messagepack();
+  let json = payload.as_json();
+  assert_eq!(
+    json["value"],
+    json!([
+      "67e55044-10b1-426f-9247-bb680e5fe0c8",
+      "PT1.5S",
+      "https://vino.dev/docs"
+    ])
+  );
+  let (from_json, from_json_duration, from_json_url): (Uuid, Duration, Url) =
+    serde_json::from_value(json["value"].clone())?;
+  assert_eq!((from_json, from_json_duration, from_json_url), (uuid, duration, url));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_validate() -> Result<()> {
+  let types = StructMap::new();
+  let uuid = Uuid::from_random_bytes([7; 16]);
+  let bytes = vino_codec::messagepack::serialize(&(uuid, Duration::new(1, 0)))?;
+  let value: serde_value::Value = vino_codec::messagepack::deserialize(&bytes)?;
+  assert_eq!(
+    "(uuid, duration)".parse::<TypeSignature>()?.validate(&value, &types),
+    Ok(())
+  );
+
+  let cases = vec![
+    (TypeSignature::Uuid, json!(uuid), json!("not-a-uuid")),
+    (TypeSignature::Duration, json!("PT1S"), json!(1)),
+    (TypeSignature::Decimal, json!("1.50"), json!(1.5)),
+    (TypeSignature::Url, json!("https://vino.dev"), json!("vino.dev")),
+  ];
+  for (sig, valid, invalid) in cases {
+    assert_eq!(sig.validate_json(&valid, &types), Ok(()), "{} accepts {}", sig, valid);
+    assert!(
+      sig.validate_json(&invalid, &types).is_err(),
+      "{} rejects {}",
+      sig,
+      invalid
+    );
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_coerce() -> Result<()> {
+  let types = StructMap::new();
+  let cases = vec![
+    (
+      TypeSignature::Uuid,
+      "67E55044-10B1-426F-9247-BB680E5FE0C8",
+      json!("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+    ),
+    (TypeSignature::Duration, "PT90S", json!("PT1M30S")),
+    (TypeSignature::Duration, "90", json!("PT1M30S")),
+    (TypeSignature::Duration, "0.5", json!("PT0.5S")),
+    (TypeSignature::Decimal, "12.50", json!("12.50")),
+    (TypeSignature::Decimal, "1e2", json!("100")),
+    (TypeSignature::Url, "HTTP://VINO.dev", json!("http://vino.dev/")),
+  ];
+  for (sig, raw, expected) in cases {
+    assert_eq!(sig.coerce_str(raw, &types)?, expected, "{} from {}", sig, raw);
+  }
+  assert_eq!(TypeSignature::Decimal.coerce(&json!(0.1), &types)?, json!("0.1"));
+
+  let errors = vec![
+    (TypeSignature::Uuid, "nope", "$: 'nope' is not a UUID"),
+    (TypeSignature::Duration, "P1M", "$: 'P1M' is not an ISO 8601 duration"),
+    (TypeSignature::Duration, "-1", "$: -1 is not a number of seconds"),
+    (TypeSignature::Decimal, "ten", "$: 'ten' is not a decimal number"),
+    (TypeSignature::Url, "vino.dev", "$: 'vino.dev' is not a URL"),
+  ];
+  for (sig, raw, message) in errors {
+    assert_eq!(sig.coerce_str(raw, &types).unwrap_err().to_string(), message);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_sample() -> Result<()> {
+  let types = StructMap::new();
+  for sig in [
+    TypeSignature::Uuid,
+    TypeSignature::Duration,
+    TypeSignature::Decimal,
+    TypeSignature::Url,
+  ] {
+    let minimal = sig.sample(&types)?;
+    assert_eq!(sig.validate_json(&minimal, &types), Ok(()), "minimal sample of {}", sig);
+    let mut sampler = Sampler::random(3);
+    for _ in 0..20 {
+      let value = sampler.sample(&sig, &types)?;
+      assert_eq!(sig.validate_json(&value, &types), Ok(()), "random sample of {}", sig);
+    }
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_schema() -> Result<()> {
+  let sigs = vec![
+    ("id", TypeSignature::Uuid, "uuid"),
+    ("timeout", TypeSignature::Duration, "duration"),
+    ("price", TypeSignature::Decimal, "decimal"),
+    ("homepage", TypeSignature::Url, "uri"),
+  ];
+  let mut properties = serde_json::Map::new();
+  for (field, sig, format) in &sigs {
+    let schema = type_schema(sig);
+    assert_eq!(schema, json!({ "type": "string", "format": format }));
+    properties.insert((*field).to_owned(), schema);
+  }
+  let (_, fields) = import_schema(&json!({
+    "type": "object",
+    "properties": properties,
+    "required": ["id", "timeout", "price", "homepage"],
+  }))?;
+  for (field, sig, _) in sigs {
+    assert_eq!(fields.get(field), Some(&sig), "import of {}", field);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_assignable() -> Result<()> {
+  let cases = vec![
+    ("u64", "decimal", true),
+    ("i8", "decimal", true),
+    ("f64", "decimal", false),
+    ("decimal", "string", true),
+    ("url", "string", true),
+    ("uuid", "string", false),
+    ("string", "uuid", false),
+    ("duration", "u64", false),
+  ];
+  for (from, to, expected) in cases {
+    let (from_sig, to_sig) = (from.parse()?, to.parse()?);
+    assert_eq!(is_assignable(&from_sig, &to_sig), expected, "{} -> {}", from, to);
+  }
+  Ok(())
+}
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use vino_types::{
+  ComponentSignature, MapWrapper, ProviderSignature, ResolvedType, SignatureError, StructSignature, TypeMap,
+  TypeSignature,
+};
+
+fn provider(types: Vec<(&str, Vec<(&str, &str)>)>, inputs: Vec<(&str, &str)>) -> Result<ProviderSignature> {
+  let mut provider = ProviderSignature::new("test");
+  for (name, fields) in types {
+    provider.types.insert(
+      name,
+      StructSignature {
+        name: name.to_owned(),
+        fields: TypeMap::try_from(fields)?,
+        ..Default::default()
+      },
+    );
+  }
+  provider.components.insert(
+    "get",
+    ComponentSignature {
+      name: "get".to_owned(),
+      inputs: TypeMap::try_from(inputs)?,
+      outputs: TypeMap::new(),
+      ..Default::default()
+    },
+  );
+  Ok(provider)
+}
+
+#[test_log::test]
+fn test_valid() -> Result<()> {
+  let sig = provider(
+    vec![
+      ("Blog", vec![("author", "ref:#/types/User")]),
+      ("User", vec![("name", "string")]),
+    ],
+    vec![("blog", "ref:Blog"), ("users", "{string: ref:User[]}")],
+  )?;
+  assert_eq!(sig.validate(), Ok(()));
+  assert_eq!(sig.resolve("#/types/User").map(|s| s.name.as_str()), Some("User"));
+  assert!(sig.resolve("Missing").is_none());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_dangling() -> Result<()> {
+  let sig = provider(
+    vec![("Blog", vec![("author", "ref:#/types/Usr")])],
+    vec![("blog", "ref:Blgo[]?"), ("limit", "u32")],
+  )?;
+  assert_eq!(
+    sig.validate(),
+    Err(vec![
+      SignatureError::DanglingRef {
+        path: "components.get.inputs.blog".to_owned(),
+        reference: "Blgo".to_owned(),
+      },
+      SignatureError::DanglingRef {
+        path: "types.Blog.fields.author".to_owned(),
+        reference: "#/types/Usr".to_owned(),
+      },
+    ])
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_dangling_config() -> Result<()> {
+  let mut sig = provider(vec![("Auth", vec![("token", "string")])], vec![])?;
+  sig.config = TypeMap::try_from(vec![("auth", "ref:Auth"), ("proxy", "ref:Proxy?")])?;
+  sig.components.get_inner_mut()["get"].config = TypeMap::try_from(vec![("retry", "{string: ref:Retry}")])?;
+  assert_eq!(
+    sig.validate(),
+    Err(vec![
+      SignatureError::DanglingRef {
+        path: "components.get.config.retry".to_owned(),
+        reference: "Retry".to_owned(),
+      },
+      SignatureError::DanglingRef {
+        path: "config.proxy".to_owned(),
+        reference: "Proxy".to_owned(),
+      },
+    ])
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_recursive() -> Result<()> {
+  let sig = provider(
+    vec![
+      ("Tree", vec![("children", "ref:Tree[]")]),
+      ("Node", vec![("next", "ref:Node?"), ("links", "{string: ref:Node}")]),
+      ("User", vec![("best", "ref:Post")]),
+      ("Post", vec![("author", "(ref:#/types/User, u8)")]),
+      ("Loop", vec![("again", "ref:Loop")]),
+      ("Leaf", vec![("value", "string")]),
+    ],
+    vec![],
+  )?;
+  let errors = sig.validate().unwrap_err();
+  assert_eq!(
+    errors,
+    vec![
+      SignatureError::RecursiveType {
+        cycle: vec!["Loop".to_owned()],
+      },
+      SignatureError::RecursiveType {
+        cycle: vec!["Post".to_owned(), "User".to_owned()],
+      },
+    ]
+  );
+  assert_eq!(errors[1].to_string(), "recursive type: Post -> User -> Post");
+  assert_eq!(
+    sig.inline(&"ref:Tree".parse()?),
+    Err(SignatureError::RecursiveType {
+      cycle: vec!["Tree".to_owned()],
+    })
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_recursive_through_collections() -> Result<()> {
+  let sig = provider(
+    vec![
+      ("Tree", vec![("children", "ref:Tree[]")]),
+      ("Node", vec![("next", "ref:Node?"), ("links", "{string: ref:Node}")]),
+    ],
+    vec![("tree", "ref:Tree")],
+  )?;
+  assert_eq!(sig.validate(), Ok(()));
+  Ok(())
+}
+
+#[test_log::test]
+fn test_inline_by_key() -> Result<()> {
+  let mut sig = provider(vec![("Inner", vec![("value", "string")])], vec![])?;
+  sig.types.insert(
+    "Outer",
+    StructSignature {
+      name: "Inner".to_owned(),
+      fields: TypeMap::try_from(vec![("inner", "ref:Inner")])?,
+      ..Default::default()
+    },
+  );
+  assert_eq!(sig.validate(), Ok(()));
+  assert!(sig.inline(&"ref:Outer".parse()?).is_ok());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_inline() -> Result<()> {
+  let sig = provider(
+    vec![
+      ("Blog", vec![("author", "ref:#/types/User?")]),
+      ("User", vec![("name", "string")]),
+    ],
+    vec![],
+  )?;
+  let user = ResolvedType::Struct {
+    name: "User".to_owned(),
+    fields: BTreeMap::from([("name".to_owned(), ResolvedType::Type(TypeSignature::String))]),
+  };
+  let blog = ResolvedType::Struct {
+    name: "Blog".to_owned(),
+    fields: BTreeMap::from([("author".to_owned(), ResolvedType::Optional { option: Box::new(user) })]),
+  };
+  assert_eq!(
+    sig.inline(&"ref:Blog[]".parse()?)?,
+    ResolvedType::List {
+      element: Box::new(blog)
+    }
+  );
+  assert_eq!(
+    sig.inline(&"{string: ref:Nope}".parse()?),
+    Err(SignatureError::DanglingRef {
+      path: "$[]".to_owned(),
+      reference: "Nope".to_owned(),
+    })
+  );
+  Ok(())
+}
@@ -0,0 +1,135 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::{MapWrapper, ProviderSignature, SampleError, Sampler, StructMap, TypeSignature};
+
+static SIGNATURE: &str = r##"{
+  "name": "shapes",
+  "types": {
+    "Circle": {
+      "name": "Circle",
+      "fields": { "radius": { "type": "f64" }, "label": { "type": "optional", "option": { "type": "string" } } }
+    },
+    "Square": {
+      "name": "Square",
+      "fields": { "side": { "type": "u32" }, "units": { "type": "enum", "values": ["cm", "in"], "default": "in" } }
+    },
+    "Tree": {
+      "name": "Tree",
+      "fields": {
+        "name": { "type": "string" },
+        "children": { "type": "list", "element": { "type": "ref", "ref": "#/types/Tree" } }
+      }
+    }
+  },
+  "components": {
+    "draw": {
+      "name": "draw",
+      "inputs": {
+        "shapes": { "type": "list", "element": { "type": "union", "tag": "kind", "variants": ["Circle", "Square"] } },
+        "at": { "type": "datetime" },
+        "weights": { "type": "map", "key": { "type": "u8" }, "value": { "type": "i16" } },
+        "tree": { "type": "ref", "ref": "#/types/Tree" },
+        "data": { "type": "bytes" },
+        "dry_run": { "type": "bool", "default": true },
+        "scale": { "type": "optional", "option": { "type": "f32" } }
+      },
+      "outputs": {}
+    }
+  }
+}"##;
+
+fn signature() -> Result<ProviderSignature> {
+  Ok(serde_json::from_str(SIGNATURE)?)
+}
+
+#[test_log::test]
+fn test_minimal() -> Result<()> {
+  let signature = signature()?;
+  let types = &signature.types;
+  let cases = vec![
+    ("u64", json!(0)),
+    ("string", json!("")),
+    ("datetime", json!("1970-01-01T00:00:00Z")),
+    ("enum(b|a)", json!("b")),
+    ("string?", json!(null)),
+    ("ref:Circle", json!({ "radius": 0.0 })),
+    ("ref:Square", json!({ "side": 0, "units": "in" })),
+    (
+      "union:kind(Square|Circle)",
+      json!({ "kind": "Square", "side": 0, "units": "in" }),
+    ),
+    ("{string: u8}", json!({})),
+  ];
+  for (sig, expected) in cases {
+    let sig: TypeSignature = sig.parse()?;
+    assert_eq!(sig.sample(types)?, expected, "{}", sig);
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_random() -> Result<()> {
+  let signature = signature()?;
+  let component = signature.get_component("draw").unwrap();
+  for seed in 0..50 {
+    for (port, sig) in component.inputs.inner() {
+      let value = Sampler::random(seed).sample(sig, &signature.types)?;
+      assert_eq!(
+        sig.validate_json(&value, &signature.types),
+        Ok(()),
+        "{} (seed {})",
+        port,
+        seed
+      );
+    }
+  }
+
+  let sig: TypeSignature = "ref:Tree".parse()?;
+  let first = Sampler::random(7).sample(&sig, &signature.types)?;
+  assert_eq!(Sampler::random(7).sample(&sig, &signature.types)?, first);
+  assert_ne!(Sampler::random(8).sample(&sig, &signature.types)?, first);
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_sample_inputs() -> Result<()> {
+  let signature = signature()?;
+  let component = signature.get_component("draw").unwrap();
+
+  let payload = component.sample_inputs(&mut Sampler::minimal(), &signature.types)?;
+  assert_eq!(component.validate_inputs(&payload, &signature.types), Ok(()));
+  assert!(payload.contains("dry_run"));
+  assert!(!payload.contains("scale"));
+
+  for seed in 0..50 {
+    let payload = component.sample_inputs(&mut Sampler::random(seed), &signature.types)?;
+    assert_eq!(component.validate_inputs(&payload, &signature.types), Ok(()));
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_errors() -> Result<()> {
+  let mut types = StructMap::new();
+  types.insert(
+    "Loop",
+    serde_json::from_value(json!({ "name": "Loop", "fields": { "next": { "type": "ref", "ref": "Loop" } } }))?,
+  );
+  let result = "ref:Loop".parse::<TypeSignature>()?.sample(&types);
+  assert!(matches!(result, Err(SampleError { message, .. }) if message.contains("nests too deeply")));
+
+  let result = "ref:Missing".parse::<TypeSignature>()?.sample(&types);
+  assert_eq!(
+    result,
+    Err(SampleError {
+      path: "$".to_owned(),
+      message: "unresolved reference 'Missing'".to_owned()
+    })
+  );
+
+  let result = TypeSignature::Enum { values: vec![] }.sample(&types);
+  assert!(result.is_err());
+  Ok(())
+}
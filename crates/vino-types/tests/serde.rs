@@ -1,8 +1,12 @@
 use std::fs::read_to_string;
 
 use anyhow::Result;
+use indexmap::IndexMap;
 use pretty_assertions::assert_eq;
-use vino_types::{InternalType, ProviderSignature, TypeSignature};
+use vino_types::{
+  ComponentMap, ComponentSignature, HostedType, InternalType, MapWrapper, Metadata, NetworkSignature,
+  ProviderDependency, ProviderSignature, SchematicSignature, StructMap, TypeMap, TypeSignature,
+};
 
 #[test_log::test]
 fn test_deserialize() -> Result<()> {
@@ -72,10 +76,216 @@ fn test_serde_all() -> Result<()> {
     TypeSignature::Link {
       provider: Some("link-test".to_owned()),
     },
+    TypeSignature::Enum {
+      values: vec!["fast".to_owned(), "accurate".to_owned()],
+    },
+    TypeSignature::Union {
+      tag: "kind".to_owned(),
+      variants: vec!["#/types/Circle".to_owned()],
+    },
   ];
 
   let json = serde_json::to_string(&types)?;
   println!("{}", json);
+  let round_trip: Vec<TypeSignature> = serde_json::from_str(&json)?;
+  assert_eq!(round_trip, types);
+
+  let value = serde_json::to_value(&types[types.len() - 1])?;
+  assert_eq!(
+    value,
+    serde_json::json!({"type": "union", "tag": "kind", "variants": ["#/types/Circle"]})
+  );
 
   Ok(())
 }
+
+#[test_log::test]
+fn test_metadata() -> Result<()> {
+  let src = serde_json::json!({
+    "name": "list",
+    "description": "List the blog's posts.",
+    "deprecated": true,
+    "inputs": {
+      "page_size": {"type": "u32", "description": "Posts per page", "default": 20},
+      "tag": {"type": "optional", "option": {"type": "string"}, "deprecated": true},
+      "author": {"type": "string"}
+    },
+    "outputs": {}
+  });
+  let sig: ComponentSignature = serde_json::from_value(src.clone())?;
+  assert_eq!(sig.description.as_deref(), Some("List the blog's posts."));
+  assert!(sig.deprecated);
+  assert_eq!(sig.inputs.get("page_size"), Some(&TypeSignature::U32));
+  assert_eq!(
+    sig.inputs.metadata("page_size"),
+    Some(
+      &Metadata::new()
+        .with_description("Posts per page")
+        .with_default(serde_json::json!(20))
+    )
+  );
+  assert_eq!(sig.inputs.metadata("tag"), Some(&Metadata::new().with_deprecated(true)));
+  assert_eq!(sig.inputs.metadata("author"), None);
+  assert_eq!(serde_json::to_value(&sig)?, src);
+
+  let bytes = vino_codec::messagepack::serialize(&sig)?;
+  let from_msgpack: ComponentSignature = vino_codec::messagepack::deserialize(&bytes)?;
+  assert_eq!(from_msgpack, sig);
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_metadata_follows_entries() -> Result<()> {
+  let mut map = TypeMap::try_from(vec![("x", "string")])?
+    .with_metadata("x", Metadata::new().with_default(serde_json::json!("hello")));
+  map.remove("x");
+  map.insert("x", TypeSignature::U8);
+  assert_eq!(map.metadata("x"), None);
+
+  map.set_metadata("x", Metadata::new().with_default(serde_json::json!(1)));
+  MapWrapper::insert(&mut map, "x", TypeSignature::String);
+  assert_eq!(map.metadata("x"), None);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_invalid_default() -> Result<()> {
+  let src = serde_json::json!({
+    "name": "list",
+    "inputs": {
+      "page_size": {"type": "u8", "default": 300},
+      "author": {"type": "ref", "ref": "#/types/User", "default": {"name": "anon"}}
+    },
+    "outputs": {}
+  });
+  let err = serde_json::from_value::<ComponentSignature>(src).unwrap_err();
+  assert!(
+    err
+      .to_string()
+      .contains("invalid default for 'page_size': $: expected u8, found"),
+    "{}",
+    err
+  );
+
+  let src = serde_json::json!({
+    "name": "list",
+    "inputs": {
+      "author": {"type": "ref", "ref": "#/types/User", "default": {"name": "anon"}}
+    },
+    "outputs": {}
+  });
+  assert!(serde_json::from_value::<ComponentSignature>(src).is_ok());
+  Ok(())
+}
+
+#[test_log::test]
+fn test_declared_order() -> Result<()> {
+  let src = r#"{"name":"search","inputs":{"query":{"type":"string"},"limit":{"type":"u32"},"after":{"type":"datetime"}},"outputs":{"results":{"type":"list","element":{"type":"string"}},"cursor":{"type":"string"}}}"#;
+  let sig: ComponentSignature = serde_json::from_str(src)?;
+  assert_eq!(sig.inputs.names(), vec!["query", "limit", "after"]);
+  assert_eq!(sig.outputs.names(), vec!["results", "cursor"]);
+  assert_eq!(serde_json::to_string(&sig)?, src);
+
+  let bytes = vino_codec::messagepack::serialize(&sig)?;
+  let from_msgpack: ComponentSignature = vino_codec::messagepack::deserialize(&bytes)?;
+  assert_eq!(from_msgpack.inputs.names(), vec!["query", "limit", "after"]);
+
+  Ok(())
+}
+
+#[test_log::test]
+fn test_hosted_types() -> Result<()> {
+  let mut schematic = SchematicSignature::new("greet");
+  schematic.inputs = TypeMap::try_from(vec![("name", "string")])?;
+  schematic.outputs = TypeMap::try_from(vec![("greeting", "string")])?;
+  schematic.providers = vec![
+    ProviderDependency::new("strings").with_version("0.2.1"),
+    ProviderDependency::new("log"),
+  ];
+  let mut network = NetworkSignature::new("hello");
+  network.schematics.insert("greet", schematic.clone());
+  network.providers = schematic.providers.clone();
+
+  let hosted = HostedType::Schematic(schematic.clone());
+  assert_eq!(hosted.get_name(), &Some("greet".to_owned()));
+  let json = serde_json::to_value(&hosted)?;
+  assert_eq!(
+    json,
+    serde_json::json!({"Schematic": {
+      "name": "greet",
+      "inputs": {"name": {"type": "string"}},
+      "outputs": {"greeting": {"type": "string"}},
+      "providers": [{"namespace": "strings", "version": "0.2.1"}, {"namespace": "log"}]
+    }})
+  );
+  assert_eq!(serde_json::from_value::<HostedType>(json)?, hosted);
+
+  let hosted = HostedType::Network(network);
+  assert_eq!(hosted.get_name(), &Some("hello".to_owned()));
+  let bytes = vino_codec::messagepack::serialize(&hosted)?;
+  let from_msgpack: HostedType = vino_codec::messagepack::deserialize(&bytes)?;
+  assert_eq!(from_msgpack, hosted);
+
+  let component = schematic.as_component();
+  assert_eq!(component.name, "greet");
+  assert_eq!(component.inputs, schematic.inputs);
+  Ok(())
+}
+
+#[test_log::test]
+fn test_map_order() -> Result<()> {
+  let mut map = TypeMap::try_from(vec![("query", "string"), ("limit", "u32"), ("after", "datetime")])?;
+  map.insert("limit", TypeSignature::U64);
+  assert_eq!(
+    map.names(),
+    vec!["query", "limit", "after"],
+    "replacing keeps the position"
+  );
+  map.remove("query");
+  map.insert("query", TypeSignature::String);
+  assert_eq!(map.names(), vec!["limit", "after", "query"]);
+  map.get_inner_mut().insert("before".to_owned(), TypeSignature::Datetime);
+  assert_eq!(map.names(), vec!["limit", "after", "query", "before"]);
+
+  let components = ComponentMap(IndexMap::from([
+    ("walk".to_owned(), ComponentSignature::new("walk")),
+    ("add".to_owned(), ComponentSignature::new("add")),
+  ]));
+  assert_eq!(components.names(), vec!["walk", "add"]);
+  let json = serde_json::to_string(&components)?;
+  assert!(
+    json.find("\"walk\"") < json.find("\"add\""),
+    "serialized in insertion order: {}",
+    json
+  );
+  let from_json: ComponentMap = serde_json::from_str(&json)?;
+  assert_eq!(from_json.names(), vec!["walk", "add"]);
+  Ok(())
+}
+
+/// A map implemented outside this crate against the [MapWrapper] trait.
+struct Ports(IndexMap<String, TypeSignature>);
+
+impl MapWrapper<TypeSignature> for Ports {
+  fn get_inner_owned(self) -> IndexMap<String, TypeSignature> {
+    self.0
+  }
+
+  fn get_inner(&self) -> &IndexMap<String, TypeSignature> {
+    &self.0
+  }
+
+  fn get_inner_mut(&mut self) -> &mut IndexMap<String, TypeSignature> {
+    &mut self.0
+  }
+}
+
+#[test_log::test]
+fn test_map_wrapper_implementor() {
+  let mut ports = Ports(IndexMap::new());
+  ports.insert("b", TypeSignature::Bool);
+  ports.insert("a", TypeSignature::String);
+  assert_eq!(ports.names(), vec!["b", "a"]);
+  assert_eq!(ports.into_inner().len(), 2);
+}
@@ -0,0 +1,274 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use vino_types::{MapWrapper, StructMap, StructSignature, TypeMap, TypeSignature};
+
+fn types() -> Result<StructMap> {
+  let mut types = StructMap::new();
+  types.insert(
+    "User",
+    StructSignature {
+      name: "User".to_owned(),
+      fields: TypeMap::try_from(vec![("name", "string"), ("age", "u8"), ("nickname", "string?")])?,
+      ..Default::default()
+    },
+  );
+  types.insert(
+    "Bot",
+    StructSignature {
+      name: "Bot".to_owned(),
+      fields: TypeMap::try_from(vec![("model", "string")])?,
+      ..Default::default()
+    },
+  );
+  Ok(types)
+}
+
+fn error_paths(sig: &str, value: serde_json::Value) -> Result<Vec<String>> {
+  let sig: TypeSignature = sig.parse()?;
+  let mut paths: Vec<_> = match sig.validate_json(&value, &types()?) {
+    Ok(()) => vec![],
+    Err(errors) => errors.into_iter().map(|e| e.path).collect(),
+  };
+  paths.sort();
+  Ok(paths)
+}
+
+#[test_log::test]
+fn test_valid_values() -> Result<()> {
+  let cases = vec![
+    ("u8", json!(255)),
+    ("i8", json!(-128)),
+    ("f32", json!(1)),
+    ("f64", json!(1.5)),
+    ("string", json!("hello")),
+    ("bool", json!(true)),
+    ("bytes", json!([0, 1, 255])),
+    ("value", json!({"anything": [1, "goes"]})),
+    ("u32?", json!(null)),
+    ("string[]", json!(["a", "b"])),
+    ("{string: u32}", json!({"a": 1, "b": 2})),
+    ("{u32: bool}", json!({"1": true})),
+    ("ref:User", json!({"name": "Jane", "age": 30})),
+    ("ref:#/types/User", json!({"name": "Jane", "age": 30, "nickname": "J"})),
+    ("ref:User[]", json!([{"name": "Jane", "age": 30}])),
+    ("enum(fast|accurate)", json!("fast")),
+    ("union:kind(User|#/types/Bot)", json!({"kind": "Bot", "model": "r2"})),
+    (
+      "union:kind(User|Bot)",
+      json!({"kind": "User", "name": "Jane", "age": 30}),
+    ),
+  ];
+  for (sig, value) in cases {
+    assert_eq!(
+      error_paths(sig, value.clone())?,
+      Vec::<String>::new(),
+      "{} {}",
+      sig,
+      value
+    );
+  }
+  Ok(())
+}
+
+#[test_log::test]
+fn test_invalid_values() -> Result<()> {
+  assert_eq!(error_paths("u8", json!(256))?, vec!["$"]);
+  assert_eq!(error_paths("i32", json!(1.5))?, vec!["$"]);
+  assert_eq!(error_paths("string", json!(null))?, vec!["$"]);
+  assert_eq!(error_paths("bytes", json!([256]))?, vec!["$"]);
+  assert_eq!(
+    error_paths("string[]", json!(["a", 1, "c", false]))?,
+    vec!["$[1]", "$[3]"]
+  );
+  assert_eq!(error_paths("{u32: bool}", json!({"x": 1}))?, vec!["$[x]", "$[x]"]);
+  assert_eq!(error_paths("ref:Missing", json!({}))?, vec!["$"]);
+  assert_eq!(
+    error_paths("ref:User[]", json!([{"name": 1, "age": 30}, {"extra": true}]))?,
+    vec!["$[0].name", "$[1].age", "$[1].extra", "$[1].name"]
+  );
+  assert_eq!(error_paths("enum(fast|accurate)", json!("slow"))?, vec!["$"]);
+  assert_eq!(error_paths("enum(fast|accurate)", json!(1))?, vec!["$"]);
+  assert_eq!(
+    error_paths("union:kind(User|Bot)", json!({"model": "r2"}))?,
+    vec!["$.kind"]
+  );
+  assert_eq!(
+    error_paths("union:kind(User|Bot)", json!({"kind": "Cat"}))?,
+    vec!["$.kind"]
+  );
+  assert_eq!(
+    error_paths("union:kind(User|Bot)", json!({"kind": "Bot", "model": 2, "name": "x"}))?,
+    vec!["$.model", "$.name"]
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_error_messages() -> Result<()> {
+  let sig: TypeSignature = "u8[]".parse()?;
+  let errors = sig.validate_json(&json!([1, "two"]), &StructMap::new()).unwrap_err();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].to_string(), "$[1]: expected u8, found string");
+  Ok(())
+}
+
+#[test_log::test]
+fn test_map_keys() -> Result<()> {
+  let none = Vec::<String>::new();
+  assert_eq!(error_paths("{string?: u32}", json!({"1": 1, "true": 2}))?, none);
+  assert_eq!(error_paths("{string(min_len: 1): u32}", json!({"12": 1}))?, none);
+  assert_eq!(error_paths("{u8(min: 1, max: 10)?: bool}", json!({"5": true}))?, none);
+  assert_eq!(
+    error_paths("{u8(min: 1, max: 10): bool}", json!({"11": true, "x": false}))?,
+    vec!["$[11]", "$[x]"]
+  );
+  Ok(())
+}
+
+#[test_log::test]
+fn test_struct_defaults() -> Result<()> {
+  use vino_types::Metadata;
+
+  let mut types = types()?;
+  types.insert(
+    "Page",
+    StructSignature {
+      name: "Page".to_owned(),
+      fields: TypeMap::try_from(vec![("size", "u32"), ("cursor", "string")])?
+        .with_metadata("size", Metadata::new().with_default(json!(20))),
+      ..Default::default()
+    },
+  );
+  let sig: TypeSignature = "ref:Page".parse()?;
+  assert_eq!(sig.validate_json(&json!({"cursor": "abc"}), &types), Ok(()));
+  let errors = sig.validate_json(&json!({"size": 10}), &types).unwrap_err();
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].to_string(), "$.cursor: missing field");
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_validate_inputs() -> Result<()> {
+  use vino_transport::{MessageTransport, TransportMap};
+  use vino_types::ComponentSignature;
+
+  let mut component = ComponentSignature::new("create");
+  component.inputs = TypeMap::try_from(vec![("user", "ref:User"), ("tags", "string[]"), ("note", "string?")])?;
+
+  let mut payload = TransportMap::new();
+  payload.insert("user", MessageTransport::success(&json!({"name": "Jane", "age": 300})));
+  payload.insert("extra", MessageTransport::success(&true));
+
+  let mut paths: Vec<_> = component
+    .validate_inputs(&payload, &types()?)
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.path)
+    .collect();
+  paths.sort();
+  assert_eq!(paths, vec!["extra", "tags", "user.age"]);
+
+  payload.insert("tags", MessageTransport::success(&vec!["a"]));
+  payload.insert("user", MessageTransport::success(&json!({"name": "Jane", "age": 30})));
+  let _ = payload.consume_raw("extra");
+  assert_eq!(component.validate_inputs(&payload, &types()?), Ok(()));
+
+  Ok(())
+}
+
+#[cfg(feature = "transport")]
+#[test_log::test]
+fn test_apply_defaults() -> Result<()> {
+  use vino_transport::{MessageTransport, TransportMap};
+  use vino_types::{ComponentSignature, Metadata};
+
+  let mut component = ComponentSignature::new("list");
+  component.inputs = TypeMap::try_from(vec![("page_size", "u32"), ("author", "string")])?
+    .with_metadata("page_size", Metadata::new().with_default(json!(20)));
+
+  let mut payload = TransportMap::new();
+  payload.insert("author", MessageTransport::success(&"Jane"));
+  assert_eq!(component.validate_inputs(&payload, &types()?), Ok(()));
+
+  component.apply_defaults(&mut payload);
+  assert_eq!(payload.consume::<u32>("page_size")?, 20);
+
+  payload.insert("page_size", MessageTransport::success(&5));
+  component.apply_defaults(&mut payload);
+  assert_eq!(payload.consume::<u32>("page_size")?, 5);
+  Ok(())
+}
+
+#[cfg(feature = "invocation")]
+#[test_log::test]
+fn test_apply_internal() -> Result<()> {
+  use std::collections::HashMap;
+
+  use vino_entity::Entity;
+  use vino_transport::{InherentData, Invocation, MessageTransport, TransportMap};
+  use vino_types::{ComponentSignature, Datetime, Uuid};
+
+  let mut component = ComponentSignature::new("roll");
+  component.inputs = TypeMap::try_from(vec![
+    ("sides", "u8"),
+    ("seed", "internal:seed"),
+    ("at", "internal:timestamp"),
+    ("caller", "internal:origin"),
+    ("tx", "internal:tx_id"),
+    ("config", "internal:config"),
+  ])?;
+  component.config = TypeMap::try_from(vec![("mode", "string"), ("limit", "u8")])?;
+
+  let mut payload = TransportMap::new();
+  payload.insert("sides", MessageTransport::success(&6));
+  payload.insert("seed", MessageTransport::success(&1));
+  payload.with_config(HashMap::from([
+    ("mode".to_owned(), serde_value::Value::String("fair".to_owned())),
+    ("limit".to_owned(), serde_value::Value::U8(20)),
+  ]));
+  let mut invocation = Invocation::new(
+    Entity::component("games", "table"),
+    Entity::component("dice", "roll"),
+    payload,
+    Some(InherentData::new(42, 1_650_000_000_000)),
+  );
+  component.apply_internal(&mut invocation);
+  assert_eq!(component.validate_inputs(&invocation.payload, &types()?), Ok(()));
+
+  let payload = &mut invocation.payload;
+  assert_eq!(
+    payload.consume::<u64>("seed")?,
+    42,
+    "runtime values replace the caller's"
+  );
+  assert_eq!(
+    payload.consume::<Datetime>("at")?,
+    Datetime::from_unix_millis(1_650_000_000_000)
+  );
+  assert_eq!(payload.consume::<String>("caller")?, "ofp://games.prov/table");
+  assert_eq!(payload.consume::<Uuid>("tx")?.to_string(), invocation.tx_id.to_string());
+  assert_eq!(
+    payload.consume::<serde_json::Value>("config")?,
+    json!({"mode": "fair", "limit": 20})
+  );
+
+  let mut payload = TransportMap::new();
+  payload.insert("sides", MessageTransport::success(&6));
+  payload.insert("seed", MessageTransport::success(&1));
+  let mut invocation = Invocation::new_test("roll", Entity::component("dice", "roll"), payload, None);
+  invocation
+    .payload
+    .with_config(HashMap::from([("mode".to_owned(), serde_value::Value::U8(1))]));
+  component.apply_internal(&mut invocation);
+  assert!(!invocation.payload.contains("seed"));
+  let paths: Vec<_> = component
+    .validate_inputs(&invocation.payload, &types()?)
+    .unwrap_err()
+    .into_iter()
+    .map(|e| e.path)
+    .collect();
+  assert_eq!(paths, vec!["seed", "at", "config.mode", "config.limit"]);
+  Ok(())
+}